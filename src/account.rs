@@ -0,0 +1,491 @@
+//! Standard bitcoin accounts (BIP44, BIP49, BIP84 and BIP86)
+//!
+//! Derive the account extended public key of the standard derivation schemes
+//! from a seed, along with its output descriptor and its addresses. After a
+//! restore, comparing the first receive address with the one of the original
+//! wallet confirms that the phrase and the passphrase are the right ones.
+//!
+//! The accounts are derived along `m/{purpose}'/{coin}'/{account}'`, where the
+//! coin is 0 on mainnet and 1 on the testnets, and the addresses along
+//! `{chain}/{index}` from the account key.
+//!
+//! # Example
+//!
+//! ```
+//! # use bip39_dict::{ENGLISH, Mnemonics, Seed};
+//! use bip39_dict::account::{Account, Chain, Purpose};
+//! use bip39_dict::bip32::Network;
+//! # let mnemonics = Mnemonics::<12>::from_string(&ENGLISH, "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about").unwrap();
+//!
+//! let seed = Seed::<64>::from_mnemonics(&ENGLISH, &mnemonics, b"");
+//! let account = Account::from_seed(seed.as_ref(), Purpose::Bip84, Network::Mainnet, 0).unwrap();
+//! let address = account.address(Chain::Receive, 0).unwrap();
+//! assert!(address == "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu");
+//! ```
+use super::base58;
+use super::bech32;
+use super::bip32::{
+    hash160, Bip32Error, ChildNumber, ExtendedPrivateKey, ExtendedPublicKey, Network,
+};
+use cryptoxide::hashing::sha2::Sha256;
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use k256::elliptic_curve::PrimeField;
+use k256::{ProjectivePoint, PublicKey, Scalar};
+
+#[cfg(not(feature = "std"))]
+use core::fmt;
+#[cfg(feature = "std")]
+use std::fmt;
+
+/// Standard derivation schemes, each associated with a type of address
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Purpose {
+    /// BIP44: legacy pay to public key hash (P2PKH) addresses
+    Bip44,
+    /// BIP49: pay to witness public key hash nested in pay to script hash (P2SH-P2WPKH)
+    Bip49,
+    /// BIP84: native segwit pay to witness public key hash (P2WPKH)
+    Bip84,
+    /// BIP86: taproot key path spending (P2TR)
+    Bip86,
+}
+
+impl Purpose {
+    /// The purpose index, hardened in the derivation path
+    pub const fn index(self) -> u32 {
+        match self {
+            Purpose::Bip44 => 44,
+            Purpose::Bip49 => 49,
+            Purpose::Bip84 => 84,
+            Purpose::Bip86 => 86,
+        }
+    }
+
+    // SLIP-132 version of the account extended public key
+    const fn public_version(self, network: Network) -> [u8; 4] {
+        match (self, network) {
+            (Purpose::Bip44 | Purpose::Bip86, Network::Mainnet) => [0x04, 0x88, 0xb2, 0x1e],
+            (Purpose::Bip44 | Purpose::Bip86, Network::Testnet) => [0x04, 0x35, 0x87, 0xcf],
+            (Purpose::Bip49, Network::Mainnet) => [0x04, 0x9d, 0x7c, 0xb2],
+            (Purpose::Bip49, Network::Testnet) => [0x04, 0x4a, 0x52, 0x62],
+            (Purpose::Bip84, Network::Mainnet) => [0x04, 0xb2, 0x47, 0x46],
+            (Purpose::Bip84, Network::Testnet) => [0x04, 0x5f, 0x1c, 0xf6],
+        }
+    }
+}
+
+/// Chain of addresses of an account
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Chain {
+    /// External chain, for the addresses given to receive payments
+    Receive,
+    /// Internal chain, for the change outputs
+    Change,
+}
+
+impl Chain {
+    /// The index of the chain in the derivation path
+    pub const fn index(self) -> u32 {
+        match self {
+            Chain::Receive => 0,
+            Chain::Change => 1,
+        }
+    }
+}
+
+/// Account of a standard derivation scheme
+///
+/// Only the public part of the account is kept, so it can derive the
+/// addresses but not spend from them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Account {
+    purpose: Purpose,
+    master_fingerprint: [u8; 4],
+    index: u32,
+    xpub: ExtendedPublicKey,
+}
+
+impl Account {
+    /// Derive the account of the given index from the seed
+    pub fn from_seed(
+        seed: &[u8],
+        purpose: Purpose,
+        network: Network,
+        index: u32,
+    ) -> Result<Self, Bip32Error> {
+        Self::from_master(&ExtendedPrivateKey::master(seed, network)?, purpose, index)
+    }
+
+    /// Derive the account of the given index from the master key, on its network
+    pub fn from_master(
+        master: &ExtendedPrivateKey,
+        purpose: Purpose,
+        index: u32,
+    ) -> Result<Self, Bip32Error> {
+        let coin = match master.network() {
+            Network::Mainnet => 0,
+            Network::Testnet => 1,
+        };
+        let mut key = master.clone();
+        for i in [purpose.index(), coin, index] {
+            let child = ChildNumber::hardened(i).ok_or(Bip32Error::InvalidIndex(i))?;
+            key = key.derive_child(child)?;
+        }
+        Ok(Account {
+            purpose,
+            master_fingerprint: master.fingerprint(),
+            index,
+            xpub: key.to_public(),
+        })
+    }
+
+    /// The derivation scheme of the account
+    pub fn purpose(&self) -> Purpose {
+        self.purpose
+    }
+
+    /// The network of the account
+    pub fn network(&self) -> Network {
+        self.xpub.network()
+    }
+
+    /// The index of the account
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// The fingerprint of the master key the account is derived from
+    pub fn master_fingerprint(&self) -> [u8; 4] {
+        self.master_fingerprint
+    }
+
+    /// The account extended public key, displayed as a xpub (tpub on the testnets)
+    pub fn xpub(&self) -> &ExtendedPublicKey {
+        &self.xpub
+    }
+
+    /// The account extended public key, with the SLIP-132 version of the purpose:
+    /// xpub for BIP44 and BIP86, ypub for BIP49 and zpub for BIP84
+    /// (tpub, upub and vpub on the testnets)
+    pub fn slip132(&self) -> Slip132<'_> {
+        Slip132(self)
+    }
+
+    /// The output descriptor of the chain of addresses, with its checksum
+    pub fn descriptor(&self, chain: Chain) -> Descriptor<'_> {
+        Descriptor {
+            account: self,
+            chain,
+        }
+    }
+
+    /// The address at the given index of the chain
+    pub fn address(&self, chain: Chain, index: u32) -> Result<Address, Bip32Error> {
+        let chain = self.xpub.derive_child(ChildNumber::from(chain.index()))?;
+        self.address_of(&chain, index)
+    }
+
+    /// The first `count` addresses of the chain
+    pub fn addresses(
+        &self,
+        chain: Chain,
+        count: u32,
+    ) -> impl Iterator<Item = Result<Address, Bip32Error>> + '_ {
+        let chain = self.xpub.derive_child(ChildNumber::from(chain.index()));
+        (0..count).map(move |index| match &chain {
+            Ok(chain) => self.address_of(chain, index),
+            Err(e) => Err(*e),
+        })
+    }
+
+    /// Search the address among the first `count` addresses of both chains,
+    /// returning its chain and index if found.
+    ///
+    /// The indices of invalid child keys are skipped, as specified by BIP32,
+    /// and the search stops at the last normal index 2^31 - 1.
+    pub fn find_address(&self, address: &str, count: u32) -> Option<(Chain, u32)> {
+        for chain in [Chain::Receive, Chain::Change] {
+            for (index, candidate) in self.addresses(chain, count).enumerate() {
+                match candidate {
+                    Ok(candidate) if candidate == *address => return Some((chain, index as u32)),
+                    Err(Bip32Error::InvalidIndex(_)) => break,
+                    _ => {}
+                }
+            }
+        }
+        None
+    }
+
+    fn address_of(&self, chain: &ExtendedPublicKey, index: u32) -> Result<Address, Bip32Error> {
+        let key = chain.derive_child(normal(index)?)?;
+        Address::new(self.purpose, self.network(), key.public_key())
+    }
+}
+
+fn normal(index: u32) -> Result<ChildNumber, Bip32Error> {
+    ChildNumber::normal(index).ok_or(Bip32Error::InvalidIndex(index))
+}
+
+/// Account extended public key with its SLIP-132 version, from [`Account::slip132`]
+#[derive(Debug, Clone, Copy)]
+pub struct Slip132<'a>(&'a Account);
+
+impl<'a> fmt::Display for Slip132<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut data = self.0.xpub.serialize();
+        data[0..4].copy_from_slice(&self.0.purpose.public_version(self.0.network()));
+        base58::encode_check(&data, f)
+    }
+}
+
+/// Output descriptor of a chain of addresses, from [`Account::descriptor`]
+///
+/// It is displayed with the key origin and its checksum, as specified by
+/// [BIP380](https://github.com/bitcoin/bips/blob/master/bip-0380.mediawiki),
+/// e.g. `wpkh([73c5da0a/84'/0'/0']xpub.../0/*)#...`
+#[derive(Debug, Clone, Copy)]
+pub struct Descriptor<'a> {
+    account: &'a Account,
+    chain: Chain,
+}
+
+impl<'a> Descriptor<'a> {
+    fn write_body<F: fmt::Write>(&self, f: &mut F) -> fmt::Result {
+        let (open, close) = match self.account.purpose {
+            Purpose::Bip44 => ("pkh(", ")"),
+            Purpose::Bip49 => ("sh(wpkh(", "))"),
+            Purpose::Bip84 => ("wpkh(", ")"),
+            Purpose::Bip86 => ("tr(", ")"),
+        };
+        let xpub = &self.account.xpub;
+        let coin = match xpub.network() {
+            Network::Mainnet => 0,
+            Network::Testnet => 1,
+        };
+        f.write_str(open)?;
+        f.write_char('[')?;
+        for b in self.account.master_fingerprint {
+            write!(f, "{:02x}", b)?;
+        }
+        write!(
+            f,
+            "/{}'/{}'/{}']{}/{}/*",
+            self.account.purpose.index(),
+            coin,
+            self.account.index,
+            xpub,
+            self.chain.index()
+        )?;
+        f.write_str(close)
+    }
+}
+
+impl<'a> fmt::Display for Descriptor<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut writer = ChecksumWriter::new(f);
+        self.write_body(&mut writer)?;
+        let checksum = writer.finalize();
+        f.write_str("#")?;
+        // the checksum characters are ASCII
+        f.write_str(core::str::from_utf8(&checksum).map_err(|_| fmt::Error)?)
+    }
+}
+
+const DESCRIPTOR_CHARSET: &[u8] =
+    b"0123456789()[],'/*abcdefgh@:$%{}IJKLMNOPQRSTUVWXYZ&+-.;<=>?!^_|~ijklmnopqrstuvwxyzABCDEFGH`#\"\\ ";
+const CHECKSUM_CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+// forward the descriptor to the writer, while computing its checksum
+struct ChecksumWriter<'a, F> {
+    writer: &'a mut F,
+    c: u64,
+    class: u64,
+    class_count: u32,
+}
+
+impl<'a, F: fmt::Write> ChecksumWriter<'a, F> {
+    fn new(writer: &'a mut F) -> Self {
+        ChecksumWriter {
+            writer,
+            c: 1,
+            class: 0,
+            class_count: 0,
+        }
+    }
+
+    fn polymod(c: u64, value: u64) -> u64 {
+        let top = c >> 35;
+        let mut c = ((c & 0x7ffffffff) << 5) ^ value;
+        for (i, g) in [
+            0xf5dee51989,
+            0xa9fdca3312,
+            0x1bab10e32d,
+            0x3706b1677a,
+            0x644d626ffd,
+        ]
+        .iter()
+        .enumerate()
+        {
+            if (top >> i) & 1 == 1 {
+                c ^= g;
+            }
+        }
+        c
+    }
+
+    fn finalize(self) -> [u8; 8] {
+        let mut c = self.c;
+        if self.class_count > 0 {
+            c = Self::polymod(c, self.class);
+        }
+        for _ in 0..8 {
+            c = Self::polymod(c, 0);
+        }
+        c ^= 1;
+        core::array::from_fn(|j| CHECKSUM_CHARSET[((c >> (5 * (7 - j))) & 31) as usize])
+    }
+}
+
+impl<'a, F: fmt::Write> fmt::Write for ChecksumWriter<'a, F> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for ch in s.bytes() {
+            let pos = DESCRIPTOR_CHARSET
+                .iter()
+                .position(|c| *c == ch)
+                .ok_or(fmt::Error)? as u64;
+            self.c = Self::polymod(self.c, pos & 31);
+            self.class = self.class * 3 + (pos >> 5);
+            self.class_count += 1;
+            if self.class_count == 3 {
+                self.c = Self::polymod(self.c, self.class);
+                self.class = 0;
+                self.class_count = 0;
+            }
+        }
+        self.writer.write_str(s)
+    }
+}
+
+/// Bitcoin address of an account
+///
+/// The `Display` implementation shows the address in the format of the
+/// account purpose, and it can be compared directly with a string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Address {
+    purpose: Purpose,
+    network: Network,
+    program: [u8; 32],
+}
+
+impl Address {
+    fn new(purpose: Purpose, network: Network, public_key: &[u8; 33]) -> Result<Self, Bip32Error> {
+        let mut program = [0u8; 32];
+        match purpose {
+            Purpose::Bip44 | Purpose::Bip84 => program[0..20].copy_from_slice(&hash160(public_key)),
+            Purpose::Bip49 => {
+                // redeem script: OP_0 <hash160 of the public key>
+                let mut script = [0u8; 22];
+                script[0..2].copy_from_slice(&[0x00, 0x14]);
+                script[2..22].copy_from_slice(&hash160(public_key));
+                program[0..20].copy_from_slice(&hash160(&script));
+            }
+            Purpose::Bip86 => {
+                program = taproot_output_key(public_key).ok_or(Bip32Error::InvalidKey)?
+            }
+        }
+        Ok(Address {
+            purpose,
+            network,
+            program,
+        })
+    }
+
+    /// The type of the address
+    pub fn purpose(&self) -> Purpose {
+        self.purpose
+    }
+
+    /// The network of the address
+    pub fn network(&self) -> Network {
+        self.network
+    }
+
+    /// The hash (20 bytes) or the taproot output key (32 bytes) the address pays to
+    pub fn program(&self) -> &[u8] {
+        match self.purpose {
+            Purpose::Bip86 => &self.program,
+            _ => &self.program[0..20],
+        }
+    }
+}
+
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let hrp = match self.network {
+            Network::Mainnet => "bc",
+            Network::Testnet => "tb",
+        };
+        let version = match (self.purpose, self.network) {
+            (Purpose::Bip44, Network::Mainnet) => 0x00,
+            (Purpose::Bip44, Network::Testnet) => 0x6f,
+            (Purpose::Bip49, Network::Mainnet) => 0x05,
+            (Purpose::Bip49, Network::Testnet) => 0xc4,
+            (Purpose::Bip84, _) => return bech32::encode_segwit(hrp, 0, self.program(), f),
+            (Purpose::Bip86, _) => return bech32::encode_segwit(hrp, 1, self.program(), f),
+        };
+        let mut data = [version; 21];
+        data[1..21].copy_from_slice(self.program());
+        base58::encode_check(&data, f)
+    }
+}
+
+// compare the rendered address with a string, without allocating
+struct Compare<'a>(&'a [u8]);
+
+impl<'a> fmt::Write for Compare<'a> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        match self.0.strip_prefix(s.as_bytes()) {
+            Some(rest) => {
+                self.0 = rest;
+                Ok(())
+            }
+            None => Err(fmt::Error),
+        }
+    }
+}
+
+impl PartialEq<str> for Address {
+    fn eq(&self, other: &str) -> bool {
+        let mut compare = Compare(other.as_bytes());
+        fmt::Write::write_fmt(&mut compare, format_args!("{}", self)).is_ok()
+            && compare.0.is_empty()
+    }
+}
+
+impl<'a> PartialEq<&'a str> for Address {
+    fn eq(&self, other: &&'a str) -> bool {
+        self == *other
+    }
+}
+
+// BIP341 output key of the BIP86 key path only spending: P + H_TapTweak(P)G
+fn taproot_output_key(public_key: &[u8; 33]) -> Option<[u8; 32]> {
+    // the internal key is the point with the same x and an even y
+    let mut internal = *public_key;
+    internal[0] = 0x02;
+    let point = PublicKey::from_sec1_bytes(&internal).ok()?.to_projective();
+
+    let tag = Sha256::new().update(b"TapTweak").finalize();
+    let tweak = Sha256::new()
+        .update(&tag)
+        .update(&tag)
+        .update(&internal[1..33])
+        .finalize();
+    let tweak: Option<Scalar> = Scalar::from_repr(tweak.into()).into();
+
+    let output = (point + ProjectivePoint::GENERATOR * tweak?).to_affine();
+    let encoded = output.to_encoded_point(true);
+    let mut out = [0u8; 32];
+    out.copy_from_slice(encoded.x()?);
+    Some(out)
+}
@@ -0,0 +1,222 @@
+//! AEZ (v5) enciphering of short messages
+//!
+//! Only what aezeed needs is implemented: the AEZ hash of the nonce and the
+//! associated data, and AEZ-tiny, which enciphers the messages of 16 to 31 bytes
+//! (the plaintext and its `tau` bytes of zeros) with a Feistel network of 8 rounds.
+//!
+//! The AES rounds are computed here, as cryptoxide only exposes the whole cipher.
+use crate::ct;
+use cryptoxide::hashing::blake2b::Blake2b;
+
+type Block = [u8; 16];
+
+const ZERO: Block = [0; 16];
+
+#[rustfmt::skip]
+const SBOX: [u8; 256] = [
+    0x63, 0x7c, 0x77, 0x7b, 0xf2, 0x6b, 0x6f, 0xc5, 0x30, 0x01, 0x67, 0x2b, 0xfe, 0xd7, 0xab, 0x76,
+    0xca, 0x82, 0xc9, 0x7d, 0xfa, 0x59, 0x47, 0xf0, 0xad, 0xd4, 0xa2, 0xaf, 0x9c, 0xa4, 0x72, 0xc0,
+    0xb7, 0xfd, 0x93, 0x26, 0x36, 0x3f, 0xf7, 0xcc, 0x34, 0xa5, 0xe5, 0xf1, 0x71, 0xd8, 0x31, 0x15,
+    0x04, 0xc7, 0x23, 0xc3, 0x18, 0x96, 0x05, 0x9a, 0x07, 0x12, 0x80, 0xe2, 0xeb, 0x27, 0xb2, 0x75,
+    0x09, 0x83, 0x2c, 0x1a, 0x1b, 0x6e, 0x5a, 0xa0, 0x52, 0x3b, 0xd6, 0xb3, 0x29, 0xe3, 0x2f, 0x84,
+    0x53, 0xd1, 0x00, 0xed, 0x20, 0xfc, 0xb1, 0x5b, 0x6a, 0xcb, 0xbe, 0x39, 0x4a, 0x4c, 0x58, 0xcf,
+    0xd0, 0xef, 0xaa, 0xfb, 0x43, 0x4d, 0x33, 0x85, 0x45, 0xf9, 0x02, 0x7f, 0x50, 0x3c, 0x9f, 0xa8,
+    0x51, 0xa3, 0x40, 0x8f, 0x92, 0x9d, 0x38, 0xf5, 0xbc, 0xb6, 0xda, 0x21, 0x10, 0xff, 0xf3, 0xd2,
+    0xcd, 0x0c, 0x13, 0xec, 0x5f, 0x97, 0x44, 0x17, 0xc4, 0xa7, 0x7e, 0x3d, 0x64, 0x5d, 0x19, 0x73,
+    0x60, 0x81, 0x4f, 0xdc, 0x22, 0x2a, 0x90, 0x88, 0x46, 0xee, 0xb8, 0x14, 0xde, 0x5e, 0x0b, 0xdb,
+    0xe0, 0x32, 0x3a, 0x0a, 0x49, 0x06, 0x24, 0x5c, 0xc2, 0xd3, 0xac, 0x62, 0x91, 0x95, 0xe4, 0x79,
+    0xe7, 0xc8, 0x37, 0x6d, 0x8d, 0xd5, 0x4e, 0xa9, 0x6c, 0x56, 0xf4, 0xea, 0x65, 0x7a, 0xae, 0x08,
+    0xba, 0x78, 0x25, 0x2e, 0x1c, 0xa6, 0xb4, 0xc6, 0xe8, 0xdd, 0x74, 0x1f, 0x4b, 0xbd, 0x8b, 0x8a,
+    0x70, 0x3e, 0xb5, 0x66, 0x48, 0x03, 0xf6, 0x0e, 0x61, 0x35, 0x57, 0xb9, 0x86, 0xc1, 0x1d, 0x9e,
+    0xe1, 0xf8, 0x98, 0x11, 0x69, 0xd9, 0x8e, 0x94, 0x9b, 0x1e, 0x87, 0xe9, 0xce, 0x55, 0x28, 0xdf,
+    0x8c, 0xa1, 0x89, 0x0d, 0xbf, 0xe6, 0x42, 0x68, 0x41, 0x99, 0x2d, 0x0f, 0xb0, 0x54, 0xbb, 0x16,
+];
+
+fn xtime(a: u8) -> u8 {
+    (a << 1) ^ (((a >> 7) & 1) * 0x1b)
+}
+
+/// One AES encryption round: SubBytes, ShiftRows, MixColumns and AddRoundKey
+fn aes_round(state: &mut Block, key: &Block) {
+    let mut t = [0u8; 16];
+    for c in 0..4 {
+        for r in 0..4 {
+            t[4 * c + r] = SBOX[state[4 * ((c + r) % 4) + r] as usize];
+        }
+    }
+    for c in 0..4 {
+        let [a0, a1, a2, a3] = [t[4 * c], t[4 * c + 1], t[4 * c + 2], t[4 * c + 3]];
+        state[4 * c] = xtime(a0) ^ xtime(a1) ^ a1 ^ a2 ^ a3 ^ key[4 * c];
+        state[4 * c + 1] = a0 ^ xtime(a1) ^ xtime(a2) ^ a2 ^ a3 ^ key[4 * c + 1];
+        state[4 * c + 2] = a0 ^ a1 ^ xtime(a2) ^ xtime(a3) ^ a3 ^ key[4 * c + 2];
+        state[4 * c + 3] = xtime(a0) ^ a0 ^ a1 ^ a2 ^ xtime(a3) ^ key[4 * c + 3];
+    }
+}
+
+fn xor(a: &Block, b: &Block) -> Block {
+    let mut out = *a;
+    for (o, b) in out.iter_mut().zip(b.iter()) {
+        *o ^= b
+    }
+    out
+}
+
+/// Multiplication by `n` in GF(2^128)
+fn mul(mut n: u32, x: &Block) -> Block {
+    let mut acc = ZERO;
+    let mut x = u128::from_be_bytes(*x);
+    while n > 0 {
+        if n & 1 == 1 {
+            acc = xor(&acc, &x.to_be_bytes());
+        }
+        x = (x << 1) ^ ((x >> 127) * 0x87);
+        n >>= 1;
+    }
+    acc
+}
+
+/// Read `n` bits of `bytes` from the bit `start`, in the top bits of the result
+fn read_bits(bytes: &[u8], start: usize, n: usize) -> u128 {
+    let mut v = 0;
+    for k in 0..n {
+        let bit = (bytes[(start + k) / 8] >> (7 - (start + k) % 8)) & 1;
+        v |= (bit as u128) << (127 - k);
+    }
+    v
+}
+
+/// Write the `n` top bits of `v` in `bytes` from the bit `start`
+fn write_bits(bytes: &mut [u8], start: usize, n: usize, v: u128) {
+    for k in 0..n {
+        let bit = ((v >> (127 - k)) & 1) as u8;
+        let (byte, shift) = ((start + k) / 8, 7 - (start + k) % 8);
+        bytes[byte] = (bytes[byte] & !(1 << shift)) | (bit << shift);
+    }
+}
+
+/// AEZ keyed with the I, J and L subkeys extracted from the key
+pub(super) struct Aez {
+    i: Block,
+    j: Block,
+    l: Block,
+}
+
+impl Drop for Aez {
+    fn drop(&mut self) {
+        ct::zeroize(&mut self.i);
+        ct::zeroize(&mut self.j);
+        ct::zeroize(&mut self.l);
+    }
+}
+
+impl Aez {
+    /// Extract the subkeys of the key with BLAKE2b-384
+    pub(super) fn new(key: &[u8]) -> Self {
+        let mut extracted = Blake2b::<384>::new().update(key).finalize();
+        let mut aez = Aez {
+            i: ZERO,
+            j: ZERO,
+            l: ZERO,
+        };
+        aez.i.copy_from_slice(&extracted[0..16]);
+        aez.j.copy_from_slice(&extracted[16..32]);
+        aez.l.copy_from_slice(&extracted[32..48]);
+        ct::zeroize(&mut extracted);
+        aez
+    }
+
+    /// The tweakable block cipher E^{j,i}, for j >= 0
+    fn e(&self, j: u32, i: u32, x: &Block) -> Block {
+        let offset = xor(
+            &xor(&mul(j, &self.j), &mul(1 << i.div_ceil(8), &self.i)),
+            &mul(i % 8, &self.l),
+        );
+        let mut state = xor(x, &offset);
+        for key in [&self.j, &self.i, &self.l, &ZERO] {
+            aes_round(&mut state, key);
+        }
+        state
+    }
+
+    /// The AEZ hash of `tau` (in bytes) and of the tweak vector
+    fn hash(&self, tau: usize, tweaks: &[&[u8]]) -> Block {
+        let mut delta = self.e(3, 1, &((tau as u128) * 8).to_be_bytes());
+        for (k, tweak) in tweaks.iter().enumerate() {
+            let j = 4 + k as u32;
+            let mut chunks = tweak.chunks_exact(16);
+            for (i, chunk) in (1..).zip(chunks.by_ref()) {
+                let mut block = ZERO;
+                block.copy_from_slice(chunk);
+                delta = xor(&delta, &self.e(j, i, &block));
+            }
+            let rem = chunks.remainder();
+            if !rem.is_empty() || tweak.is_empty() {
+                let mut block = ZERO;
+                block[..rem.len()].copy_from_slice(rem);
+                block[rem.len()] = 0x80;
+                delta = xor(&delta, &self.e(j, 0, &block));
+            }
+        }
+        delta
+    }
+
+    /// AEZ-tiny, enciphering or deciphering in place a message of 16 to 31 bytes
+    fn tiny(&self, delta: &Block, x: &mut [u8], decipher: bool) {
+        debug_assert!((16..32).contains(&x.len()));
+        let n = x.len() * 4;
+        let mask = !0u128 << (128 - n);
+        let round = |v: u128, counter: u8| -> u128 {
+            let mut block = xor(&(v | (1 << (127 - n))).to_be_bytes(), delta);
+            block[15] ^= counter;
+            u128::from_be_bytes(self.e(0, 6, &block)) & mask
+        };
+        let mut left = read_bits(x, 0, n);
+        let mut right = read_bits(x, n, n);
+        for k in 0..4u8 {
+            let (a, b) = if decipher {
+                (7 - 2 * k, 6 - 2 * k)
+            } else {
+                (2 * k, 2 * k + 1)
+            };
+            left ^= round(right, a);
+            right ^= round(left, b);
+        }
+        write_bits(x, 0, n, right);
+        write_bits(x, n, n, left);
+    }
+
+    /// Encrypt the plaintext of M bytes, with an empty nonce, in C bytes:
+    /// the authenticator is made of the C - M bytes of zeros enciphered with it
+    pub(super) fn encrypt<const M: usize, const C: usize>(
+        &self,
+        ad: &[u8],
+        plaintext: &[u8; M],
+    ) -> [u8; C] {
+        let delta = self.hash(C - M, &[&[], ad]);
+        let mut out = [0u8; C];
+        out[..M].copy_from_slice(plaintext);
+        self.tiny(&delta, &mut out, false);
+        out
+    }
+
+    /// Decrypt the ciphertext of C bytes in M bytes, checking the authenticator
+    pub(super) fn decrypt<const M: usize, const C: usize>(
+        &self,
+        ad: &[u8],
+        ciphertext: &[u8; C],
+    ) -> Option<[u8; M]> {
+        let delta = self.hash(C - M, &[&[], ad]);
+        let mut x = *ciphertext;
+        self.tiny(&delta, &mut x, true);
+        let valid = x[M..].iter().fold(0, |acc, b| acc | b) == 0;
+        let mut out = [0u8; M];
+        out.copy_from_slice(&x[..M]);
+        ct::zeroize(&mut x);
+        if valid {
+            Some(out)
+        } else {
+            ct::zeroize(&mut out);
+            None
+        }
+    }
+}
@@ -0,0 +1,270 @@
+//! aezeed: the seed format of the LND Lightning wallets
+//!
+//! An aezeed is a 24 words phrase, in the BIP39 English wordlist, encoding 33 bytes
+//! (without the BIP39 checksum):
+//!
+//! ```text
+//! version (1) | ciphertext (23) | salt (5) | CRC32-C of the previous bytes (4)
+//! ```
+//!
+//! The ciphertext is the AEZ encryption of the internal version (1 byte), the
+//! birthday of the wallet in days since the Bitcoin genesis block (2 bytes) and
+//! the entropy (16 bytes), with 4 bytes of authenticator. The key is derived
+//! from the passphrase (`"aezeed"` when empty) and the salt with scrypt
+//! (N = 32768, r = 8, p = 1), and the version and salt are authenticated as
+//! associated data, so that a wrong passphrase is detected.
+//!
+//! # Example
+//!
+//! ```
+//! use bip39_dict::{aezeed::{self, CipherSeed}, Entropy, ENGLISH};
+//!
+//! let seed = CipherSeed {
+//!     internal_version: 0,
+//!     birthday: 3365,
+//!     entropy: Entropy([0x42; 16]),
+//!     salt: *b"salt1",
+//! };
+//! let mnemonics = seed.to_mnemonics(b"passphrase").unwrap();
+//! assert_eq!(aezeed::salt(&mnemonics).unwrap(), *b"salt1");
+//!
+//! let recovered = CipherSeed::from_mnemonics(&mnemonics, b"passphrase").unwrap();
+//! assert_eq!(recovered, seed);
+//! assert!(CipherSeed::from_mnemonics(&mnemonics, b"wrong").is_err());
+//! ```
+mod aez;
+
+use super::ct;
+use super::entropy::{Entropy, EntropyError};
+use super::mnemonics::Mnemonics;
+use cryptoxide::scrypt::{scrypt, ScryptParams};
+#[cfg(feature = "rand")]
+use rand_core::{CryptoRng, RngCore};
+
+#[cfg(not(feature = "std"))]
+use core::fmt;
+#[cfg(feature = "std")]
+use {std::error::Error, std::fmt};
+
+/// The version of the aezeed format, the first byte of the enciphered seed
+pub const CIPHER_SEED_VERSION: u8 = 0;
+
+/// The passphrase used when the given passphrase is empty
+pub const DEFAULT_PASSPHRASE: &[u8] = b"aezeed";
+
+/// Unix timestamp of the Bitcoin genesis block, the origin of the birthdays
+pub const BITCOIN_GENESIS_TIMESTAMP: u64 = 1231006505;
+
+const SCRYPT_LOG_N: u8 = 15;
+const PLAINTEXT_SIZE: usize = 19;
+const CIPHERTEXT_SIZE: usize = 23;
+const SALT_SIZE: usize = 5;
+const ENCIPHERED_SIZE: usize = 33;
+
+/// Possible errors when deciphering an aezeed
+#[derive(Debug, Clone)]
+pub enum AezeedError {
+    /// The version of the enciphered seed is not supported
+    UnsupportedVersion(u8),
+    /// The CRC32-C of the enciphered seed doesn't match
+    InvalidChecksum,
+    /// The ciphertext can't be authenticated: the passphrase is wrong
+    InvalidPassphrase,
+    /// The conversion between the phrase and the enciphered seed failed
+    Entropy(EntropyError),
+}
+
+impl fmt::Display for AezeedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnsupportedVersion(version) => write!(f, "Unsupported version {}", version),
+            Self::InvalidChecksum => write!(f, "Invalid checksum"),
+            Self::InvalidPassphrase => write!(f, "Invalid passphrase"),
+            Self::Entropy(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for AezeedError {}
+
+impl From<EntropyError> for AezeedError {
+    fn from(e: EntropyError) -> Self {
+        AezeedError::Entropy(e)
+    }
+}
+
+/// The content of an aezeed
+///
+/// The salt is expected to be random, generated along with the entropy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CipherSeed {
+    /// Version of the derivation of the wallet from the entropy
+    pub internal_version: u8,
+    /// Creation day of the wallet, in days since the Bitcoin genesis block
+    pub birthday: u16,
+    /// The entropy of the wallet
+    pub entropy: Entropy<16>,
+    /// Salt of the key derivation
+    pub salt: [u8; SALT_SIZE],
+}
+
+fn crc32c(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in data {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = (crc >> 1) ^ ((crc & 1) * 0x82F6_3B78);
+        }
+    }
+    !crc
+}
+
+fn cipher(passphrase: &[u8], salt: &[u8; SALT_SIZE], log_n: u8) -> aez::Aez {
+    let passphrase = if passphrase.is_empty() {
+        DEFAULT_PASSPHRASE
+    } else {
+        passphrase
+    };
+    let mut key = [0u8; 32];
+    scrypt(passphrase, salt, &ScryptParams::new(log_n, 8, 1), &mut key);
+    let aez = aez::Aez::new(&key);
+    ct::zeroize(&mut key);
+    aez
+}
+
+fn associated_data(salt: &[u8; SALT_SIZE]) -> [u8; 1 + SALT_SIZE] {
+    let mut ad = [CIPHER_SEED_VERSION; 1 + SALT_SIZE];
+    ad[1..].copy_from_slice(salt);
+    ad
+}
+
+/// Check the version and the checksum of the enciphered seed
+fn enciphered(mnemonics: &Mnemonics<24>) -> Result<Entropy<ENCIPHERED_SIZE>, AezeedError> {
+    let bytes = Entropy::<ENCIPHERED_SIZE>::from_mnemonics::<24, 0>(mnemonics)?;
+    if bytes.0[0] != CIPHER_SEED_VERSION {
+        return Err(AezeedError::UnsupportedVersion(bytes.0[0]));
+    }
+    let (data, checksum) = bytes.0.split_at(ENCIPHERED_SIZE - 4);
+    if crc32c(data).to_be_bytes() != checksum {
+        return Err(AezeedError::InvalidChecksum);
+    }
+    Ok(bytes)
+}
+
+/// Get the salt of the aezeed phrase, checking its version and checksum
+///
+/// This doesn't need the passphrase, and doesn't check it.
+pub fn salt(mnemonics: &Mnemonics<24>) -> Result<[u8; SALT_SIZE], AezeedError> {
+    let mut bytes = enciphered(mnemonics)?;
+    let mut salt = [0u8; SALT_SIZE];
+    salt.copy_from_slice(&bytes.0[1 + CIPHERTEXT_SIZE..1 + CIPHERTEXT_SIZE + SALT_SIZE]);
+    ct::zeroize(&mut bytes.0);
+    Ok(salt)
+}
+
+impl CipherSeed {
+    /// Generate a new seed of the given birthday, with the internal version 0,
+    /// and an entropy and a salt drawn from the given cryptographically secure
+    /// random generator (with the `rand` feature)
+    ///
+    /// # Error
+    ///
+    /// Any error from the random generator is returned.
+    #[cfg(feature = "rand")]
+    pub fn generate<R: RngCore + CryptoRng>(
+        birthday: u16,
+        rng: &mut R,
+    ) -> Result<Self, rand_core::Error> {
+        let entropy = Entropy::generate_from_rng(rng)?;
+        let mut salt = [0u8; SALT_SIZE];
+        rng.try_fill_bytes(&mut salt)?;
+        Ok(CipherSeed {
+            internal_version: 0,
+            birthday,
+            entropy,
+            salt,
+        })
+    }
+
+    /// The birthday of a wallet created at the given unix timestamp
+    pub fn birthday_from_timestamp(timestamp: u64) -> u16 {
+        let days = timestamp.saturating_sub(BITCOIN_GENESIS_TIMESTAMP) / 86400;
+        u16::try_from(days).unwrap_or(u16::MAX)
+    }
+
+    /// Unix timestamp of the birthday of the wallet
+    pub fn birthday_timestamp(&self) -> u64 {
+        BITCOIN_GENESIS_TIMESTAMP + self.birthday as u64 * 86400
+    }
+
+    /// Encipher the seed with the passphrase, into the 24 words of the aezeed
+    ///
+    /// An empty passphrase is replaced by [`DEFAULT_PASSPHRASE`].
+    pub fn to_mnemonics(&self, passphrase: &[u8]) -> Result<Mnemonics<24>, AezeedError> {
+        self.to_mnemonics_with_cost(passphrase, SCRYPT_LOG_N)
+    }
+
+    // with the scrypt cost N = 2^log_n, lowered to 16 in the LND test vectors
+    pub(crate) fn to_mnemonics_with_cost(
+        &self,
+        passphrase: &[u8],
+        log_n: u8,
+    ) -> Result<Mnemonics<24>, AezeedError> {
+        let mut plaintext = [0u8; PLAINTEXT_SIZE];
+        plaintext[0] = self.internal_version;
+        plaintext[1..3].copy_from_slice(&self.birthday.to_be_bytes());
+        plaintext[3..].copy_from_slice(&self.entropy.0);
+        let ciphertext: [u8; CIPHERTEXT_SIZE] =
+            cipher(passphrase, &self.salt, log_n).encrypt(&associated_data(&self.salt), &plaintext);
+        ct::zeroize(&mut plaintext);
+
+        let mut bytes = Entropy([0u8; ENCIPHERED_SIZE]);
+        bytes.0[0] = CIPHER_SEED_VERSION;
+        bytes.0[1..1 + CIPHERTEXT_SIZE].copy_from_slice(&ciphertext);
+        bytes.0[1 + CIPHERTEXT_SIZE..ENCIPHERED_SIZE - 4].copy_from_slice(&self.salt);
+        let checksum = crc32c(&bytes.0[..ENCIPHERED_SIZE - 4]);
+        bytes.0[ENCIPHERED_SIZE - 4..].copy_from_slice(&checksum.to_be_bytes());
+        let mnemonics = bytes.to_mnemonics::<24, 0>();
+        ct::zeroize(&mut bytes.0);
+        Ok(mnemonics?)
+    }
+
+    /// Decipher the aezeed phrase with the passphrase
+    ///
+    /// An empty passphrase is replaced by [`DEFAULT_PASSPHRASE`].
+    pub fn from_mnemonics(
+        mnemonics: &Mnemonics<24>,
+        passphrase: &[u8],
+    ) -> Result<Self, AezeedError> {
+        Self::from_mnemonics_with_cost(mnemonics, passphrase, SCRYPT_LOG_N)
+    }
+
+    // with the scrypt cost N = 2^log_n, see `to_mnemonics_with_cost`
+    pub(crate) fn from_mnemonics_with_cost(
+        mnemonics: &Mnemonics<24>,
+        passphrase: &[u8],
+        log_n: u8,
+    ) -> Result<Self, AezeedError> {
+        let mut bytes = enciphered(mnemonics)?;
+        let mut ciphertext = [0u8; CIPHERTEXT_SIZE];
+        ciphertext.copy_from_slice(&bytes.0[1..1 + CIPHERTEXT_SIZE]);
+        let mut salt = [0u8; SALT_SIZE];
+        salt.copy_from_slice(&bytes.0[1 + CIPHERTEXT_SIZE..ENCIPHERED_SIZE - 4]);
+        ct::zeroize(&mut bytes.0);
+
+        let mut plaintext: [u8; PLAINTEXT_SIZE] = cipher(passphrase, &salt, log_n)
+            .decrypt(&associated_data(&salt), &ciphertext)
+            .ok_or(AezeedError::InvalidPassphrase)?;
+        let mut entropy = Entropy([0u8; 16]);
+        entropy.0.copy_from_slice(&plaintext[3..]);
+        let seed = CipherSeed {
+            internal_version: plaintext[0],
+            birthday: u16::from_be_bytes([plaintext[1], plaintext[2]]),
+            entropy,
+            salt,
+        };
+        ct::zeroize(&mut plaintext);
+        Ok(seed)
+    }
+}
@@ -0,0 +1,50 @@
+//! Algorand mnemonics
+//!
+//! Algorand writes a 32 bytes private key (the ed25519 seed) as 25 words of the
+//! BIP39 English wordlist, but not as BIP39 does:
+//!
+//! * the key is split in 24 words of 11 bits, least significant bits first,
+//!   the last word holding the 3 remaining bits padded with 8 zero bits;
+//! * the 25th word is the checksum: the first 11 bits (least significant bits
+//!   first) of the SHA-512/256 of the key.
+//!
+//! # Example
+//!
+//! ```
+//! use bip39_dict::{algorand, Mnemonics, ENGLISH};
+//!
+//! let phrase = "advice pudding treat near rule blouse same whisper inner electric quit surface sunny dismiss leader blood seat clown cost exist hospital century reform able sponsor";
+//! let mnemonics = Mnemonics::<25>::from_string(&ENGLISH, phrase).unwrap();
+//! let key = algorand::from_mnemonics(&mnemonics).unwrap();
+//! assert_eq!(key.0[0..4], [0x20, 0x48, 0xab, 0xcf]);
+//! assert_eq!(algorand::to_mnemonics(&key).unwrap(), mnemonics);
+//! ```
+use super::bits::Lsb;
+use super::entropy::{Entropy, EntropyError};
+use super::mnemonics::Mnemonics;
+use cryptoxide::hashing::sha2::Sha512Trunc256;
+
+// the 19 bits following the key: the 8 padding bits of the 24th word, and
+// the first 11 bits of the SHA-512/256 of the key, the 25th word
+fn checksum(key: &Entropy<32>) -> [u8; 32] {
+    let digest = Sha512Trunc256::new().update(&key.0).finalize();
+    let mut data = [0u8; 32];
+    data[1..3].copy_from_slice(&digest[0..2]);
+    data
+}
+
+/// Encode the 32 bytes key in 25 words
+///
+/// With the `hardened` feature, the encoding is checked by decoding the words
+/// back, and [`EntropyError::FaultDetected`] is returned if they don't match.
+pub fn to_mnemonics(key: &Entropy<32>) -> Result<Mnemonics<25>, EntropyError> {
+    key.to_mnemonics_with::<25, 19, Lsb>(checksum)
+}
+
+/// Decode the 32 bytes key from the 25 words, checking the checksum word
+///
+/// The padding bits of the 24th word must be zeros, otherwise
+/// [`EntropyError::ChecksumInvalid`] is returned as for an invalid checksum word.
+pub fn from_mnemonics(mnemonics: &Mnemonics<25>) -> Result<Entropy<32>, EntropyError> {
+    Entropy::from_mnemonics_with::<25, 19, Lsb>(mnemonics, checksum)
+}
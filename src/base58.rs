@@ -0,0 +1,147 @@
+//! Base58 and Base58Check encoding, as used by bitcoin
+//!
+//! Both directions work on fixed size buffers, and support inputs
+//! up to [`MAX_BYTES`] bytes, which is enough for extended keys and addresses.
+use core::fmt;
+use cryptoxide::hashing::sha2::Sha256;
+
+const ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// maximum size in bytes of the data supported
+pub(crate) const MAX_BYTES: usize = 96;
+// ceil(MAX_BYTES * log(256) / log(58))
+const MAX_DIGITS: usize = 132;
+
+fn checksum(data: &[u8]) -> [u8; 4] {
+    let hash = Sha256::new()
+        .update(&Sha256::new().update(data).finalize())
+        .finalize();
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+/// Write the base58 encoding of data (at most MAX_BYTES) to the writer
+pub(crate) fn encode<F: fmt::Write>(data: &[u8], writer: &mut F) -> fmt::Result {
+    assert!(data.len() <= MAX_BYTES);
+    let zeros = data.iter().take_while(|b| **b == 0).count();
+
+    // base58 digits, least significant first
+    let mut digits = [0u8; MAX_DIGITS];
+    let mut len = 0;
+    for b in &data[zeros..] {
+        let mut carry = *b as u32;
+        for d in digits[0..len].iter_mut() {
+            carry += (*d as u32) << 8;
+            *d = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits[len] = (carry % 58) as u8;
+            len += 1;
+            carry /= 58;
+        }
+    }
+
+    for _ in 0..zeros {
+        writer.write_char('1')?;
+    }
+    for d in digits[0..len].iter().rev() {
+        writer.write_char(ALPHABET[*d as usize] as char)?;
+    }
+    Ok(())
+}
+
+/// Write the base58 encoding of data (at most MAX_BYTES - 4) followed by its checksum
+pub(crate) fn encode_check<F: fmt::Write>(data: &[u8], writer: &mut F) -> fmt::Result {
+    assert!(data.len() + 4 <= MAX_BYTES);
+    let mut buf = [0u8; MAX_BYTES];
+    buf[0..data.len()].copy_from_slice(data);
+    buf[data.len()..data.len() + 4].copy_from_slice(&checksum(data));
+    encode(&buf[0..data.len() + 4], writer)
+}
+
+/// Decode the base58 string into the output, returning the number of bytes written
+///
+/// None is returned if the string is not valid base58, or doesn't fit the output
+pub(crate) fn decode(s: &str, output: &mut [u8]) -> Option<usize> {
+    // bytes of the value, least significant first
+    let mut bytes = [0u8; MAX_BYTES];
+    let mut len = 0;
+    let zeros = s.bytes().take_while(|c| *c == b'1').count();
+    for c in s.bytes().skip(zeros) {
+        let mut carry = ALPHABET.iter().position(|a| *a == c)? as u32;
+        for b in bytes[0..len].iter_mut() {
+            carry += (*b as u32) * 58;
+            *b = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            if len == MAX_BYTES {
+                return None;
+            }
+            bytes[len] = carry as u8;
+            len += 1;
+            carry >>= 8;
+        }
+    }
+
+    let total = zeros + len;
+    if total > output.len() {
+        return None;
+    }
+    output[0..zeros].fill(0);
+    for (o, b) in output[zeros..total]
+        .iter_mut()
+        .zip(bytes[0..len].iter().rev())
+    {
+        *o = *b;
+    }
+    Some(total)
+}
+
+/// Decode the base58 string and check its checksum, returning the number of bytes
+/// of data written into the output (excluding the checksum)
+pub(crate) fn decode_check(s: &str, output: &mut [u8]) -> Option<usize> {
+    let mut buf = [0u8; MAX_BYTES];
+    let len = decode(s, &mut buf)?.checked_sub(4)?;
+    if len > output.len() || checksum(&buf[0..len]) != buf[len..len + 4] {
+        return None;
+    }
+    output[0..len].copy_from_slice(&buf[0..len]);
+    Some(len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::String;
+
+    #[test]
+    fn roundtrip() {
+        let vectors: &[(&[u8], &str)] = &[
+            (b"", ""),
+            (b"hello world", "StV1DL6CwTryKyV"),
+            (&[0, 0, 0x28, 0x7f, 0xb4, 0xcd], "11233QC4"),
+            (&[0; 3], "111"),
+        ];
+        for (data, encoded) in vectors {
+            let mut s = String::new();
+            encode(data, &mut s).unwrap();
+            assert_eq!(&s, encoded);
+            let mut out = [0u8; 16];
+            let len = decode(encoded, &mut out).unwrap();
+            assert_eq!(&out[0..len], *data);
+        }
+        assert_eq!(decode("0OIl", &mut [0u8; 16]), None);
+    }
+
+    #[test]
+    fn check() {
+        // P2PKH address of the hash160 0x00..00
+        let mut s = String::new();
+        encode_check(&[0; 21], &mut s).unwrap();
+        assert_eq!(s, "1111111111111111111114oLvT2");
+        let mut out = [0u8; 21];
+        assert_eq!(decode_check(&s, &mut out), Some(21));
+        assert_eq!(decode_check("1111111111111111111114oLvT3", &mut out), None);
+    }
+}
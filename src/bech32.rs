@@ -0,0 +1,126 @@
+//! Bech32 and Bech32m encoding of segwit addresses
+//!
+//! Only the encoding is supported, written directly to a `fmt::Write`, as
+//! specified by [BIP173](https://github.com/bitcoin/bips/blob/master/bip-0173.mediawiki)
+//! for the witness version 0, and by
+//! [BIP350](https://github.com/bitcoin/bips/blob/master/bip-0350.mediawiki) for
+//! the later versions.
+use core::fmt;
+
+const CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+const GENERATOR: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+const BECH32_CONST: u32 = 1;
+const BECH32M_CONST: u32 = 0x2bc830a3;
+
+/// maximum size in bytes of the witness program
+pub(crate) const MAX_PROGRAM: usize = 40;
+// ceil(MAX_PROGRAM * 8 / 5)
+const MAX_PROGRAM_DIGITS: usize = 64;
+
+fn polymod(chk: u32, value: u8) -> u32 {
+    let top = chk >> 25;
+    let mut chk = ((chk & 0x1ffffff) << 5) ^ value as u32;
+    for (i, g) in GENERATOR.iter().enumerate() {
+        if (top >> i) & 1 == 1 {
+            chk ^= g;
+        }
+    }
+    chk
+}
+
+/// Write the segwit address of the witness program (2 to MAX_PROGRAM bytes) to the writer
+pub(crate) fn encode_segwit<F: fmt::Write>(
+    hrp: &str,
+    version: u8,
+    program: &[u8],
+    writer: &mut F,
+) -> fmt::Result {
+    assert!(version <= 16);
+    assert!((2..=MAX_PROGRAM).contains(&program.len()));
+
+    // witness version followed by the program regrouped in 5 bits, padded with zeros
+    let mut data = [0u8; MAX_PROGRAM_DIGITS + 1];
+    data[0] = version;
+    let mut len = 1;
+    let mut acc = 0u32;
+    let mut bits = 0;
+    for b in program {
+        acc = (acc << 8) | *b as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            data[len] = ((acc >> bits) & 31) as u8;
+            len += 1;
+        }
+    }
+    if bits > 0 {
+        data[len] = ((acc << (5 - bits)) & 31) as u8;
+        len += 1;
+    }
+
+    let mut chk = 1;
+    for c in hrp.bytes() {
+        chk = polymod(chk, c >> 5);
+    }
+    chk = polymod(chk, 0);
+    for c in hrp.bytes() {
+        chk = polymod(chk, c & 31);
+    }
+    for d in &data[0..len] {
+        chk = polymod(chk, *d);
+    }
+    for _ in 0..6 {
+        chk = polymod(chk, 0);
+    }
+    chk ^= if version == 0 {
+        BECH32_CONST
+    } else {
+        BECH32M_CONST
+    };
+
+    writer.write_str(hrp)?;
+    writer.write_char('1')?;
+    for d in &data[0..len] {
+        writer.write_char(CHARSET[*d as usize] as char)?;
+    }
+    for i in 0..6 {
+        writer.write_char(CHARSET[((chk >> (5 * (5 - i))) & 31) as usize] as char)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::String;
+
+    #[test]
+    fn segwit() {
+        let vectors: &[(&str, u8, &str, &str)] = &[
+            (
+                "bc",
+                0,
+                "751e76e8199196d454941c45d1b3a323f1433bd6",
+                "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+            ),
+            (
+                "tb",
+                0,
+                "1863143c14c5166804bd19203356da136c985678cd4d27a1b8c6329604903262",
+                "tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sl5k7",
+            ),
+            (
+                "bc",
+                1,
+                "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+                "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0",
+            ),
+            ("bc", 16, "751e", "bc1sw50qgdz25j"),
+        ];
+        for (hrp, version, program, address) in vectors {
+            let mut s = String::new();
+            encode_segwit(hrp, *version, &hex::decode(program).unwrap(), &mut s).unwrap();
+            assert_eq!(&s, address);
+        }
+    }
+}
@@ -0,0 +1,725 @@
+//! BIP32 hierarchical deterministic keys
+//!
+//! Derive the master extended private key from a seed, and the child keys
+//! along derivation paths such as `m/84'/0'/0'`, as specified by
+//! [BIP32](https://github.com/bitcoin/bips/blob/master/bip-0032.mediawiki).
+//!
+//! The extended keys can be serialized (and parsed) in the usual base58
+//! xprv/xpub (mainnet) and tprv/tpub (testnet) formats.
+use super::base58;
+use super::ct;
+use super::hmac::Hmac;
+use core::str::FromStr;
+use cryptoxide::hashing::ripemd160::Ripemd160;
+use cryptoxide::hashing::sha2::{Context512, Sha256};
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use k256::elliptic_curve::PrimeField;
+use k256::{ProjectivePoint, PublicKey, Scalar};
+
+#[cfg(not(feature = "std"))]
+use core::fmt;
+#[cfg(feature = "std")]
+use {std::error::Error, std::fmt};
+
+/// Size in bytes of a serialized extended key, before the base58check encoding
+pub const EXTENDED_KEY_SIZE: usize = 78;
+
+/// Possible errors when deriving or parsing BIP32 keys
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bip32Error {
+    /// The seed leads to an invalid master key, or is not between 16 and 64 bytes
+    InvalidSeed,
+    /// The child key at this index is invalid, the next index should be used instead
+    InvalidChild(ChildNumber),
+    /// Hardened children cannot be derived from a public key
+    HardenedFromPublic(ChildNumber),
+    /// The maximum depth of 255 is reached
+    MaxDepth,
+    /// The derivation path is not valid
+    InvalidPath,
+    /// The string is not valid base58check, or has not the size of an extended key
+    InvalidEncoding,
+    /// The version bytes of the serialized key are not known
+    UnknownVersion([u8; 4]),
+    /// The serialized key is not a valid key
+    InvalidKey,
+    /// The index is 2^31 or more, out of the range of the normal and hardened children
+    InvalidIndex(u32),
+}
+
+impl fmt::Display for Bip32Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidSeed => write!(f, "Invalid seed"),
+            Self::InvalidChild(child) => write!(f, "Invalid child key at index {}", child),
+            Self::HardenedFromPublic(child) => write!(
+                f,
+                "Cannot derive hardened child {} from a public key",
+                child
+            ),
+            Self::MaxDepth => write!(f, "Maximum derivation depth reached"),
+            Self::InvalidPath => write!(f, "Invalid derivation path"),
+            Self::InvalidEncoding => write!(f, "Invalid extended key encoding"),
+            Self::UnknownVersion(v) => write!(
+                f,
+                "Unknown extended key version {:02x}{:02x}{:02x}{:02x}",
+                v[0], v[1], v[2], v[3]
+            ),
+            Self::InvalidKey => write!(f, "Invalid key"),
+            Self::InvalidIndex(index) => write!(f, "Invalid child index {}", index),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for Bip32Error {}
+
+/// Network of the extended keys, which defines the serialization version bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Network {
+    /// Bitcoin mainnet: xprv / xpub
+    Mainnet,
+    /// Bitcoin testnets: tprv / tpub
+    Testnet,
+}
+
+impl Network {
+    const fn private_version(self) -> [u8; 4] {
+        match self {
+            Network::Mainnet => [0x04, 0x88, 0xad, 0xe4],
+            Network::Testnet => [0x04, 0x35, 0x83, 0x94],
+        }
+    }
+
+    const fn public_version(self) -> [u8; 4] {
+        match self {
+            Network::Mainnet => [0x04, 0x88, 0xb2, 0x1e],
+            Network::Testnet => [0x04, 0x35, 0x87, 0xcf],
+        }
+    }
+}
+
+/// Index of a child key, either normal or hardened
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ChildNumber(u32);
+
+impl ChildNumber {
+    const HARDENED: u32 = 0x8000_0000;
+
+    /// Normal child at the given index, which must be less than 2^31
+    pub const fn normal(index: u32) -> Option<Self> {
+        if index < Self::HARDENED {
+            Some(ChildNumber(index))
+        } else {
+            None
+        }
+    }
+
+    /// Hardened child at the given index, which must be less than 2^31
+    pub const fn hardened(index: u32) -> Option<Self> {
+        if index < Self::HARDENED {
+            Some(ChildNumber(index | Self::HARDENED))
+        } else {
+            None
+        }
+    }
+
+    /// Whether the child is hardened
+    pub const fn is_hardened(self) -> bool {
+        self.0 & Self::HARDENED != 0
+    }
+
+    /// The index of the child, without the hardened bit
+    pub const fn index(self) -> u32 {
+        self.0 & !Self::HARDENED
+    }
+}
+
+impl From<u32> for ChildNumber {
+    /// Child from its raw value, where the top bit denotes a hardened child
+    fn from(v: u32) -> Self {
+        ChildNumber(v)
+    }
+}
+
+impl From<ChildNumber> for u32 {
+    fn from(c: ChildNumber) -> Self {
+        c.0
+    }
+}
+
+impl fmt::Display for ChildNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_hardened() {
+            write!(f, "{}'", self.index())
+        } else {
+            write!(f, "{}", self.index())
+        }
+    }
+}
+
+impl FromStr for ChildNumber {
+    type Err = Bip32Error;
+
+    /// Parse a child as found in a derivation path: `84'`, `84h` and `84H` are hardened
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (index, hardened) = match s.strip_suffix(['\'', 'h', 'H']) {
+            Some(index) => (index, true),
+            None => (s, false),
+        };
+        // only accept plain digits, without sign or leading zeros
+        if index.is_empty()
+            || !index.bytes().all(|c| c.is_ascii_digit())
+            || (index.len() > 1 && index.starts_with('0'))
+        {
+            return Err(Bip32Error::InvalidPath);
+        }
+        let index = index.parse().map_err(|_| Bip32Error::InvalidPath)?;
+        let child = if hardened {
+            ChildNumber::hardened(index)
+        } else {
+            ChildNumber::normal(index)
+        };
+        child.ok_or(Bip32Error::InvalidPath)
+    }
+}
+
+/// Derivation path, e.g. `m/84'/0'/0'`
+///
+/// The path is validated when created, but not copied, so that no allocation is needed.
+/// The leading `m` is optional.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DerivationPath<'a>(&'a str);
+
+impl<'a> DerivationPath<'a> {
+    /// Validate the given derivation path
+    pub fn parse(path: &'a str) -> Result<Self, Bip32Error> {
+        let path = DerivationPath(path);
+        let mut depth = 0usize;
+        for child in path.components() {
+            child.parse::<ChildNumber>()?;
+            depth += 1;
+        }
+        if depth > u8::MAX as usize {
+            return Err(Bip32Error::InvalidPath);
+        }
+        Ok(path)
+    }
+
+    fn components(&self) -> impl Iterator<Item = &'a str> {
+        // only "m" denotes the root, any other path has at least one child
+        let root = self.0 == "m";
+        let relative = self.0.strip_prefix("m/").unwrap_or(self.0);
+        relative.split('/').filter(move |_| !root)
+    }
+
+    /// The children of the path, from the root
+    pub fn children(&self) -> impl Iterator<Item = ChildNumber> + 'a {
+        // the components have been validated when creating the path
+        self.components()
+            .map(|c| c.parse().unwrap_or(ChildNumber(0)))
+    }
+
+    /// Number of children in the path
+    pub fn depth(&self) -> u8 {
+        self.components().count() as u8
+    }
+}
+
+impl<'a> fmt::Display for DerivationPath<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+/// HASH160 (RIPEMD160 of SHA256) of the data
+pub(crate) fn hash160(data: &[u8]) -> [u8; 20] {
+    Ripemd160::new()
+        .update(&Sha256::new().update(data).finalize())
+        .finalize()
+}
+
+fn fingerprint_of(public_key: &[u8; 33]) -> [u8; 4] {
+    let hash = hash160(public_key);
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+fn parse_scalar(bytes: &[u8; 32]) -> Option<Scalar> {
+    // fails if the value is not less than the order of the curve
+    let scalar: Option<Scalar> = Scalar::from_repr((*bytes).into()).into();
+    scalar.filter(|s| !bool::from(s.is_zero()))
+}
+
+fn serialize_point(point: &ProjectivePoint) -> [u8; 33] {
+    let mut out = [0u8; 33];
+    out.copy_from_slice(point.to_affine().to_encoded_point(true).as_bytes());
+    out
+}
+
+// split the HMAC-SHA512 output in its left and right halves
+fn split(i: &[u8; 64]) -> ([u8; 32], [u8; 32]) {
+    let mut left = [0u8; 32];
+    let mut right = [0u8; 32];
+    left.copy_from_slice(&i[0..32]);
+    right.copy_from_slice(&i[32..64]);
+    (left, right)
+}
+
+// serialize the common part of the extended keys
+fn serialize(
+    version: [u8; 4],
+    depth: u8,
+    parent_fingerprint: &[u8; 4],
+    child_number: ChildNumber,
+    chain_code: &[u8; 32],
+    key: &[u8; 33],
+) -> [u8; EXTENDED_KEY_SIZE] {
+    let mut out = [0u8; EXTENDED_KEY_SIZE];
+    out[0..4].copy_from_slice(&version);
+    out[4] = depth;
+    out[5..9].copy_from_slice(parent_fingerprint);
+    out[9..13].copy_from_slice(&child_number.0.to_be_bytes());
+    out[13..45].copy_from_slice(chain_code);
+    out[45..78].copy_from_slice(key);
+    out
+}
+
+// the parsed fields of a serialized extended key
+struct Serialized {
+    version: [u8; 4],
+    depth: u8,
+    parent_fingerprint: [u8; 4],
+    child_number: ChildNumber,
+    chain_code: [u8; 32],
+    key: [u8; 33],
+}
+
+impl Serialized {
+    fn decode(s: &str) -> Result<Self, Bip32Error> {
+        let mut buf = [0u8; EXTENDED_KEY_SIZE];
+        match base58::decode_check(s, &mut buf) {
+            Some(EXTENDED_KEY_SIZE) => {}
+            _ => return Err(Bip32Error::InvalidEncoding),
+        }
+        let mut out = Serialized {
+            version: [0; 4],
+            depth: buf[4],
+            parent_fingerprint: [0; 4],
+            child_number: ChildNumber(u32::from_be_bytes([buf[9], buf[10], buf[11], buf[12]])),
+            chain_code: [0; 32],
+            key: [0; 33],
+        };
+        out.version.copy_from_slice(&buf[0..4]);
+        out.parent_fingerprint.copy_from_slice(&buf[5..9]);
+        out.chain_code.copy_from_slice(&buf[13..45]);
+        out.key.copy_from_slice(&buf[45..78]);
+        ct::zeroize(&mut buf);
+
+        // a master key has no parent
+        if out.depth == 0 && (out.parent_fingerprint != [0; 4] || out.child_number.0 != 0) {
+            return Err(Bip32Error::InvalidKey);
+        }
+        Ok(out)
+    }
+}
+
+/// BIP32 extended private key
+///
+/// The key is overwritten with zeros when dropped. The `Debug` implementation
+/// doesn't display the key, while the `Display` implementation shows the
+/// serialized key (xprv / tprv).
+///
+/// # Example
+///
+/// ```
+/// # use bip39_dict::{ENGLISH, Mnemonics, Seed};
+/// use bip39_dict::bip32::{DerivationPath, ExtendedPrivateKey, Network};
+/// # let mnemonics = Mnemonics::<12>::from_string(&ENGLISH, "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about").unwrap();
+///
+/// let seed = Seed::<64>::from_mnemonics(&ENGLISH, &mnemonics, b"");
+/// let master = ExtendedPrivateKey::master(seed.as_ref(), Network::Mainnet).unwrap();
+/// let account = master
+///     .derive_path(&DerivationPath::parse("m/84'/0'/0'").unwrap())
+///     .unwrap();
+/// let xpub = account.to_public();
+/// ```
+#[derive(Clone)]
+pub struct ExtendedPrivateKey {
+    network: Network,
+    depth: u8,
+    parent_fingerprint: [u8; 4],
+    child_number: ChildNumber,
+    chain_code: [u8; 32],
+    key: [u8; 32],
+}
+
+impl ExtendedPrivateKey {
+    /// Derive the master key from the seed (e.g. a BIP39 seed), which
+    /// should be between 16 and 64 bytes.
+    pub fn master(seed: &[u8], network: Network) -> Result<Self, Bip32Error> {
+        if seed.len() < 16 || seed.len() > 64 {
+            return Err(Bip32Error::InvalidSeed);
+        }
+        let mut i = [0u8; 64];
+        Hmac::<Context512>::new(b"Bitcoin seed").mac([seed], &mut i);
+        let (key, chain_code) = split(&i);
+        ct::zeroize(&mut i);
+        parse_scalar(&key).ok_or(Bip32Error::InvalidSeed)?;
+        Ok(ExtendedPrivateKey {
+            network,
+            depth: 0,
+            parent_fingerprint: [0; 4],
+            child_number: ChildNumber(0),
+            chain_code,
+            key,
+        })
+    }
+
+    fn scalar(&self) -> Scalar {
+        // always valid by construction
+        parse_scalar(&self.key).unwrap_or(Scalar::ONE)
+    }
+
+    /// Derive the child key at the given index
+    pub fn derive_child(&self, child: ChildNumber) -> Result<Self, Bip32Error> {
+        let depth = self.depth.checked_add(1).ok_or(Bip32Error::MaxDepth)?;
+        let mut i = [0u8; 64];
+        let index = child.0.to_be_bytes();
+        let mac = Hmac::<Context512>::new(&self.chain_code);
+        if child.is_hardened() {
+            mac.mac([&[0u8][..], &self.key, &index], &mut i);
+        } else {
+            mac.mac([&self.public_key()[..], &index], &mut i);
+        }
+        let (mut tweak, chain_code) = split(&i);
+        ct::zeroize(&mut i);
+
+        let parsed = parse_scalar(&tweak);
+        ct::zeroize(&mut tweak);
+        let scalar = parsed.ok_or(Bip32Error::InvalidChild(child))? + self.scalar();
+        if bool::from(scalar.is_zero()) {
+            return Err(Bip32Error::InvalidChild(child));
+        }
+        Ok(ExtendedPrivateKey {
+            network: self.network,
+            depth,
+            parent_fingerprint: self.fingerprint(),
+            child_number: child,
+            chain_code,
+            key: scalar.to_repr().into(),
+        })
+    }
+
+    /// Derive the key along the given path, from this key
+    pub fn derive_path(&self, path: &DerivationPath) -> Result<Self, Bip32Error> {
+        let mut key = self.clone();
+        for child in path.children() {
+            key = key.derive_child(child)?;
+        }
+        Ok(key)
+    }
+
+    /// The extended public key associated with this key
+    pub fn to_public(&self) -> ExtendedPublicKey {
+        ExtendedPublicKey {
+            network: self.network,
+            depth: self.depth,
+            parent_fingerprint: self.parent_fingerprint,
+            child_number: self.child_number,
+            chain_code: self.chain_code,
+            key: self.public_key(),
+        }
+    }
+
+    /// The compressed public key (33 bytes)
+    pub fn public_key(&self) -> [u8; 33] {
+        serialize_point(&(ProjectivePoint::GENERATOR * self.scalar()))
+    }
+
+    /// The private key (32 bytes)
+    pub fn private_key(&self) -> &[u8; 32] {
+        &self.key
+    }
+
+    /// The chain code
+    pub fn chain_code(&self) -> &[u8; 32] {
+        &self.chain_code
+    }
+
+    /// The fingerprint of this key, which is the parent fingerprint of its children.
+    ///
+    /// The fingerprint of the master key is known as the master fingerprint.
+    pub fn fingerprint(&self) -> [u8; 4] {
+        fingerprint_of(&self.public_key())
+    }
+
+    /// The fingerprint of the parent key, all zeros for the master key
+    pub fn parent_fingerprint(&self) -> [u8; 4] {
+        self.parent_fingerprint
+    }
+
+    /// Depth of the key, 0 for the master key
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    /// Index of this key in its parent
+    pub fn child_number(&self) -> ChildNumber {
+        self.child_number
+    }
+
+    /// The network used for the serialization
+    pub fn network(&self) -> Network {
+        self.network
+    }
+
+    /// Serialize the key as specified by BIP32, without the base58check encoding
+    pub fn serialize(&self) -> [u8; EXTENDED_KEY_SIZE] {
+        let mut key = [0u8; 33];
+        key[1..33].copy_from_slice(&self.key);
+        let out = serialize(
+            self.network.private_version(),
+            self.depth,
+            &self.parent_fingerprint,
+            self.child_number,
+            &self.chain_code,
+            &key,
+        );
+        ct::zeroize(&mut key);
+        out
+    }
+}
+
+impl Drop for ExtendedPrivateKey {
+    fn drop(&mut self) {
+        ct::zeroize(&mut self.key);
+        ct::zeroize(&mut self.chain_code);
+    }
+}
+
+impl fmt::Debug for ExtendedPrivateKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExtendedPrivateKey")
+            .field("network", &self.network)
+            .field("depth", &self.depth)
+            .field("child_number", &self.child_number)
+            .finish_non_exhaustive()
+    }
+}
+
+impl fmt::Display for ExtendedPrivateKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut serialized = self.serialize();
+        let r = base58::encode_check(&serialized, f);
+        ct::zeroize(&mut serialized);
+        r
+    }
+}
+
+impl FromStr for ExtendedPrivateKey {
+    type Err = Bip32Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut serialized = Serialized::decode(s)?;
+        let network = match serialized.version {
+            v if v == Network::Mainnet.private_version() => Network::Mainnet,
+            v if v == Network::Testnet.private_version() => Network::Testnet,
+            v => return Err(Bip32Error::UnknownVersion(v)),
+        };
+        let mut key = [0u8; 32];
+        key.copy_from_slice(&serialized.key[1..33]);
+        let prefix = serialized.key[0];
+        ct::zeroize(&mut serialized.key);
+        let xprv = ExtendedPrivateKey {
+            network,
+            depth: serialized.depth,
+            parent_fingerprint: serialized.parent_fingerprint,
+            child_number: serialized.child_number,
+            chain_code: serialized.chain_code,
+            key,
+        };
+        if prefix != 0 || parse_scalar(&xprv.key).is_none() {
+            return Err(Bip32Error::InvalidKey);
+        }
+        Ok(xprv)
+    }
+}
+
+/// BIP32 extended public key
+///
+/// The `Display` implementation shows the serialized key (xpub / tpub).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtendedPublicKey {
+    network: Network,
+    depth: u8,
+    parent_fingerprint: [u8; 4],
+    child_number: ChildNumber,
+    chain_code: [u8; 32],
+    key: [u8; 33],
+}
+
+impl ExtendedPublicKey {
+    fn point(&self) -> ProjectivePoint {
+        // always valid by construction
+        PublicKey::from_sec1_bytes(&self.key)
+            .map(|p| p.to_projective())
+            .unwrap_or(ProjectivePoint::GENERATOR)
+    }
+
+    /// Derive the (non hardened) child key at the given index
+    pub fn derive_child(&self, child: ChildNumber) -> Result<Self, Bip32Error> {
+        if child.is_hardened() {
+            return Err(Bip32Error::HardenedFromPublic(child));
+        }
+        let depth = self.depth.checked_add(1).ok_or(Bip32Error::MaxDepth)?;
+        let mut i = [0u8; 64];
+        Hmac::<Context512>::new(&self.chain_code)
+            .mac([&self.key[..], &child.0.to_be_bytes()], &mut i);
+        let (tweak, chain_code) = split(&i);
+
+        let tweak = parse_scalar(&tweak).ok_or(Bip32Error::InvalidChild(child))?;
+        let point = ProjectivePoint::GENERATOR * tweak + self.point();
+        let point = PublicKey::from_affine(point.to_affine())
+            .map_err(|_| Bip32Error::InvalidChild(child))?;
+        Ok(ExtendedPublicKey {
+            network: self.network,
+            depth,
+            parent_fingerprint: self.fingerprint(),
+            child_number: child,
+            chain_code,
+            key: serialize_point(&point.to_projective()),
+        })
+    }
+
+    /// Derive the key along the given path, from this key.
+    ///
+    /// The path cannot contain hardened children.
+    pub fn derive_path(&self, path: &DerivationPath) -> Result<Self, Bip32Error> {
+        let mut key = self.clone();
+        for child in path.children() {
+            key = key.derive_child(child)?;
+        }
+        Ok(key)
+    }
+
+    /// The compressed public key (33 bytes)
+    pub fn public_key(&self) -> &[u8; 33] {
+        &self.key
+    }
+
+    /// The chain code
+    pub fn chain_code(&self) -> &[u8; 32] {
+        &self.chain_code
+    }
+
+    /// The fingerprint of this key, which is the parent fingerprint of its children.
+    pub fn fingerprint(&self) -> [u8; 4] {
+        fingerprint_of(&self.key)
+    }
+
+    /// The fingerprint of the parent key, all zeros for the master key
+    pub fn parent_fingerprint(&self) -> [u8; 4] {
+        self.parent_fingerprint
+    }
+
+    /// Depth of the key, 0 for the master key
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    /// Index of this key in its parent
+    pub fn child_number(&self) -> ChildNumber {
+        self.child_number
+    }
+
+    /// The network used for the serialization
+    pub fn network(&self) -> Network {
+        self.network
+    }
+
+    /// Serialize the key as specified by BIP32, without the base58check encoding
+    pub fn serialize(&self) -> [u8; EXTENDED_KEY_SIZE] {
+        serialize(
+            self.network.public_version(),
+            self.depth,
+            &self.parent_fingerprint,
+            self.child_number,
+            &self.chain_code,
+            &self.key,
+        )
+    }
+}
+
+impl fmt::Display for ExtendedPublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        base58::encode_check(&self.serialize(), f)
+    }
+}
+
+impl FromStr for ExtendedPublicKey {
+    type Err = Bip32Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let serialized = Serialized::decode(s)?;
+        let network = match serialized.version {
+            v if v == Network::Mainnet.public_version() => Network::Mainnet,
+            v if v == Network::Testnet.public_version() => Network::Testnet,
+            v => return Err(Bip32Error::UnknownVersion(v)),
+        };
+        if !matches!(serialized.key[0], 2 | 3)
+            || PublicKey::from_sec1_bytes(&serialized.key).is_err()
+        {
+            return Err(Bip32Error::InvalidKey);
+        }
+        Ok(ExtendedPublicKey {
+            network,
+            depth: serialized.depth,
+            parent_fingerprint: serialized.parent_fingerprint,
+            child_number: serialized.child_number,
+            chain_code: serialized.chain_code,
+            key: serialized.key,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::ToString;
+
+    #[test]
+    fn path() {
+        let path = DerivationPath::parse("m/84'/0h/1H/2").unwrap();
+        assert_eq!(path.depth(), 4);
+        let children: std::vec::Vec<_> = path.children().collect();
+        assert_eq!(
+            children,
+            [
+                ChildNumber::hardened(84).unwrap(),
+                ChildNumber::hardened(0).unwrap(),
+                ChildNumber::hardened(1).unwrap(),
+                ChildNumber::normal(2).unwrap(),
+            ]
+        );
+        assert_eq!(children[0].to_string(), "84'");
+        assert_eq!(DerivationPath::parse("m").unwrap().depth(), 0);
+        assert_eq!(DerivationPath::parse("0/1").unwrap().depth(), 2);
+        for invalid in [
+            "m/",
+            "m//1",
+            "m/1'/",
+            "m/-1",
+            "m/01",
+            "m/2147483648",
+            "m/1/x",
+            "/1",
+        ] {
+            assert_eq!(
+                DerivationPath::parse(invalid),
+                Err(Bip32Error::InvalidPath),
+                "{}",
+                invalid
+            );
+        }
+    }
+}
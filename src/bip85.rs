@@ -0,0 +1,332 @@
+//! BIP85 deterministic entropy
+//!
+//! Derive child secrets (BIP39 mnemonics, hex entropy, WIF keys, passwords) from
+//! a BIP32 root key, as specified by
+//! [BIP85](https://github.com/bitcoin/bips/blob/master/bip-0085.mediawiki), so that
+//! a single master backup can recover all of them.
+//!
+//! Every application derives a hardened path `m/83696968'/{app}'/...` from the root key,
+//! and the entropy is HMAC-SHA512(key = "bip-entropy-from-k", data = derived private key).
+//!
+//! # Example
+//!
+//! ```
+//! use bip39_dict::{bip85, ENGLISH};
+//! use bip39_dict::bip32::ExtendedPrivateKey;
+//!
+//! let root: ExtendedPrivateKey = "xprv9s21ZrQH143K2LBWUUQRFXhucrQqBpKdRRxNVq2zBqsx8HVqFk2uYo8kmbaLLHRdqtQpUm98uKfu3vca1LqdGhUtyoFnCNkfmXRyPXLjbKb".parse().unwrap();
+//! let mnemonics = bip85::mnemonics::<_, 12>(&root, &ENGLISH, 0).unwrap();
+//! assert_eq!(
+//!     mnemonics.display(&ENGLISH).to_string(),
+//!     "girl mad pet galaxy egg matter matrix prison refuse sense ordinary nose"
+//! );
+//! ```
+use super::base58;
+use super::bip32::{Bip32Error, ChildNumber, ExtendedPrivateKey};
+use super::ct;
+use super::dictionary::Language;
+use super::entropy::{Entropy, EntropyError};
+use super::hmac::Hmac;
+use super::mnemonics::Mnemonics;
+use cryptoxide::hashing::sha2::Context512;
+
+#[cfg(not(feature = "std"))]
+use core::fmt;
+#[cfg(feature = "std")]
+use {std::error::Error, std::fmt};
+
+const PURPOSE: u32 = 83696968;
+const APP_BIP39: u32 = 39;
+const APP_HEX: u32 = 128169;
+const APP_WIF: u32 = 2;
+const APP_PWD_BASE64: u32 = 707764;
+const APP_PWD_BASE85: u32 = 707785;
+
+/// Maximum length of the passwords
+pub const MAX_PASSWORD_LENGTH: usize = 86;
+
+/// Possible errors when deriving BIP85 entropy
+#[derive(Debug, Clone)]
+pub enum Bip85Error {
+    /// The derivation failed
+    Bip32(Bip32Error),
+    /// The index is not less than 2^31
+    InvalidIndex(u32),
+    /// The language has no BIP85 code
+    UnsupportedLanguage(&'static str),
+    /// The number of words is not 12, 18 or 24
+    UnsupportedWords(usize),
+    /// The length requested is not supported by the application
+    InvalidLength(usize),
+    /// The entropy couldn't be converted to mnemonics
+    Entropy(EntropyError),
+}
+
+impl fmt::Display for Bip85Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bip32(err) => write!(f, "{}", err),
+            Self::InvalidIndex(index) => write!(f, "Invalid index {}", index),
+            Self::UnsupportedLanguage(name) => write!(f, "Unsupported language {}", name),
+            Self::UnsupportedWords(words) => write!(f, "Unsupported number of words {}", words),
+            Self::InvalidLength(length) => write!(f, "Invalid length {}", length),
+            Self::Entropy(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for Bip85Error {}
+
+impl From<Bip32Error> for Bip85Error {
+    fn from(e: Bip32Error) -> Self {
+        Bip85Error::Bip32(e)
+    }
+}
+
+impl From<EntropyError> for Bip85Error {
+    fn from(e: EntropyError) -> Self {
+        Bip85Error::Entropy(e)
+    }
+}
+
+/// BIP85 code of the language, from its [`Language::name`]
+pub fn language_code(name: &str) -> Option<u32> {
+    match name {
+        "english" => Some(0),
+        "japanese" => Some(1),
+        "korean" => Some(2),
+        "spanish" => Some(3),
+        "chinese-simplified" => Some(4),
+        "chinese-traditional" => Some(5),
+        "french" => Some(6),
+        "italian" => Some(7),
+        "czech" => Some(8),
+        "portuguese" => Some(9),
+        _ => None,
+    }
+}
+
+/// Derive the 64 bytes of entropy along the hardened path `m/83696968'/{path}'`
+///
+/// This is the building block of all the applications, for which dedicated
+/// functions are available.
+pub fn derive_entropy(root: &ExtendedPrivateKey, path: &[u32]) -> Result<[u8; 64], Bip85Error> {
+    let mut key = root.derive_child(hardened(PURPOSE)?)?;
+    for index in path {
+        key = key.derive_child(hardened(*index)?)?;
+    }
+    let mut entropy = [0u8; 64];
+    Hmac::<Context512>::new(b"bip-entropy-from-k").mac([&key.private_key()[..]], &mut entropy);
+    Ok(entropy)
+}
+
+fn hardened(index: u32) -> Result<ChildNumber, Bip85Error> {
+    ChildNumber::hardened(index).ok_or(Bip85Error::InvalidIndex(index))
+}
+
+// truncate the entropy to N bytes, and erase it
+fn truncate<const N: usize>(mut entropy: [u8; 64]) -> [u8; N] {
+    let mut out = [0u8; N];
+    out.copy_from_slice(&entropy[0..N]);
+    ct::zeroize(&mut entropy);
+    out
+}
+
+/// BIP39 application: the entropy of the mnemonics of W words (12, 18 or 24) in the
+/// given language, at the given index.
+///
+/// N must be the entropy size associated with W: 16, 24 or 32 bytes.
+pub fn mnemonics_entropy<D: Language, const N: usize>(
+    root: &ExtendedPrivateKey,
+    dict: &D,
+    index: u32,
+) -> Result<Entropy<N>, Bip85Error> {
+    let words = match N {
+        16 => 12,
+        24 => 18,
+        32 => 24,
+        _ => return Err(Bip85Error::InvalidLength(N)),
+    };
+    let language =
+        language_code(dict.name()).ok_or(Bip85Error::UnsupportedLanguage(dict.name()))?;
+    let entropy = derive_entropy(root, &[APP_BIP39, language, words, index])?;
+    Ok(Entropy(truncate(entropy)))
+}
+
+/// BIP39 application: the mnemonics of W words (12, 18 or 24) in the given language,
+/// at the given index.
+pub fn mnemonics<D: Language, const W: usize>(
+    root: &ExtendedPrivateKey,
+    dict: &D,
+    index: u32,
+) -> Result<Mnemonics<W>, Bip85Error> {
+    let mnemonics = match W {
+        12 => mnemonics_entropy::<D, 16>(root, dict, index)?.to_mnemonics::<W, 4>(),
+        18 => mnemonics_entropy::<D, 24>(root, dict, index)?.to_mnemonics::<W, 6>(),
+        24 => mnemonics_entropy::<D, 32>(root, dict, index)?.to_mnemonics::<W, 8>(),
+        _ => return Err(Bip85Error::UnsupportedWords(W)),
+    };
+    Ok(mnemonics?)
+}
+
+/// HEX application: N bytes of entropy (between 16 and 64) at the given index
+pub fn hex<const N: usize>(root: &ExtendedPrivateKey, index: u32) -> Result<[u8; N], Bip85Error> {
+    if !(16..=64).contains(&N) {
+        return Err(Bip85Error::InvalidLength(N));
+    }
+    let entropy = derive_entropy(root, &[APP_HEX, N as u32, index])?;
+    Ok(truncate(entropy))
+}
+
+/// Private key for bitcoin, displayed in the Wallet Import Format (mainnet, compressed)
+///
+/// The key is overwritten with zeros when dropped, and is not displayed by the
+/// `Debug` implementation.
+#[derive(Clone)]
+pub struct WifKey([u8; 32]);
+
+impl WifKey {
+    /// The private key
+    pub fn private_key(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl Drop for WifKey {
+    fn drop(&mut self) {
+        ct::zeroize(&mut self.0)
+    }
+}
+
+impl fmt::Debug for WifKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "WifKey(<redacted>)")
+    }
+}
+
+impl fmt::Display for WifKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut data = [0u8; 34];
+        data[0] = 0x80;
+        data[1..33].copy_from_slice(&self.0);
+        data[33] = 0x01;
+        let r = base58::encode_check(&data, f);
+        ct::zeroize(&mut data);
+        r
+    }
+}
+
+/// WIF application: the bitcoin private key at the given index
+pub fn wif(root: &ExtendedPrivateKey, index: u32) -> Result<WifKey, Bip85Error> {
+    let entropy = derive_entropy(root, &[APP_WIF, index])?;
+    Ok(WifKey(truncate(entropy)))
+}
+
+/// Password derived with BIP85, of at most [`MAX_PASSWORD_LENGTH`] ASCII characters
+///
+/// The password is overwritten with zeros when dropped, and is not displayed by the
+/// `Debug` implementation.
+#[derive(Clone)]
+pub struct Password {
+    chars: [u8; MAX_PASSWORD_LENGTH],
+    len: usize,
+}
+
+impl Password {
+    /// The password
+    pub fn as_str(&self) -> &str {
+        // only ASCII characters from the alphabets are ever written
+        core::str::from_utf8(&self.chars[0..self.len]).unwrap_or("")
+    }
+}
+
+impl Drop for Password {
+    fn drop(&mut self) {
+        ct::zeroize(&mut self.chars)
+    }
+}
+
+impl fmt::Debug for Password {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Password(<redacted>)")
+    }
+}
+
+impl fmt::Display for Password {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const BASE85: &[u8; 85] =
+    b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz!#$%&()*+-;<=>?@^_`{|}~";
+
+/// PWD BASE64 application: password of the given length (between 20 and 86)
+/// at the given index, made of the base64 encoding of the entropy.
+pub fn password_base64(
+    root: &ExtendedPrivateKey,
+    length: usize,
+    index: u32,
+) -> Result<Password, Bip85Error> {
+    if !(20..=86).contains(&length) {
+        return Err(Bip85Error::InvalidLength(length));
+    }
+    let mut entropy = derive_entropy(root, &[APP_PWD_BASE64, length as u32, index])?;
+    let mut password = Password {
+        chars: [0; MAX_PASSWORD_LENGTH],
+        len: length,
+    };
+    // 64 bytes are 21 groups of 3 bytes and a last byte, which is enough
+    // for 86 characters, before the padding
+    let mut pos = 0;
+    for group in entropy.chunks(3) {
+        let mut b = [0u8; 3];
+        b[0..group.len()].copy_from_slice(group);
+        let v = u32::from_be_bytes([0, b[0], b[1], b[2]]);
+        for i in 0..4 {
+            if pos < length {
+                password.chars[pos] = BASE64[(v >> (18 - 6 * i)) as usize & 0x3f];
+                pos += 1;
+            }
+        }
+    }
+    ct::zeroize(&mut entropy);
+    Ok(password)
+}
+
+/// PWD BASE85 application: password of the given length (between 10 and 80)
+/// at the given index, made of the base85 encoding (RFC1924 alphabet) of the entropy.
+pub fn password_base85(
+    root: &ExtendedPrivateKey,
+    length: usize,
+    index: u32,
+) -> Result<Password, Bip85Error> {
+    if !(10..=80).contains(&length) {
+        return Err(Bip85Error::InvalidLength(length));
+    }
+    let mut entropy = derive_entropy(root, &[APP_PWD_BASE85, length as u32, index])?;
+    let mut password = Password {
+        chars: [0; MAX_PASSWORD_LENGTH],
+        len: length,
+    };
+    // each group of 4 bytes is 5 characters, most significant first
+    let mut pos = 0;
+    for group in entropy.chunks(4) {
+        let mut v = u32::from_be_bytes([group[0], group[1], group[2], group[3]]);
+        let mut digits = [0u8; 5];
+        for d in digits.iter_mut().rev() {
+            *d = BASE85[(v % 85) as usize];
+            v /= 85;
+        }
+        for d in digits {
+            if pos < length {
+                password.chars[pos] = d;
+                pos += 1;
+            }
+        }
+    }
+    ct::zeroize(&mut entropy);
+    Ok(password)
+}
@@ -1,3 +1,5 @@
+use core::marker::PhantomData;
+
 #[allow(dead_code)]
 const NUM_BITS_PER_BLOCK: usize = 11;
 
@@ -91,8 +93,9 @@ impl WriteState {
 // 9            + 8 => 1*11          + 6          => S6
 // 10           + 8 => 1*11          + 7          => S7
 //
-#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
 pub(crate) enum ReadState {
+    #[default]
     S0,
     S1(u16),
     S2(u16),
@@ -106,12 +109,6 @@ pub(crate) enum ReadState {
     S10(u16),
 }
 
-impl Default for ReadState {
-    fn default() -> Self {
-        ReadState::S0
-    }
-}
-
 pub(crate) enum NextRead {
     Zero(ReadState),
     One(u16, ReadState),
@@ -155,19 +152,61 @@ impl ReadState {
     }
 }
 
-pub struct BitWriterBy11<F> {
+/// Order of the bits of the 11 bits words in the bytes
+///
+/// The states above pack the words most significant bits first. The other order
+/// is the same packing with the bits of every word and of every byte reversed.
+pub(crate) trait BitOrder {
+    /// the word as packed most significant bits first
+    fn word(w: u16) -> u16;
+    /// the byte as packed most significant bits first
+    fn byte(b: u8) -> u8;
+}
+
+/// Most significant bits first, the order of BIP39
+pub(crate) struct Msb;
+
+/// Least significant bits first, the order of the Algorand mnemonics
+#[cfg(any(test, feature = "algorand"))]
+pub(crate) struct Lsb;
+
+impl BitOrder for Msb {
+    fn word(w: u16) -> u16 {
+        w
+    }
+
+    fn byte(b: u8) -> u8 {
+        b
+    }
+}
+
+#[cfg(any(test, feature = "algorand"))]
+impl BitOrder for Lsb {
+    fn word(w: u16) -> u16 {
+        w.reverse_bits() >> (16 - NUM_BITS_PER_BLOCK)
+    }
+
+    fn byte(b: u8) -> u8 {
+        b.reverse_bits()
+    }
+}
+
+pub struct BitWriterBy11<F, O> {
     writer: F,
     state: WriteState,
+    order: PhantomData<O>,
 }
 
-impl<F> BitWriterBy11<F>
+impl<F, O> BitWriterBy11<F, O>
 where
-    F: FnMut(u8) -> (),
+    F: FnMut(u8),
+    O: BitOrder,
 {
     pub fn new(writer: F) -> Self {
         BitWriterBy11 {
             writer,
             state: WriteState::S0,
+            order: PhantomData,
         }
     }
 
@@ -185,14 +224,14 @@ where
     }
 
     fn emit(&mut self, byte: u8) {
-        (self.writer)(byte)
+        (self.writer)(O::byte(byte))
     }
 
     // write 11 bits in the buffer
     pub fn write(&mut self, e: u16) {
         assert!(e < 2048);
 
-        match self.state.append11(e) {
+        match self.state.append11(O::word(e)) {
             NextWrite::One(byte, state) => {
                 self.emit(byte);
                 self.state = state;
@@ -207,41 +246,43 @@ where
 }
 
 #[cfg(test)]
-pub struct BitReaderBy11<'a> {
+pub struct BitReaderBy11<'a, O> {
     buffer: &'a [u8],
     state: ReadState,
+    order: PhantomData<O>,
 }
 
 #[cfg(test)]
-impl<'a> BitReaderBy11<'a> {
+impl<'a, O: BitOrder> BitReaderBy11<'a, O> {
     pub fn new(bytes: &'a [u8]) -> Self {
         BitReaderBy11 {
             buffer: bytes,
             state: ReadState::S0,
+            order: PhantomData,
         }
     }
 
     // read 1 or 2 bytes and returns an 11 bits unsigned integer (in shape of a u16)
     pub fn read(&mut self) -> u16 {
-        let v = self.buffer[0];
+        let v = O::byte(self.buffer[0]);
         match self.state.read8(v) {
             // if it's zero, then we need to read another byte to make an 11 bits
             NextRead::Zero(next) => {
-                let v2 = self.buffer[1];
+                let v2 = O::byte(self.buffer[1]);
                 self.buffer = &self.buffer[2..];
                 match next.read8(v2) {
                     // it's *guarantee* that reading 2 bytes will lead to at least 1 consumption of 16 bits
                     NextRead::Zero(_) => unreachable!(),
                     NextRead::One(r, next2) => {
                         self.state = next2;
-                        r
+                        O::word(r)
                     }
                 }
             }
             NextRead::One(r, next) => {
                 self.state = next;
                 self.buffer = &self.buffer[1..];
-                r
+                O::word(r)
             }
         }
     }
@@ -251,7 +292,7 @@ impl<'a> BitReaderBy11<'a> {
 mod test {
     use super::*;
 
-    const WORDS: &'static [u16] = &[
+    const WORDS: &[u16] = &[
         0b000_0000_0001,
         0b000_0000_0001,
         0b000_0000_0001,
@@ -303,7 +344,7 @@ mod test {
             bytes[bytes_pos] = b;
             bytes_pos += 1;
         };
-        let mut writer = BitWriterBy11::new(emit);
+        let mut writer = BitWriterBy11::<_, Msb>::new(emit);
         for w in WORDS {
             writer.write(*w)
         }
@@ -314,11 +355,31 @@ mod test {
 
     #[test]
     fn bit_read_by_11() {
-        let mut reader = BitReaderBy11::new(&BYTES);
+        let mut reader = BitReaderBy11::<Msb>::new(&BYTES);
 
         for (ith, w) in WORDS.iter().enumerate() {
             let word = reader.read();
             assert_eq!(word, *w, "{} WORD not correct", ith);
         }
     }
+
+    #[test]
+    fn lsb_bits_by_11() {
+        let mut bytes = [0; BYTES.len()];
+        let mut bytes_pos = 0;
+        let mut writer = BitWriterBy11::<_, Lsb>::new(|b: u8| {
+            bytes[bytes_pos] = b;
+            bytes_pos += 1;
+        });
+        for w in WORDS {
+            writer.write(*w)
+        }
+        writer.finalize();
+        assert_eq!(bytes[0..3], [0b0000_0001, 0b0000_1000, 0b0100_0000]);
+
+        let mut reader = BitReaderBy11::<Lsb>::new(&bytes);
+        for (ith, w) in WORDS.iter().enumerate() {
+            assert_eq!(reader.read(), *w, "{} WORD not correct", ith);
+        }
+    }
 }
@@ -0,0 +1,143 @@
+//! Cardano root keys
+//!
+//! Cardano wallets use extended ed25519 keys (BIP32-Ed25519): the 96 bytes of
+//! a root key are the extended secret key (kL and kR, 32 bytes each) followed by
+//! the chain code (32 bytes), and are returned as a [`Seed<96>`]. Three different
+//! derivations of the root key are in use, as described in
+//! [CIP-3](https://github.com/cardano-foundation/CIPs/blob/master/CIP-0003/CIP-0003.md):
+//!
+//! * [`icarus`]: the Shelley wallets (Daedalus, Yoroi, ...), PBKDF2-HMAC-SHA512
+//!   of the passphrase salted with the entropy, 4096 iterations;
+//! * [`ledger`]: the Ledger hardware wallets, SLIP-10 on the standard
+//!   BIP39 seed, retried until the key has the expected bit cleared;
+//! * [`byron`]: the legacy Byron wallets of Daedalus, HMAC-SHA512 keyed with the
+//!   CBOR encoding of the BLAKE2b-256 of the CBOR encoding of the entropy.
+//!
+//! # Example
+//!
+//! ```
+//! use bip39_dict::{cardano, Entropy, Mnemonics, ENGLISH};
+//!
+//! let phrase = "eight country switch draw meat scout mystery blade tip drift useless good keep usage title";
+//! let mnemonics = Mnemonics::<15>::from_string(&ENGLISH, phrase).unwrap();
+//! let entropy = Entropy::<20>::from_mnemonics::<15, 5>(&mnemonics).unwrap();
+//! let root = cardano::icarus(&entropy, b"");
+//! assert_eq!(&root.to_string()[..16], "c065afd2832cd8b0");
+//! ```
+use super::ct;
+use super::dictionary::Language;
+use super::entropy::Entropy;
+use super::hmac::{self, Hmac};
+use super::mnemonics::Mnemonics;
+use super::seed::Seed;
+use cryptoxide::hashing::blake2b::Blake2b;
+use cryptoxide::hashing::sha2::{Context256, Context512, Sha512};
+
+const ED25519_SEED: &[u8] = b"ed25519 seed";
+
+// clear the 3 lowest bits and the highest bit, and set the second highest bit of kL
+fn clamp(key: &mut [u8]) {
+    key[0] &= 0b1111_1000;
+    key[31] &= 0b0111_1111;
+    key[31] |= 0b0100_0000;
+}
+
+/// CBOR header of a byte string of `len` bytes, with its size
+fn cbor_bytes_header(len: usize) -> ([u8; 3], usize) {
+    match len {
+        0..=23 => ([0x40 | len as u8, 0, 0], 1),
+        24..=255 => ([0x58, len as u8, 0], 2),
+        _ => ([0x59, (len >> 8) as u8, len as u8], 3),
+    }
+}
+
+/// Icarus root key: PBKDF2-HMAC-SHA512 of the passphrase, salted with the entropy
+///
+/// This is the derivation of the Shelley era wallets.
+pub fn icarus<const N: usize>(entropy: &Entropy<N>, passphrase: &[u8]) -> Seed<96> {
+    let mut key = [0u8; 96];
+    hmac::pbkdf2(
+        &Hmac::<Context512>::new(passphrase),
+        &[&entropy.0],
+        4096,
+        &mut key,
+    );
+    // the third highest bit of kL is cleared too
+    key[31] &= 0b0001_1111;
+    clamp(&mut key);
+    Seed::from_bytes(key)
+}
+
+/// Ledger root key, from the standard BIP39 seed of the mnemonics and the passphrase
+///
+/// The chain code is the HMAC-SHA256 of `0x01 || seed`, and the key is the
+/// HMAC-SHA512 of the seed, hashed again until the third highest bit of kL is
+/// cleared, both keyed with `"ed25519 seed"`.
+pub fn ledger<D: Language, const W: usize>(
+    dict: &D,
+    mnemonics: &Mnemonics<W>,
+    passphrase: &[u8],
+) -> Seed<96> {
+    let seed = Seed::<64>::from_mnemonics(dict, mnemonics, passphrase);
+    let mut key = [0u8; 96];
+    Hmac::<Context256>::new(ED25519_SEED).mac([&[1u8][..], seed.as_ref()], &mut key[64..]);
+    let mac = Hmac::<Context512>::new(ED25519_SEED);
+    mac.mac([seed.as_ref()], &mut key[..64]);
+    while key[31] & 0b0010_0000 != 0 {
+        let mut previous = [0u8; 64];
+        previous.copy_from_slice(&key[..64]);
+        mac.mac([&previous[..]], &mut key[..64]);
+        ct::zeroize(&mut previous);
+    }
+    clamp(&mut key);
+    Seed::from_bytes(key)
+}
+
+/// Byron root key, of the legacy Daedalus wallets (12 words, without passphrase)
+///
+/// The HMAC-SHA512 of `"Root Seed Chain " || i` (with `i` in decimal, starting at 1)
+/// gives the SHA512 of its first half as the extended secret key, and its second
+/// half as the chain code. `i` is incremented until the third highest bit of kL is
+/// cleared.
+pub fn byron<const N: usize>(entropy: &Entropy<N>) -> Seed<96> {
+    let (header, size) = cbor_bytes_header(N);
+    let digest = Blake2b::<256>::new()
+        .update(&header[..size])
+        .update(&entropy.0)
+        .finalize();
+    let mut hmac_key = [0u8; 34];
+    hmac_key[0] = 0x58;
+    hmac_key[1] = 32;
+    hmac_key[2..].copy_from_slice(&digest);
+    let mac = Hmac::<Context512>::new(&hmac_key);
+    ct::zeroize(&mut hmac_key);
+
+    let mut key = [0u8; 96];
+    let mut i = [0u8; 64];
+    for iteration in 1u32.. {
+        let digits = decimal(iteration);
+        let digits = &digits[digits.iter().position(|d| *d != 0).unwrap()..];
+        mac.mac([&b"Root Seed Chain "[..], digits], &mut i);
+        key[..64].copy_from_slice(&Sha512::new().update(&i[..32]).finalize());
+        if key[31] & 0b0010_0000 == 0 {
+            break;
+        }
+    }
+    key[64..].copy_from_slice(&i[32..]);
+    ct::zeroize(&mut i);
+    clamp(&mut key);
+    Seed::from_bytes(key)
+}
+
+// ASCII decimal digits of the value, preceded by zero bytes
+fn decimal(mut value: u32) -> [u8; 10] {
+    let mut digits = [0u8; 10];
+    for d in digits.iter_mut().rev() {
+        *d = b'0' + (value % 10) as u8;
+        value /= 10;
+        if value == 0 {
+            break;
+        }
+    }
+    digits
+}
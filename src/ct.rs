@@ -0,0 +1,102 @@
+//! Constant time helpers
+//!
+//! Those are used to prevent the timing and the memory access pattern of
+//! some operations to depend on secret data (e.g. the mnemonic words).
+//!
+//! The branches in those functions only depend on public values (the length
+//! of the dictionary, the length of the dictionary words), or on the length
+//! of the secret inputs.
+
+use core::hint::black_box;
+
+/// return 0xff if `a` is equal to `b`, 0 otherwise
+#[cfg(feature = "hardened")]
+#[inline]
+fn mask_eq(a: &[u8], b: &[u8]) -> u8 {
+    let len = a.len().max(b.len());
+    let mut diff = (a.len() ^ b.len()) as u64;
+    for i in 0..len {
+        let x = a.get(i).copied().unwrap_or(0);
+        let y = b.get(i).copied().unwrap_or(0);
+        diff |= (x ^ y) as u64;
+    }
+    // diff is 0 if equal; turn it into 0xff if zero, 0 otherwise
+    let nonzero = ((diff | diff.wrapping_neg()) >> 63) as u8;
+    black_box(nonzero.wrapping_sub(1))
+}
+
+/// Check that the first `bits` bits of `a` and `b` are equals
+///
+/// This always process all the necessary bytes, and doesn't return early
+/// on the first difference.
+pub(crate) fn eq_bits(a: &[u8], b: &[u8], bits: usize) -> bool {
+    let mut diff = 0u8;
+    let mut rem = bits;
+    let mut ofs = 0;
+    while rem > 0 {
+        let mask = if rem >= 8 {
+            0xff
+        } else {
+            (((1u16 << rem) - 1) << (8 - rem)) as u8
+        };
+        diff |= (a[ofs] ^ b[ofs]) & mask;
+        rem = rem.saturating_sub(8);
+        ofs += 1;
+    }
+    black_box(diff) == 0
+}
+
+/// Find the position of `word` in the `words` list
+///
+/// Every word of the list is compared against the searched word, and the
+/// result is accumulated without branching on the comparison.
+#[cfg(feature = "hardened")]
+pub(crate) fn position(words: &[&str], word: &str) -> Option<usize> {
+    let mut found = 0u8;
+    let mut index = 0usize;
+    for (i, candidate) in words.iter().enumerate() {
+        let m = mask_eq(candidate.as_bytes(), word.as_bytes());
+        found |= m;
+        index |= i & ((m & 1) as usize).wrapping_neg();
+    }
+    if black_box(found) == 0xff {
+        Some(index)
+    } else {
+        None
+    }
+}
+
+/// Overwrite the buffer with zeros, in a way the compiler can't elide,
+/// even if the buffer is never read again.
+pub(crate) fn zeroize<T: Copy + Default>(buf: &mut [T]) {
+    for b in buf.iter_mut() {
+        // SAFETY: `b` is a valid and aligned reference to a value
+        unsafe { core::ptr::write_volatile(b, T::default()) };
+    }
+    core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "hardened")]
+    fn ct_position() {
+        let words = ["abc", "ab", "abcd", "b"];
+        for (i, w) in words.iter().enumerate() {
+            assert_eq!(position(&words, w), Some(i));
+        }
+        assert_eq!(position(&words, ""), None);
+        assert_eq!(position(&words, "a"), None);
+        assert_eq!(position(&words, "abcde"), None);
+    }
+
+    #[test]
+    fn ct_eq_bits() {
+        assert!(eq_bits(&[0xff, 0xf0], &[0xff, 0xff], 12));
+        assert!(!eq_bits(&[0xff, 0xf0], &[0xff, 0xff], 13));
+        assert!(eq_bits(&[0x12], &[0x34], 0));
+        assert!(!eq_bits(&[0x12], &[0x92], 1));
+    }
+}
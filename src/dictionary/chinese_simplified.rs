@@ -1,4 +1,5 @@
-pub(crate) const WORDS: [&'static str; 2048] = [
+#[allow(clippy::large_const_arrays)]
+pub(crate) const WORDS: [&str; 2048] = [
     "的", "一", "是", "在", "不", "了", "有", "和", "人", "这", "中", "大", "为", "上", "个", "国",
     "我", "以", "要", "他", "时", "来", "用", "们", "生", "到", "作", "地", "于", "出", "就", "分",
     "对", "成", "会", "可", "主", "发", "年", "动", "同", "工", "也", "能", "下", "过", "子", "说",
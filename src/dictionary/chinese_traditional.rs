@@ -1,4 +1,5 @@
-pub(crate) const WORDS: [&'static str; 2048] = [
+#[allow(clippy::large_const_arrays)]
+pub(crate) const WORDS: [&str; 2048] = [
     "的", "一", "是", "在", "不", "了", "有", "和", "人", "這", "中", "大", "為", "上", "個", "國",
     "我", "以", "要", "他", "時", "來", "用", "們", "生", "到", "作", "地", "於", "出", "就", "分",
     "對", "成", "會", "可", "主", "發", "年", "動", "同", "工", "也", "能", "下", "過", "子", "說",
@@ -1,4 +1,5 @@
-pub(crate) const WORDS: [&'static str; 2048] = [
+#[allow(clippy::large_const_arrays)]
+pub(crate) const WORDS: [&str; 2048] = [
     "abandon", "ability", "able", "about", "above", "absent", "absorb", "abstract", "absurd",
     "abuse", "access", "accident", "account", "accuse", "achieve", "acid", "acoustic", "acquire",
     "across", "act", "action", "actor", "actress", "actual", "adapt", "add", "addict", "address",
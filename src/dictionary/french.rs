@@ -1,4 +1,5 @@
-﻿pub(crate) const WORDS: [&'static str; 2048] = [
+﻿#[allow(clippy::large_const_arrays)]
+pub(crate) const WORDS: [&str; 2048] = [
     "abaisser",
     "abandon",
     "abdiquer",
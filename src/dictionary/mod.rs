@@ -25,30 +25,102 @@ mod korean;
 mod spanish;
 
 #[cfg(not(feature = "std"))]
-use {
-    alloc::string::{String, ToString},
-    core::fmt,
-};
+use core::fmt;
 
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::string::String;
 #[cfg(feature = "std")]
-use {
-    std::error::Error,
-    std::fmt,
-    std::string::{String, ToString},
-};
+use {std::error::Error, std::fmt, std::string::String};
 
 use crate::index::MnemonicIndex;
 
+/// Coarse classification of the length of a word.
+///
+/// This is safe to report (e.g. in logs) as it doesn't reveal the word
+/// itself, but still gives a hint about the nature of the mistake.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Copy, Clone)]
+pub enum WordLength {
+    /// the word is empty
+    Empty,
+    /// between 1 and 3 characters
+    Short,
+    /// between 4 and 8 characters
+    Medium,
+    /// more than 8 characters
+    Long,
+}
+
+impl WordLength {
+    /// classify the given word by its number of characters
+    pub fn of(word: &str) -> Self {
+        match word.chars().count() {
+            0 => WordLength::Empty,
+            1..=3 => WordLength::Short,
+            4..=8 => WordLength::Medium,
+            _ => WordLength::Long,
+        }
+    }
+}
+
+impl fmt::Display for WordLength {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WordLength::Empty => write!(f, "empty"),
+            WordLength::Short => write!(f, "short"),
+            WordLength::Medium => write!(f, "medium"),
+            WordLength::Long => write!(f, "long"),
+        }
+    }
+}
+
 /// Errors associated to a given language/dictionary
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
+///
+/// The word searched is likely to be (a misspelling of) a secret word, so
+/// neither the `Debug` nor the `Display` implementation reveal it. It is
+/// only available through the [`WordNotFound::word_searched`] field, with the
+/// `alloc` feature: without it, only the [`WordLength`] of the word is kept.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone)]
 pub struct WordNotFound {
     /// The word searched that was not found
+    ///
+    /// This is raw user input, and should be treated as secret.
+    #[cfg(feature = "alloc")]
     pub word_searched: String,
+    #[cfg(not(feature = "alloc"))]
+    length: WordLength,
+}
+
+impl WordNotFound {
+    /// create a new error for the given word
+    pub fn new(word: &str) -> Self {
+        WordNotFound {
+            #[cfg(feature = "alloc")]
+            word_searched: String::from(word),
+            #[cfg(not(feature = "alloc"))]
+            length: WordLength::of(word),
+        }
+    }
+
+    /// The length class of the word searched
+    pub fn length(&self) -> WordLength {
+        #[cfg(feature = "alloc")]
+        return WordLength::of(&self.word_searched);
+        #[cfg(not(feature = "alloc"))]
+        return self.length;
+    }
+}
+
+impl fmt::Debug for WordNotFound {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("WordNotFound")
+            .field("length", &self.length())
+            .finish()
+    }
 }
 
 impl fmt::Display for WordNotFound {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "word '{}' not found in dictionary", self.word_searched)
+        write!(f, "{} word not found in dictionary", self.length())
     }
 }
 
@@ -98,19 +170,25 @@ impl Language for DefaultDictionary {
     fn separator(&self) -> &'static str {
         " "
     }
+    #[cfg(feature = "hardened")]
+    fn lookup_mnemonic(&self, word: &str) -> Result<MnemonicIndex, WordNotFound> {
+        // always scan the whole dictionary, independently of the ordering
+        match crate::ct::position(&self.words, word) {
+            None => Err(WordNotFound::new(word)),
+            Some(v) => Ok(MnemonicIndex::new(v as u16).unwrap()),
+        }
+    }
+
+    #[cfg(not(feature = "hardened"))]
     fn lookup_mnemonic(&self, word: &str) -> Result<MnemonicIndex, WordNotFound> {
         if self.ordered {
             match self.words.binary_search(&word) {
                 Ok(v) => Ok(MnemonicIndex::new(v as u16).unwrap()),
-                Err(_) => Err(WordNotFound {
-                    word_searched: word.to_string(),
-                }),
+                Err(_) => Err(WordNotFound::new(word)),
             }
         } else {
             match self.words.iter().position(|x| x == &word) {
-                None => Err(WordNotFound {
-                    word_searched: word.to_string(),
-                }),
+                None => Err(WordNotFound::new(word)),
                 Some(v) => {
                     Ok(
                         // it is safe to call unwrap as we guarantee that the
@@ -243,4 +321,15 @@ mod tests {
             dict_valid!(KOREAN);
         }
     }
+
+    #[test]
+    fn word_not_found() {
+        // long words are kept in full
+        let long = "éééééééééééééééééééé";
+        let err = WordNotFound::new(long);
+        #[cfg(feature = "alloc")]
+        assert_eq!(err.word_searched, long);
+        assert_eq!(err.length(), WordLength::Long);
+        assert!(!format!("{} {:?}", err, err).contains('é'));
+    }
 }
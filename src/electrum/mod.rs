@@ -0,0 +1,205 @@
+//! Electrum seeds
+//!
+//! Electrum (since version 2) doesn't use the BIP39 checksum: the words are
+//! taken from the BIP39 wordlists, but the phrase is valid when the
+//! HMAC-SHA512 of the phrase, keyed with "Seed version", starts with the
+//! version prefix of the wallet type (in hexadecimal):
+//!
+//! | type                      | prefix  |
+//! |---------------------------|---------|
+//! | [`SeedType::Standard`]    | `01`    |
+//! | [`SeedType::Segwit`]      | `100`   |
+//! | [`SeedType::Standard2fa`] | `101`   |
+//! | [`SeedType::Segwit2fa`]   | `102`   |
+//!
+//! New phrases are generated by grinding: starting from random words, the phrase
+//! is incremented until it has the requested prefix. The seed is then derived as
+//! in BIP39, but with the "electrum" salt prefix (see [`SeedScheme::electrum`]).
+//!
+//! Electrum normalizes the phrase and the passphrase before using them (NFKD,
+//! lowercase, without accents and with single spaces), which is expected to be
+//! done by the caller for the passphrase. Phrases in the English wordlist
+//! rendered by this crate are already normalized.
+//!
+//! The phrases of the first Electrum versions, in their own wordlist of 1626
+//! words ([`V1_WORDLIST`]), are supported by [`V1Mnemonics`].
+//!
+//! # Example
+//!
+//! ```
+//! use bip39_dict::{electrum::{self, SeedType}, Mnemonics, ENGLISH};
+//!
+//! let phrase = "wild father tree among universe such mobile favorite target dynamic credit identify";
+//! let mnemonics = Mnemonics::<12>::from_string(&ENGLISH, phrase).unwrap();
+//! assert_eq!(electrum::seed_type(&ENGLISH, &mnemonics), Some(SeedType::Segwit));
+//! let seed = electrum::seed(&ENGLISH, &mnemonics, b"");
+//! assert_eq!(&seed.to_string()[..16], "aac2a6302e48577a");
+//! ```
+mod v1;
+mod v1_words;
+
+pub use v1::{V1Error, V1Mnemonics, V1Wordlist, V1_WORDLIST};
+
+use super::dictionary::Language;
+use super::hmac::Hmac;
+use super::mnemonics::Mnemonics;
+use super::seed::{Seed, SeedScheme};
+use cryptoxide::hashing::sha2::Context512;
+#[cfg(feature = "rand")]
+use {
+    super::index::{MnemonicIndex, MAX_MNEMONIC_VALUE},
+    super::rng::GenerateError,
+    rand_core::{CryptoRng, RngCore},
+};
+
+/// Type of wallet of an Electrum seed, given by the version prefix
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeedType {
+    /// P2PKH wallet, prefix `01`
+    Standard,
+    /// P2WPKH wallet, prefix `100`
+    Segwit,
+    /// two factor authentication P2SH wallet, prefix `101`
+    Standard2fa,
+    /// two factor authentication P2WSH wallet, prefix `102`
+    Segwit2fa,
+}
+
+impl SeedType {
+    const ALL: [SeedType; 4] = [
+        SeedType::Standard,
+        SeedType::Segwit,
+        SeedType::Standard2fa,
+        SeedType::Segwit2fa,
+    ];
+
+    /// The version prefix, in hexadecimal
+    pub const fn prefix(self) -> &'static str {
+        match self {
+            SeedType::Standard => "01",
+            SeedType::Segwit => "100",
+            SeedType::Standard2fa => "101",
+            SeedType::Segwit2fa => "102",
+        }
+    }
+}
+
+// HMAC-SHA512("Seed version", phrase), with the phrase streamed word by word
+fn version_hash<D: Language, const W: usize>(dict: &D, mnemonics: &Mnemonics<W>) -> [u8; 64] {
+    let separator = dict.separator().as_bytes();
+    let words = mnemonics
+        .indices()
+        .flat_map(|index| [separator, index.to_word(dict).as_bytes()])
+        .skip(1);
+    let mut hash = [0u8; 64];
+    Hmac::<Context512>::new(b"Seed version").mac(words, &mut hash);
+    hash
+}
+
+fn has_prefix(hash: &[u8; 64], prefix: &str) -> bool {
+    prefix.bytes().enumerate().all(|(i, c)| {
+        let nibble = if i % 2 == 0 {
+            hash[i / 2] >> 4
+        } else {
+            hash[i / 2] & 0xf
+        };
+        char::from_digit(nibble as u32, 16) == Some(c as char)
+    })
+}
+
+/// Whether the mnemonics are an Electrum seed of the given type
+pub fn is_seed_type<D: Language, const W: usize>(
+    dict: &D,
+    mnemonics: &Mnemonics<W>,
+    seed_type: SeedType,
+) -> bool {
+    has_prefix(&version_hash(dict, mnemonics), seed_type.prefix())
+}
+
+/// The type of the Electrum seed, or None if the mnemonics are not an Electrum seed
+pub fn seed_type<D: Language, const W: usize>(
+    dict: &D,
+    mnemonics: &Mnemonics<W>,
+) -> Option<SeedType> {
+    let hash = version_hash(dict, mnemonics);
+    SeedType::ALL
+        .into_iter()
+        .find(|seed_type| has_prefix(&hash, seed_type.prefix()))
+}
+
+// whether all the words are in the v1 wordlist, as checked by Electrum
+#[cfg(feature = "rand")]
+fn is_v1_phrase<D: Language, const W: usize>(dict: &D, mnemonics: &Mnemonics<W>) -> bool {
+    (W == 12 || W == 24)
+        && mnemonics
+            .indices()
+            .all(|index| V1_WORDLIST.lookup_mnemonic(index.to_word(dict)).is_ok())
+}
+
+/// Generate new mnemonics of W words (12 in Electrum) of the given type, using the
+/// given cryptographically secure random generator (with the `rand` feature)
+///
+/// Random words are drawn from the random generator, then incremented (as a
+/// number in base 2048, with the first word as the least significant digit)
+/// until the phrase has the version prefix. As done by Electrum, phrases which
+/// are also valid v1 phrases, or have a valid BIP39 checksum, are skipped, so
+/// that the phrase can't be mistaken for one of them.
+///
+/// On average 256 (`01`) or 4096 (other types) phrases are tried.
+///
+/// # Error
+///
+/// Any error from the random generator is returned, and mnemonics of 0 words are
+/// rejected with [`GenerateError::NonStandardWords`].
+#[cfg(feature = "rand")]
+pub fn generate<D, R, const W: usize>(
+    dict: &D,
+    seed_type: SeedType,
+    rng: &mut R,
+) -> Result<Mnemonics<W>, GenerateError>
+where
+    D: Language,
+    R: RngCore + CryptoRng,
+{
+    if W == 0 {
+        return Err(GenerateError::NonStandardWords { words: W });
+    }
+    let mut indices = [MnemonicIndex(0); W];
+    for index in indices.iter_mut() {
+        let mut bytes = [0u8; 2];
+        rng.try_fill_bytes(&mut bytes)?;
+        *index = MnemonicIndex(u16::from_be_bytes(bytes) & MAX_MNEMONIC_VALUE);
+    }
+    // the most significant word is not zero, so that the number has all the words
+    if indices[W - 1].0 == 0 {
+        indices[W - 1].0 = 1;
+    }
+    loop {
+        for index in indices.iter_mut() {
+            if index.0 == MAX_MNEMONIC_VALUE {
+                index.0 = 0;
+            } else {
+                index.0 += 1;
+                break;
+            }
+        }
+        let mnemonics = Mnemonics::from(indices);
+        if is_seed_type(dict, &mnemonics, seed_type)
+            && !is_v1_phrase(dict, &mnemonics)
+            && mnemonics.check_standard_checksum().is_err()
+        {
+            return Ok(mnemonics);
+        }
+    }
+}
+
+/// Derive the seed of Electrum mnemonics with the given (normalized) passphrase
+///
+/// The type of the seed is not checked, see [`seed_type`].
+pub fn seed<D: Language, const W: usize>(
+    dict: &D,
+    mnemonics: &Mnemonics<W>,
+    passphrase: &[u8],
+) -> Seed<64> {
+    Seed::from_bytes(SeedScheme::electrum().derive(dict, mnemonics, passphrase))
+}
@@ -0,0 +1,199 @@
+//! Electrum v1 seeds
+//!
+//! The first versions of Electrum used a 128 bits seed, written as 12 words from
+//! a list of 1626 words, each group of 3 words encoding 32 bits of the seed:
+//!
+//! ```text
+//! x = w1 + n * ((w2 - w1) mod n) + n² * ((w3 - w2) mod n)     with n = 1626
+//! ```
+//!
+//! The wordlist is available as [`V1_WORDLIST`], and [`V1Mnemonics`] converts
+//! between the phrases and the seed bytes.
+//!
+//! The wordlist is not a [`Language`](crate::dictionary::Language): a language has a word
+//! for each of the 2048 indices, so that any [`Mnemonics`](crate::Mnemonics) can be
+//! rendered, while only the indices lower than 1626 have a word here.
+use super::v1_words::WORDS;
+use crate::dictionary::WordNotFound;
+use crate::index::MnemonicIndex;
+use crate::mnemonics::MnemonicError;
+
+#[cfg(not(feature = "std"))]
+use core::fmt;
+#[cfg(feature = "std")]
+use {std::error::Error, std::fmt};
+
+/// The wordlist of the Electrum v1 phrases, of 1626 words
+pub struct V1Wordlist;
+
+/// The Electrum v1 wordlist
+pub const V1_WORDLIST: V1Wordlist = V1Wordlist;
+
+impl V1Wordlist {
+    /// Number of words in the list, 1626
+    pub const fn size(&self) -> usize {
+        WORDS.len()
+    }
+
+    /// Lookup the index of the given word, or an error if not found
+    #[cfg(feature = "hardened")]
+    pub fn lookup_mnemonic(&self, word: &str) -> Result<MnemonicIndex, WordNotFound> {
+        match crate::ct::position(&WORDS, word) {
+            None => Err(WordNotFound::new(word)),
+            Some(v) => Ok(MnemonicIndex(v as u16)),
+        }
+    }
+
+    /// Lookup the index of the given word, or an error if not found
+    #[cfg(not(feature = "hardened"))]
+    pub fn lookup_mnemonic(&self, word: &str) -> Result<MnemonicIndex, WordNotFound> {
+        match WORDS.iter().position(|x| x == &word) {
+            None => Err(WordNotFound::new(word)),
+            Some(v) => Ok(MnemonicIndex(v as u16)),
+        }
+    }
+
+    /// Lookup the word of the given index, or None if the index is not lower than 1626
+    pub fn lookup_word(&self, mnemonic: MnemonicIndex) -> Option<&'static str> {
+        WORDS.get(mnemonic.0 as usize).copied()
+    }
+}
+
+/// Possible errors when converting between Electrum v1 phrases and seed bytes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum V1Error {
+    /// The number of bytes is not a multiple of 4, or doesn't match the number of words
+    InvalidParameters {
+        /// number of bytes
+        bytes: usize,
+        /// number of words
+        words: usize,
+    },
+    /// The group of 3 words at the given index doesn't encode a 32 bits value
+    Overflow(usize),
+}
+
+impl fmt::Display for V1Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidParameters { bytes, words } => {
+                write!(f, "Invalid parameters: {} bytes in {} words", bytes, words)
+            }
+            Self::Overflow(group) => write!(f, "Group of words {} overflows", group),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for V1Error {}
+
+/// Electrum v1 phrase of W words (12 in Electrum), in the v1 wordlist
+///
+/// The `Display` implementation writes the phrase, while the `Debug`
+/// implementation doesn't display the words.
+///
+/// # Example
+///
+/// ```
+/// use bip39_dict::electrum::V1Mnemonics;
+///
+/// let phrase = "hardly point goal hallway patience key stone difference ready caught listen fact";
+/// let mnemonics = V1Mnemonics::<12>::from_string(phrase).unwrap();
+/// let seed: [u8; 16] = mnemonics.to_bytes().unwrap();
+/// assert_eq!(seed[0..4], [0x8e, 0xda, 0xd3, 0x1a]);
+/// assert_eq!(V1Mnemonics::<12>::from_bytes(&seed).unwrap(), mnemonics);
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct V1Mnemonics<const W: usize>([MnemonicIndex; W]);
+
+impl<const W: usize> V1Mnemonics<W> {
+    fn check_parameters<const N: usize>() -> Result<(), V1Error> {
+        if N.is_multiple_of(4) && N / 4 * 3 == W {
+            Ok(())
+        } else {
+            Err(V1Error::InvalidParameters { bytes: N, words: W })
+        }
+    }
+
+    /// Encode the seed bytes, 3 words for every 4 bytes
+    pub fn from_bytes<const N: usize>(bytes: &[u8; N]) -> Result<Self, V1Error> {
+        Self::check_parameters::<N>()?;
+        let n = V1_WORDLIST.size() as u32;
+        let mut words = [MnemonicIndex(0); W];
+        for (chunk, group) in bytes.chunks(4).zip(words.chunks_mut(3)) {
+            let x = u32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+            let w1 = x % n;
+            let w2 = (x / n + w1) % n;
+            let w3 = (x / n / n + w2) % n;
+            group[0] = MnemonicIndex(w1 as u16);
+            group[1] = MnemonicIndex(w2 as u16);
+            group[2] = MnemonicIndex(w3 as u16);
+        }
+        Ok(V1Mnemonics(words))
+    }
+
+    /// Decode the seed bytes, 4 bytes for every 3 words
+    pub fn to_bytes<const N: usize>(&self) -> Result<[u8; N], V1Error> {
+        Self::check_parameters::<N>()?;
+        let n = V1_WORDLIST.size() as u64;
+        let mut bytes = [0u8; N];
+        for (i, (chunk, group)) in bytes.chunks_mut(4).zip(self.0.chunks(3)).enumerate() {
+            let [w1, w2, w3] = [group[0].0 as u64, group[1].0 as u64, group[2].0 as u64];
+            let x = w1 + n * ((w2 + n - w1) % n) + n * n * ((w3 + n - w2) % n);
+            match u32::try_from(x) {
+                Ok(x) => chunk.copy_from_slice(&x.to_be_bytes()),
+                Err(_) => {
+                    crate::ct::zeroize(&mut bytes);
+                    return Err(V1Error::Overflow(i));
+                }
+            }
+        }
+        Ok(bytes)
+    }
+
+    /// Parse the phrase, with the words separated by any whitespaces
+    pub fn from_string(phrase: &str) -> Result<Self, MnemonicError> {
+        let mut words = [MnemonicIndex(0); W];
+        let mut len = 0;
+        for (i, word) in phrase.split_whitespace().enumerate() {
+            if i < W {
+                words[i] = V1_WORDLIST
+                    .lookup_mnemonic(word)
+                    .map_err(|err| MnemonicError::WordError { index: i, err })?;
+            }
+            len += 1;
+        }
+        if len == W {
+            Ok(V1Mnemonics(words))
+        } else {
+            Err(MnemonicError::InvalidWords {
+                expected_words: W,
+                got_words: len,
+            })
+        }
+    }
+
+    /// Indices of the words in the v1 wordlist, all lower than 1626
+    pub fn indices(&self) -> impl Iterator<Item = &MnemonicIndex> {
+        self.0.iter()
+    }
+}
+
+impl<const W: usize> fmt::Display for V1Mnemonics<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, index) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            // the indices are lower than 1626 by construction
+            f.write_str(V1_WORDLIST.lookup_word(*index).ok_or(fmt::Error)?)?;
+        }
+        Ok(())
+    }
+}
+
+impl<const W: usize> fmt::Debug for V1Mnemonics<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "V1Mnemonics<{}>(<redacted>)", W)
+    }
+}
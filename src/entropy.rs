@@ -1,4 +1,5 @@
-use super::bits;
+use super::bits::{self, BitOrder, Msb};
+use super::ct;
 use super::index::*;
 use super::mnemonics::*;
 use cryptoxide::hashing::sha2::Sha256;
@@ -12,9 +13,17 @@ use {std::error::Error, std::fmt};
 ///
 /// See module documentation for mode details about how to use
 /// `Entropy`.
+///
+/// The `Debug` implementation doesn't display the content.
 #[derive(PartialEq, Eq, PartialOrd, Ord, Clone)]
 pub struct Entropy<const N: usize>(pub [u8; N]);
 
+impl<const N: usize> fmt::Debug for Entropy<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Entropy<{}>(<redacted>)", N)
+    }
+}
+
 /// Possible error when trying to create entropy from the mnemonics
 #[derive(Debug, Clone)]
 pub enum EntropyError {
@@ -29,6 +38,9 @@ pub enum EntropyError {
     },
     /// Mismatch in checksum
     ChecksumInvalid,
+    /// The redundant computation didn't match the original computation,
+    /// which is likely the result of a fault (only with the `hardened` feature)
+    FaultDetected,
 }
 
 impl fmt::Display for EntropyError {
@@ -46,6 +58,7 @@ impl fmt::Display for EntropyError {
                 )
             }
             Self::ChecksumInvalid => write!(f, "Invalid Checksum"),
+            Self::FaultDetected => write!(f, "Fault Detected"),
         }
     }
 }
@@ -55,9 +68,9 @@ impl Error for EntropyError {}
 
 impl<const N: usize> Entropy<N> {
     /// generate entropy using the given random generator.
-    pub fn generate<G>(gen: G) -> Self
+    pub fn generate<G>(mut gen: G) -> Self
     where
-        G: Fn() -> u8,
+        G: FnMut() -> u8,
     {
         let mut bytes = [0u8; N];
         for e in bytes.iter_mut() {
@@ -107,8 +120,79 @@ impl<const N: usize> Entropy<N> {
     pub fn from_mnemonics<const W: usize, const CS: usize>(
         mnemonics: &Mnemonics<W>,
     ) -> Result<Self, EntropyError> {
-        assert!(CS <= 256);
+        Self::from_mnemonics_with::<W, CS, Msb>(mnemonics, Self::full_checksum_data)
+    }
+
+    // from_mnemonics with the given bit order, and the given checksum bits
+    // following the entropy
+    pub(crate) fn from_mnemonics_with<const W: usize, const CS: usize, O: BitOrder>(
+        mnemonics: &Mnemonics<W>,
+        checksum: fn(&Self) -> [u8; 32],
+    ) -> Result<Self, EntropyError> {
+        Self::check_parameters::<W, CS>()?;
+        let ret = Self::decode::<W, CS, O>(mnemonics, checksum)?;
+
+        // redundantly check the result by going back to the mnemonics, so that
+        // a fault injected during the decoding doesn't go unnoticed
+        #[cfg(feature = "hardened")]
+        if ret
+            .encode::<W, CS, O>(checksum)
+            .indices()
+            .ne(mnemonics.indices())
+        {
+            return Err(EntropyError::FaultDetected);
+        }
+
+        Ok(ret)
+    }
+
+    /// convert the given `Entropy` into a mnemonic phrase of W words.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")]
+    /// # {
+    /// # use bip39_dict::{ENGLISH, Entropy};
+    ///
+    /// let entropy = Entropy::<16>([0;16]);
+    ///
+    /// // convert the 16 bytes entropy into 12 words with 4 bits of checksum
+    /// let mnemonics = entropy.to_mnemonics::<12, 4>()
+    ///     .expect("correct value of words/checksum for 16 bytes entropy")
+    ///     .to_string(&ENGLISH);
+    /// assert_eq!(mnemonics, "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about");
+    /// # }
+    /// ```
+    ///
+    pub fn to_mnemonics<const W: usize, const CS: usize>(
+        &self,
+    ) -> Result<Mnemonics<W>, EntropyError> {
+        self.to_mnemonics_with::<W, CS, Msb>(Self::full_checksum_data)
+    }
+
+    // to_mnemonics with the given bit order, and the given checksum bits
+    // following the entropy
+    pub(crate) fn to_mnemonics_with<const W: usize, const CS: usize, O: BitOrder>(
+        &self,
+        checksum: fn(&Self) -> [u8; 32],
+    ) -> Result<Mnemonics<W>, EntropyError> {
+        Self::check_parameters::<W, CS>()?;
+        let mnemonics = self.encode::<W, CS, O>(checksum);
 
+        // redundantly check the result by going back to the entropy, so that
+        // a fault injected during the encoding doesn't go unnoticed
+        #[cfg(feature = "hardened")]
+        match Self::decode::<W, CS, O>(&mnemonics, checksum) {
+            Ok(entropy) if ct::eq_bits(&entropy.0, &self.0, N * 8) => {}
+            _ => return Err(EntropyError::FaultDetected),
+        }
+
+        Ok(mnemonics)
+    }
+
+    fn check_parameters<const W: usize, const CS: usize>() -> Result<(), EntropyError> {
+        assert!(CS <= 256);
         let total_bits = N * 8 + CS;
         if total_bits != Mnemonics::<W>::BITS {
             return Err(EntropyError::InvalidParameters {
@@ -117,22 +201,31 @@ impl<const N: usize> Entropy<N> {
                 words: W,
             });
         }
+        Ok(())
+    }
+
+    // decode and verify the checksum, assuming the parameters have been checked
+    fn decode<const W: usize, const CS: usize, O: BitOrder>(
+        mnemonics: &Mnemonics<W>,
+        checksum: fn(&Self) -> [u8; 32],
+    ) -> Result<Self, EntropyError> {
         use bits::BitWriterBy11;
 
         let mut entropy = [0u8; N];
         let mut entropy_writer_pos = 0;
         let mut checksum_data = [0u8; 256];
 
-        // emit the byte to entropy for the N first byte, then to the checksum_data
+        // emit the byte to entropy for the N first byte, then to the checksum_data,
+        // kept in the packing order as its last byte is partial
         let emit = |b: u8| {
             if entropy_writer_pos >= N {
-                checksum_data[entropy_writer_pos - N] = b;
+                checksum_data[entropy_writer_pos - N] = O::byte(b);
             } else {
                 entropy[entropy_writer_pos] = b;
             }
             entropy_writer_pos += 1;
         };
-        let mut to_validate = BitWriterBy11::new(emit);
+        let mut to_validate = BitWriterBy11::<_, O>::new(emit);
         for mnemonic in mnemonics.indices() {
             to_validate.write(mnemonic.0);
         }
@@ -141,64 +234,24 @@ impl<const N: usize> Entropy<N> {
         let ret = Self(entropy);
 
         // check the checksum got from the mnemonics, from the one calculated
-        // from the entropy generated
-        let expected_checksum = ret.full_checksum_data();
-        if CS > 0 {
-            let checksum_data = &checksum_data[0..(entropy_writer_pos - N)];
-            let mut rem = CS;
-            let mut ofs = 0;
-            while rem > 0 {
-                if rem >= 8 {
-                    if checksum_data[ofs] != expected_checksum[ofs] {
-                        return Err(EntropyError::ChecksumInvalid);
-                    }
-                    rem -= 8;
-                } else {
-                    // process up to 7 bits
-                    let mask = ((1 << rem) - 1) << (8 - rem);
-                    if (checksum_data[ofs] & mask) != (expected_checksum[ofs] & mask) {
-                        return Err(EntropyError::ChecksumInvalid);
-                    }
-                    rem = 0;
-                }
-                ofs += 1;
-            }
+        // from the entropy generated. The comparison doesn't stop on the first
+        // difference, to not leak how much of the checksum is valid.
+        let expected_checksum = checksum(&ret).map(O::byte);
+        if !ct::eq_bits(&checksum_data, &expected_checksum, CS) {
+            return Err(EntropyError::ChecksumInvalid);
         }
 
         Ok(ret)
     }
 
-    /// convert the given `Entropy` into a mnemonic phrase of W words.
-    ///
-    /// # Example
-    ///
-    /// ```
-    /// # use bip39_dict::{ENGLISH, Entropy};
-    ///
-    /// let entropy = Entropy::<16>([0;16]);
-    ///
-    /// // convert the 16 bytes entropy into 12 words with 4 bits of checksum
-    /// let mnemonics = entropy.to_mnemonics::<12, 4>()
-    /// 	.expect("correct value of words/checksum for 16 bytes entropy")
-    ///     .to_string(&ENGLISH);
-    /// assert_eq!(mnemonics, "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about");
-    /// ```
-    ///
-    pub fn to_mnemonics<const W: usize, const CS: usize>(
+    // encode with the checksum, assuming the parameters have been checked
+    fn encode<const W: usize, const CS: usize, O: BitOrder>(
         &self,
-    ) -> Result<Mnemonics<W>, EntropyError> {
-        assert!(CS <= 256);
-        let total_bits = N * 8 + CS;
-        if total_bits != Mnemonics::<W>::BITS {
-            return Err(EntropyError::InvalidParameters {
-                checksum_bits: CS,
-                total_bits,
-                words: W,
-            });
-        }
+        checksum: fn(&Self) -> [u8; 32],
+    ) -> Mnemonics<W> {
         use bits::{NextRead, ReadState};
 
-        let checksum = self.full_checksum_data();
+        let checksum = checksum(self);
 
         let mut state = ReadState::default();
         let mut read_pos = 0;
@@ -212,19 +265,19 @@ impl<const N: usize> Entropy<N> {
                 self.0[read_pos]
             };
             read_pos += 1;
-            match state.read8(next_byte) {
+            match state.read8(O::byte(next_byte)) {
                 NextRead::Zero(next_state) => {
                     state = next_state;
                 }
                 NextRead::One(n, next_state) => {
-                    words[write_pos] = MnemonicIndex::new(n).unwrap();
+                    words[write_pos] = MnemonicIndex::new(O::word(n)).unwrap();
                     write_pos += 1;
                     state = next_state;
                 }
             }
         }
 
-        Ok(Mnemonics::<W>::from(words))
+        Mnemonics::<W>::from(words)
     }
 }
 
@@ -0,0 +1,261 @@
+//! Allocation free HMAC and PBKDF2
+//!
+//! The HMAC key can be streamed piece by piece through [`KeyStream`] (which
+//! also implements `fmt::Write`), so that a mnemonic phrase can be rendered
+//! directly into the key schedule, without building the phrase in memory first.
+use super::ct;
+use core::fmt;
+use cryptoxide::hashing::sha2::{Context256, Context512};
+
+// largest block size and output size of the supported hash functions
+const MAX_BLOCK: usize = 128;
+const MAX_OUTPUT: usize = 64;
+
+/// Hash function usable with HMAC
+pub(crate) trait Hash: Clone {
+    /// size in bytes of the internal block
+    const BLOCK: usize;
+    /// size in bytes of the digest
+    const OUTPUT: usize;
+
+    fn new() -> Self;
+    fn update(&mut self, data: &[u8]);
+    fn finalize_into(self, out: &mut [u8]);
+}
+
+impl Hash for Context256 {
+    const BLOCK: usize = 64;
+    const OUTPUT: usize = 32;
+
+    fn new() -> Self {
+        Context256::new()
+    }
+    fn update(&mut self, data: &[u8]) {
+        self.update_mut(data)
+    }
+    fn finalize_into(self, out: &mut [u8]) {
+        out.copy_from_slice(&self.finalize())
+    }
+}
+
+impl Hash for Context512 {
+    const BLOCK: usize = 128;
+    const OUTPUT: usize = 64;
+
+    fn new() -> Self {
+        Context512::new()
+    }
+    fn update(&mut self, data: &[u8]) {
+        self.update_mut(data)
+    }
+    fn finalize_into(self, out: &mut [u8]) {
+        out.copy_from_slice(&self.finalize())
+    }
+}
+
+// overwrite the hash state with a fresh one, in a way the compiler can't elide,
+// as the state of a keyed hash is as sensitive as the key
+fn wipe<H: Hash>(hasher: &mut H) {
+    // SAFETY: `hasher` is a valid and aligned reference, and the hash states
+    // don't own any resource, so overwriting without dropping doesn't leak
+    unsafe { core::ptr::write_volatile(hasher, H::new()) };
+    core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
+}
+
+/// HMAC key being built incrementally.
+///
+/// As long as the key fits in a block it is buffered, and as soon as it
+/// doesn't, it is hashed, as the HMAC key schedule would do.
+pub(crate) struct KeyStream<H: Hash> {
+    buf: [u8; MAX_BLOCK],
+    len: usize,
+    hasher: Option<H>,
+}
+
+impl<H: Hash> KeyStream<H> {
+    pub fn new() -> Self {
+        KeyStream {
+            buf: [0; MAX_BLOCK],
+            len: 0,
+            hasher: None,
+        }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        match &mut self.hasher {
+            Some(hasher) => hasher.update(data),
+            None if self.len + data.len() <= H::BLOCK => {
+                self.buf[self.len..self.len + data.len()].copy_from_slice(data);
+                self.len += data.len();
+            }
+            None => {
+                let mut hasher = H::new();
+                hasher.update(&self.buf[0..self.len]);
+                hasher.update(data);
+                self.hasher = Some(hasher);
+                ct::zeroize(&mut self.buf);
+                self.len = 0;
+            }
+        }
+    }
+
+    pub fn finalize(mut self) -> Hmac<H> {
+        if let Some(hasher) = self.hasher.take() {
+            hasher.finalize_into(&mut self.buf[0..H::OUTPUT]);
+        }
+        Hmac::from_block(&self.buf[0..H::BLOCK])
+    }
+}
+
+impl<H: Hash> Drop for KeyStream<H> {
+    fn drop(&mut self) {
+        ct::zeroize(&mut self.buf);
+        if let Some(hasher) = &mut self.hasher {
+            wipe(hasher);
+        }
+    }
+}
+
+impl<H: Hash> fmt::Write for KeyStream<H> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.update(s.as_bytes());
+        Ok(())
+    }
+}
+
+/// HMAC keyed state, ready to compute any number of MACs
+#[derive(Clone)]
+pub(crate) struct Hmac<H: Hash> {
+    inner: H,
+    outer: H,
+}
+
+impl<H: Hash> Hmac<H> {
+    pub fn new(key: &[u8]) -> Self {
+        let mut stream = KeyStream::new();
+        stream.update(key);
+        stream.finalize()
+    }
+
+    // key is exactly a block, already hashed or padded with zeros
+    fn from_block(key: &[u8]) -> Self {
+        let mut pad = [0u8; MAX_BLOCK];
+        let mut inner = H::new();
+        let mut outer = H::new();
+        for (p, k) in pad.iter_mut().zip(key) {
+            *p = k ^ 0x36;
+        }
+        inner.update(&pad[0..H::BLOCK]);
+        for (p, k) in pad.iter_mut().zip(key) {
+            *p = k ^ 0x5c;
+        }
+        outer.update(&pad[0..H::BLOCK]);
+        ct::zeroize(&mut pad);
+        Hmac { inner, outer }
+    }
+
+    /// compute the MAC of the concatenation of all the parts, into `out` of H::OUTPUT bytes
+    pub fn mac<'a, I: IntoIterator<Item = &'a [u8]>>(&self, parts: I, out: &mut [u8]) {
+        let mut inner = self.inner.clone();
+        for part in parts {
+            inner.update(part);
+        }
+        let mut inner_hash = [0u8; MAX_OUTPUT];
+        inner.finalize_into(&mut inner_hash[0..H::OUTPUT]);
+        let mut outer = self.outer.clone();
+        outer.update(&inner_hash[0..H::OUTPUT]);
+        ct::zeroize(&mut inner_hash);
+        outer.finalize_into(out);
+    }
+}
+
+impl<H: Hash> Drop for Hmac<H> {
+    fn drop(&mut self) {
+        wipe(&mut self.inner);
+        wipe(&mut self.outer);
+    }
+}
+
+/// PBKDF2 with the salt made of the concatenation of all the parts, filling the whole output
+pub(crate) fn pbkdf2<H: Hash>(mac: &Hmac<H>, salt: &[&[u8]], iterations: u32, output: &mut [u8]) {
+    assert!(iterations > 0);
+    let mut u = [0u8; MAX_OUTPUT];
+    for (i, chunk) in output.chunks_mut(H::OUTPUT).enumerate() {
+        // U1 = PRF(password, salt || INT(i))
+        let index = (i as u32 + 1).to_be_bytes();
+        mac.mac(
+            salt.iter().copied().chain([&index[..]]),
+            &mut u[0..H::OUTPUT],
+        );
+        let mut t = u;
+        // Uj = PRF(password, Uj-1)
+        for _ in 1..iterations {
+            let mut previous = u;
+            mac.mac([&previous[0..H::OUTPUT]], &mut u[0..H::OUTPUT]);
+            ct::zeroize(&mut previous);
+            for (t, u) in t.iter_mut().zip(u.iter()) {
+                *t ^= u;
+            }
+        }
+        chunk.copy_from_slice(&t[0..chunk.len()]);
+        ct::zeroize(&mut t);
+    }
+    ct::zeroize(&mut u);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rfc4231() {
+        // test case 1 & 6 (key larger than the block size)
+        let mut out = [0u8; 64];
+        Hmac::<Context512>::new(&[0x0b; 20]).mac([&b"Hi There"[..]], &mut out);
+        assert_eq!(hex::encode(out), "87aa7cdea5ef619d4ff0b4241a1d6cb02379f4e2ce4ec2787ad0b30545e17cdedaa833b7d6b8a702038b274eaea3f4e4be9d914eeb61f1702e696c203a126854");
+        let mut out = [0u8; 32];
+        Hmac::<Context256>::new(&[0xaa; 131]).mac(
+            [
+                &b"Test Using Larger Than Block-"[..],
+                b"Size Key - Hash Key First",
+            ],
+            &mut out,
+        );
+        assert_eq!(
+            hex::encode(out),
+            "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"
+        );
+    }
+
+    #[test]
+    fn key_stream() {
+        for len in [0, 1, 63, 64, 65, 127, 128, 129, 300] {
+            let key: [u8; 300] = core::array::from_fn(|i| i as u8);
+            let mut stream = KeyStream::<Context512>::new();
+            for piece in key[0..len].chunks(7) {
+                stream.update(piece);
+            }
+            let mut expected = [0u8; 64];
+            let mut got = [0u8; 64];
+            Hmac::<Context512>::new(&key[0..len]).mac([&b"data"[..]], &mut expected);
+            stream.finalize().mac([&b"data"[..]], &mut got);
+            assert_eq!(expected, got);
+        }
+    }
+
+    #[test]
+    fn wiped_on_drop() {
+        // a clone keeps working once the original state is wiped
+        let mac = Hmac::<Context512>::new(&[0x0b; 20]);
+        let copy = mac.clone();
+        drop(mac);
+        let mut out = [0u8; 64];
+        copy.mac([&b"Hi There"[..]], &mut out);
+        assert_eq!(hex::encode(out), "87aa7cdea5ef619d4ff0b4241a1d6cb02379f4e2ce4ec2787ad0b30545e17cdedaa833b7d6b8a702038b274eaea3f4e4be9d914eeb61f1702e696c203a126854");
+
+        let mut state = Context256::new();
+        state.update_mut(b"key");
+        wipe(&mut state);
+        assert_eq!(state.finalize(), Context256::new().finalize());
+    }
+}
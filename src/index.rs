@@ -1,5 +1,10 @@
 use super::dictionary;
 
+#[cfg(not(feature = "std"))]
+use core::fmt;
+#[cfg(feature = "std")]
+use std::fmt;
+
 /// the maximum authorized value for a mnemonic. i.e. 2047
 pub const MAX_MNEMONIC_VALUE: u16 = 2047;
 
@@ -9,9 +14,18 @@ pub const MAX_MNEMONIC_VALUE: u16 = 2047;
 /// See [`dictionary module documentation`](./dictionary/index.html) for
 /// more details about how to use this.
 ///
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Copy, Clone)]
+/// The index identifies a secret word, so the `Debug` implementation doesn't
+/// display it.
+///
+#[derive(PartialEq, Eq, PartialOrd, Ord, Copy, Clone)]
 pub struct MnemonicIndex(pub u16);
 
+impl fmt::Debug for MnemonicIndex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "MnemonicIndex(<redacted>)")
+    }
+}
+
 impl MnemonicIndex {
     /// smart constructor, validate the given value fits the mnemonic index
     /// boundaries (see [`MAX_MNEMONIC_VALUE`](./constant.MAX_MNEMONIC_VALUE.html)).
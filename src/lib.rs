@@ -43,6 +43,7 @@
 //!
 //! // create a 36 words mnemonics with 12 bits checksum
 //! let mnemonics = entropy.to_mnemonics::<36, 12>().unwrap();
+//! # #[cfg(feature = "alloc")]
 //! let mnemonics_string = mnemonics.to_string(&ENGLISH);
 //! ```
 //1
@@ -64,6 +65,99 @@
 //! let seed: [u8; 64] = seed_from_mnemonics(&ENGLISH, &mnemonics, b"some password", 2048);
 //! ```
 //!
+//! # Random generation
+//!
+//! With the `rand` feature, [`Entropy`] and standard [`Mnemonics`] can be generated from
+//! any random generator implementing the `rand_core` `RngCore` and `CryptoRng` traits.
+//! The `getrandom` feature adds convenience functions using the operating system
+//! random generator:
+//!
+//! ```
+//! # #[cfg(feature = "getrandom")]
+//! # {
+//! use bip39_dict::{Mnemonics, ENGLISH};
+//!
+//! let mnemonics = Mnemonics::<24>::generate_os().expect("OS random generator");
+//! let phrase = mnemonics.to_string(&ENGLISH);
+//! # }
+//! ```
+//!
+//! # BIP32
+//!
+//! With the `bip32` feature, the [`bip32`] module derives the BIP32 master key from
+//! a [`Seed`], and the child keys along derivation paths:
+//!
+//! ```
+//! # #[cfg(feature = "bip32")]
+//! # {
+//! use bip39_dict::{Entropy, Seed, ENGLISH};
+//! use bip39_dict::bip32::{DerivationPath, ExtendedPrivateKey, Network};
+//!
+//! let mnemonics = Entropy::<16>([0; 16]).to_mnemonics::<12, 4>().unwrap();
+//! let seed = Seed::<64>::from_mnemonics(&ENGLISH, &mnemonics, b"");
+//! let master = ExtendedPrivateKey::master(seed.as_ref(), Network::Mainnet).unwrap();
+//! let path = DerivationPath::parse("m/84'/0'/0'").unwrap();
+//! let xpub = master.derive_path(&path).unwrap().to_public().to_string();
+//! # }
+//! ```
+//!
+//! The [`account`] module derives the standard BIP44, BIP49, BIP84 and BIP86
+//! accounts, with their output descriptors and addresses, to check a restored
+//! wallet against the original one.
+//!
+//! With the `slip10` feature, the [`slip10`] module derives keys for the ed25519,
+//! NIST P-256 and secp256k1 curves as specified by SLIP-10.
+//!
+//! With the `bip85` feature, the [`bip85`] module derives child mnemonics, keys
+//! and passwords from a BIP32 root key as specified by BIP85.
+//!
+//! With the `cardano` feature, the [`cardano`] module derives the Cardano root keys
+//! of the Icarus, Ledger and Byron schemes, as specified by CIP-3.
+//!
+//! # Backup splitting
+//!
+//! With the `slip39` feature, the [`slip39`] module splits an [`Entropy`] in
+//! SLIP-39 share phrases, organized in groups, and recovers it from them.
+//!
+//! With the `shamir` feature, the [`shamir`] module splits an [`Entropy`] in
+//! Shamir shares written with the BIP39 wordlists, using the same flexible
+//! words / checksum scheme as [`Entropy::to_mnemonics`].
+//!
+//! With the `seed_xor` feature, the [`seed_xor`] module splits 12, 18 or 24 words
+//! phrases in several valid phrases of the same size which XOR to the original,
+//! compatible with Coldcard's Seed XOR.
+//!
+//! Splitting draws from a cryptographically secure random generator, and needs
+//! the `rand` feature; recovering doesn't.
+//!
+//! # Other wallet formats
+//!
+//! With the `electrum` feature, the [`electrum`] module checks, generates (with the
+//! `rand` feature) and derives the seed of Electrum phrases, which use a version prefix
+//! instead of the BIP39 checksum, and converts the phrases of the first Electrum versions,
+//! in their own wordlist.
+//!
+//! With the `aezeed` feature, the [`aezeed`] module enciphers and deciphers the
+//! aezeed phrases of the LND Lightning wallets, holding the wallet birthday.
+//!
+//! With the `algorand` feature, the [`algorand`] module converts between Algorand
+//! private keys and their 25 words mnemonics.
+//!
+//! # Allocation
+//!
+//! Without the `alloc` feature (enabled by `std`), the crate doesn't allocate: the
+//! errors are fixed-size, phrases are rendered with [`Mnemonics::write_to`] or
+//! [`Mnemonics::display`], and seeds are derived without building the phrase in memory.
+//! The conveniences needing an allocator, like [`Mnemonics::to_string`], are only
+//! available with the `alloc` feature.
+//!
+//! # Hardened mode
+//!
+//! With the `hardened` feature, the words lookup in the default dictionaries is
+//! done with a constant time scan of the whole dictionary, and the conversions
+//! between [`Entropy`] and [`Mnemonics`] are redundantly computed to detect
+//! fault injection (see [`EntropyError::FaultDetected`]).
+//!
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![deny(missing_docs)]
@@ -72,21 +166,66 @@
 #[macro_use]
 extern crate std;
 
-#[cfg(not(feature = "std"))]
+#[cfg(all(feature = "alloc", not(feature = "std")))]
 extern crate alloc;
 
+#[cfg(feature = "bip32")]
+pub mod account;
+#[cfg(feature = "aezeed")]
+pub mod aezeed;
+#[cfg(feature = "algorand")]
+pub mod algorand;
+#[cfg(feature = "bip32")]
+mod base58;
+#[cfg(feature = "bip32")]
+mod bech32;
+#[cfg(feature = "bip32")]
+pub mod bip32;
+#[cfg(feature = "bip85")]
+pub mod bip85;
 mod bits;
+#[cfg(feature = "cardano")]
+pub mod cardano;
+mod ct;
 mod dictionary;
+#[cfg(feature = "electrum")]
+pub mod electrum;
 mod entropy;
+mod hmac;
 mod index;
+mod mixer;
 mod mnemonics;
+mod physical;
+mod quality;
+#[cfg(feature = "rand")]
+mod rng;
+#[cfg(any(feature = "slip39", feature = "shamir"))]
+mod secret_sharing;
 mod seed;
+#[cfg(feature = "seed_xor")]
+pub mod seed_xor;
+#[cfg(feature = "shamir")]
+pub mod shamir;
+#[cfg(feature = "slip10")]
+pub mod slip10;
+#[cfg(feature = "slip39")]
+pub mod slip39;
 
 pub use dictionary::*;
 pub use entropy::{Entropy, EntropyError};
 pub use index::MnemonicIndex;
-pub use mnemonics::{MnemonicError, Mnemonics};
-pub use seed::seed_from_mnemonics;
+pub use mixer::{EntropyMixer, MixError, MixSource, MixTranscript, MAX_MIX_SOURCES};
+pub use mnemonics::{MnemonicError, Mnemonics, MnemonicsDisplay};
+pub use physical::PhysicalEntropyError;
+pub use quality::{QualityReport, Weakness};
+#[cfg(feature = "rand")]
+pub use rng::GenerateError;
+pub use seed::{
+    seed_from_mnemonics, seed_from_phrase, seed_from_phrase_checked, substrate_mini_secret,
+    substrate_seed_from_entropy, InvalidCost, PhraseError, Prf, Seed, SeedDerivation, SeedScheme,
+};
+#[cfg(feature = "alloc")]
+pub use seed::{seed_from_mnemonics_argon2id, seed_from_mnemonics_scrypt, Argon2Cost, ScryptCost};
 
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,330 @@
+//! Entropy mixing
+//!
+//! Combine multiple independent sources of randomness (e.g. a hardware random
+//! generator and user-supplied dice rolls) into a single [`Entropy`], so that
+//! no single source controls the result.
+//!
+//! The sources are combined with SHA512, each source being prefixed by its
+//! label and its length:
+//!
+//! ```text
+//! output = SHA512("bip39-dict-mixer-v1"
+//!                 || for each source: u32be(len(label)) || label || u32be(len(data)) || data)[0..N]
+//! ```
+//!
+//! The mixing produces a [`MixTranscript`], which records for every source its
+//! label, length and HMAC-SHA256 digest, and the HMAC-SHA256 digest of the output.
+//! The digests are keyed with a random nonce given to the mixer and kept by the
+//! user: with the nonce, the transcript allows to verify offline that the output
+//! was derived from the sources provided, and without it the transcript doesn't
+//! reveal the sources nor the output.
+//!
+//! Anyone knowing the nonce can check guesses of the sources against the
+//! transcript, which is quick for sources of low entropy like a few dice rolls:
+//! the nonce must stay as secret as the output.
+
+use super::ct;
+use super::entropy::Entropy;
+use super::hmac::Hmac;
+use cryptoxide::hashing::sha2::{Context256, Context512, Sha512};
+
+#[cfg(not(feature = "std"))]
+use core::fmt;
+#[cfg(feature = "std")]
+use {std::error::Error, std::fmt};
+
+/// Maximum number of sources that can be mixed together
+pub const MAX_MIX_SOURCES: usize = 8;
+
+const DOMAIN: &[u8] = b"bip39-dict-mixer-v1";
+
+/// Possible error when mixing entropy
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MixError {
+    /// More than [`MAX_MIX_SOURCES`] sources
+    TooManySources,
+    /// Less than 2 sources, which defeat the purpose of mixing
+    NotEnoughSources {
+        /// number of sources added
+        sources: usize,
+    },
+    /// A source has no data
+    EmptySource {
+        /// index of the source
+        index: usize,
+    },
+    /// The requested entropy size is bigger than the mixer output (64 bytes)
+    OutputTooLarge {
+        /// number of bytes of entropy asked
+        bytes: usize,
+    },
+}
+
+impl fmt::Display for MixError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooManySources => write!(f, "Too many sources, maximum {}", MAX_MIX_SOURCES),
+            Self::NotEnoughSources { sources } => {
+                write!(f, "Not enough sources, got {} but need at least 2", sources)
+            }
+            Self::EmptySource { index } => write!(f, "Source {} is empty", index),
+            Self::OutputTooLarge { bytes } => {
+                write!(f, "Output too large {} bytes, maximum 64", bytes)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for MixError {}
+
+/// Transcript entry of a mixed source
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MixSource<'a> {
+    /// label of the source
+    pub label: &'a str,
+    /// number of bytes of the source
+    pub length: usize,
+    /// HMAC-SHA256 of the source data, keyed with the nonce
+    pub digest: [u8; 32],
+}
+
+/// Auditable record of an entropy mixing
+///
+/// The `Display` implementation gives a textual representation of the
+/// transcript, that can be printed or saved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MixTranscript<'a> {
+    sources: [Option<MixSource<'a>>; MAX_MIX_SOURCES],
+    output_length: usize,
+    output_digest: [u8; 32],
+}
+
+impl<'a> MixTranscript<'a> {
+    /// sources in the order they have been mixed
+    pub fn sources(&self) -> impl Iterator<Item = &MixSource<'a>> {
+        self.sources.iter().flatten()
+    }
+
+    /// number of bytes of the output
+    pub fn output_length(&self) -> usize {
+        self.output_length
+    }
+
+    /// HMAC-SHA256 of the output, keyed with the nonce
+    pub fn output_digest(&self) -> &[u8; 32] {
+        &self.output_digest
+    }
+
+    /// Verify that the given data is the source at `index` recorded in this transcript
+    pub fn verify_source<D: AsRef<[u8]> + ?Sized>(
+        &self,
+        nonce: &[u8; 32],
+        index: usize,
+        data: &D,
+    ) -> bool {
+        let data = data.as_ref();
+        match self.sources().nth(index) {
+            Some(source) => {
+                source.length == data.len()
+                    && ct::eq_bits(&digest(&Hmac::new(nonce), data), &source.digest, 256)
+            }
+            None => false,
+        }
+    }
+
+    /// Verify that the given entropy is the output recorded in this transcript
+    pub fn verify_output<const N: usize>(&self, nonce: &[u8; 32], entropy: &Entropy<N>) -> bool {
+        N == self.output_length
+            && ct::eq_bits(
+                &digest(&Hmac::new(nonce), &entropy.0),
+                &self.output_digest,
+                256,
+            )
+    }
+}
+
+// HMAC-SHA256 of the data, keyed with the nonce
+fn digest(mac: &Hmac<Context256>, data: &[u8]) -> [u8; 32] {
+    let mut digest = [0u8; 32];
+    mac.mac([data], &mut digest);
+    digest
+}
+
+struct Hex<'b>(&'b [u8]);
+
+impl fmt::Display for Hex<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for b in self.0 {
+            write!(f, "{:02x}", b)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for MixTranscript<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", core::str::from_utf8(DOMAIN).unwrap())?;
+        for (i, source) in self.sources().enumerate() {
+            writeln!(
+                f,
+                "source {}: label={:?} length={} hmac-sha256={}",
+                i,
+                source.label,
+                source.length,
+                Hex(&source.digest)
+            )?;
+        }
+        write!(
+            f,
+            "output: length={} hmac-sha256={}",
+            self.output_length,
+            Hex(&self.output_digest)
+        )
+    }
+}
+
+/// Mix multiple sources of randomness into an [`Entropy`]
+///
+/// # Example
+///
+/// ```
+/// use bip39_dict::{Entropy, EntropyMixer};
+///
+/// # let hardware = Entropy::<32>([1; 32]);
+/// # let dice = Entropy::<32>::from_dice_rolls(&[4; 100]).unwrap();
+/// # let nonce = [7u8; 32];
+/// let mut mixer = EntropyMixer::new(&nonce);
+/// mixer.add("hardware", &hardware).unwrap();
+/// mixer.add("dice", &dice).unwrap();
+/// let (entropy, transcript) = mixer.finalize::<32>().unwrap();
+/// assert!(transcript.verify_output(&nonce, &entropy));
+/// assert!(transcript.verify_source(&nonce, 1, &dice));
+/// println!("{}", transcript);
+/// ```
+pub struct EntropyMixer<'a> {
+    context: Context512,
+    mac: Hmac<Context256>,
+    sources: [Option<MixSource<'a>>; MAX_MIX_SOURCES],
+    count: usize,
+}
+
+impl<'a> EntropyMixer<'a> {
+    /// Create a new mixer without any source, keying the digests of the transcript
+    /// with the given nonce
+    ///
+    /// The nonce is expected to be random, and is needed to verify the transcript.
+    /// It doesn't change the output.
+    pub fn new(nonce: &[u8; 32]) -> Self {
+        EntropyMixer {
+            context: Sha512::new().update(DOMAIN),
+            mac: Hmac::new(nonce),
+            sources: [None; MAX_MIX_SOURCES],
+            count: 0,
+        }
+    }
+
+    /// Add a source of randomness, identified by the given label
+    ///
+    /// The data can be any bytes, e.g. an [`Entropy`] or the raw
+    /// output of a random generator.
+    pub fn add<D: AsRef<[u8]> + ?Sized>(
+        &mut self,
+        label: &'a str,
+        data: &D,
+    ) -> Result<(), MixError> {
+        let data = data.as_ref();
+        if self.count == MAX_MIX_SOURCES {
+            return Err(MixError::TooManySources);
+        }
+        if data.is_empty() {
+            return Err(MixError::EmptySource { index: self.count });
+        }
+        self.context.update_mut(&(label.len() as u32).to_be_bytes());
+        self.context.update_mut(label.as_bytes());
+        self.context.update_mut(&(data.len() as u32).to_be_bytes());
+        self.context.update_mut(data);
+        self.sources[self.count] = Some(MixSource {
+            label,
+            length: data.len(),
+            digest: digest(&self.mac, data),
+        });
+        self.count += 1;
+        Ok(())
+    }
+
+    /// Mix all the sources into an entropy of N bytes, along with its transcript
+    pub fn finalize<const N: usize>(self) -> Result<(Entropy<N>, MixTranscript<'a>), MixError> {
+        if N > 64 {
+            return Err(MixError::OutputTooLarge { bytes: N });
+        }
+        if self.count < 2 {
+            return Err(MixError::NotEnoughSources {
+                sources: self.count,
+            });
+        }
+        let mut mixed = self.context.finalize();
+        let entropy = Entropy::<N>::from_slice(&mixed[0..N]).unwrap();
+        ct::zeroize(&mut mixed);
+        let transcript = MixTranscript {
+            sources: self.sources,
+            output_length: N,
+            output_digest: digest(&self.mac, &entropy.0),
+        };
+        Ok((entropy, transcript))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mix() {
+        let nonce = [9u8; 32];
+        let mut mixer = EntropyMixer::new(&nonce);
+        mixer.add("a", &[1u8, 2]).unwrap();
+        mixer.add("bc", &Entropy::<3>([3, 4, 5])).unwrap();
+        let (entropy, transcript) = mixer.finalize::<16>().unwrap();
+
+        let expected = Sha512::new()
+            .update(b"bip39-dict-mixer-v1")
+            .update(&[0, 0, 0, 1, b'a', 0, 0, 0, 2, 1, 2])
+            .update(&[0, 0, 0, 2, b'b', b'c', 0, 0, 0, 3, 3, 4, 5])
+            .finalize();
+        assert_eq!(entropy.0, expected[0..16]);
+        assert!(transcript.verify_output(&nonce, &entropy));
+        assert!(!transcript.verify_output(&[0; 32], &entropy));
+        assert_eq!(transcript.sources().count(), 2);
+        let mut digest = [0u8; 32];
+        Hmac::<Context256>::new(&nonce).mac([&[3u8, 4, 5][..]], &mut digest);
+        assert_eq!(transcript.sources().nth(1).unwrap().digest, digest);
+        assert!(transcript.verify_source(&nonce, 1, &[3u8, 4, 5]));
+        assert!(!transcript.verify_source(&nonce, 1, &[3u8, 4, 6]));
+        assert!(!transcript.verify_source(&nonce, 2, &[3u8, 4, 5]));
+    }
+
+    #[test]
+    fn mix_errors() {
+        let mut mixer = EntropyMixer::new(&[0; 32]);
+        mixer.add("a", &[1u8]).unwrap();
+        assert_eq!(
+            mixer.add("b", &[0u8; 0]),
+            Err(MixError::EmptySource { index: 1 })
+        );
+        assert_eq!(
+            mixer.finalize::<16>().unwrap_err(),
+            MixError::NotEnoughSources { sources: 1 }
+        );
+
+        let mut mixer = EntropyMixer::new(&[0; 32]);
+        for _ in 0..MAX_MIX_SOURCES {
+            mixer.add("a", &[1u8]).unwrap();
+        }
+        assert_eq!(mixer.add("a", &[1u8]), Err(MixError::TooManySources));
+        assert_eq!(
+            mixer.finalize::<65>().unwrap_err(),
+            MixError::OutputTooLarge { bytes: 65 }
+        );
+    }
+}
@@ -1,7 +1,10 @@
 use super::dictionary;
+use super::entropy::{Entropy, EntropyError};
 use super::index::MnemonicIndex;
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::string::String;
 #[cfg(not(feature = "std"))]
-use {alloc::string::String, core::fmt};
+use core::fmt;
 #[cfg(feature = "std")]
 use {std::error::Error, std::fmt, std::string::String};
 
@@ -17,9 +20,18 @@ use {std::error::Error, std::fmt, std::string::String};
 /// the derived key of a HDWallet using the [`Seed`](./struct.Seed.html)
 /// as a source to generate the root key.
 ///
-#[derive(Debug, PartialEq, Eq, Clone)]
+/// The `Debug` implementation doesn't display the words, to avoid leaking
+/// the mnemonics in logs by mistake.
+///
+#[derive(PartialEq, Eq, Clone)]
 pub struct Mnemonics<const W: usize>([MnemonicIndex; W]);
 
+impl<const W: usize> fmt::Debug for Mnemonics<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Mnemonics<{}>(<redacted>)", W)
+    }
+}
+
 impl<const W: usize> AsRef<[MnemonicIndex]> for Mnemonics<W> {
     fn as_ref(&self) -> &[MnemonicIndex] {
         &self.0[..]
@@ -42,6 +54,13 @@ pub enum MnemonicError {
         /// the error returned by the dictionary
         err: dictionary::WordNotFound,
     },
+    /// Invalid Word in mnemonics, without the word itself
+    RedactedWordError {
+        /// index of the words having an issue
+        index: usize,
+        /// the length class of the word having an issue
+        length: dictionary::WordLength,
+    },
     /// Number of words does not match expectation set by the function
     InvalidWords {
         /// number of expected words
@@ -55,6 +74,9 @@ impl fmt::Display for MnemonicError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::WordError { index, err } => write!(f, "at {}: {}", index, err),
+            Self::RedactedWordError { index, length } => {
+                write!(f, "at {}: {} word not found in dictionary", index, length)
+            }
             Self::InvalidWords {
                 expected_words,
                 got_words,
@@ -70,6 +92,20 @@ impl fmt::Display for MnemonicError {
 #[cfg(feature = "std")]
 impl Error for MnemonicError {}
 
+impl MnemonicError {
+    /// drop the raw word from the error, keeping only its position
+    /// and its length class.
+    pub fn redact(self) -> Self {
+        match self {
+            Self::WordError { index, err } => Self::RedactedWordError {
+                index,
+                length: err.length(),
+            },
+            err => err,
+        }
+    }
+}
+
 impl<const W: usize> Mnemonics<W> {
     /// Size in bits of each element of mnemonics
     pub const BITS: usize = W * 11;
@@ -77,18 +113,59 @@ impl<const W: usize> Mnemonics<W> {
     /// get the mnemonic string representation in the given
     /// [`Language`](./dictionary/trait.Language.html).
     ///
+    /// Only available with the `alloc` feature, see [`Mnemonics::write_to`]
+    /// and [`Mnemonics::display`] otherwise.
+    ///
+    #[cfg(feature = "alloc")]
     pub fn to_string<D>(&self, dict: &D) -> String
     where
         D: dictionary::Language,
     {
         let mut out = String::new();
+        // writing to a String never fails
+        let _ = self.write_to(dict, &mut out);
+        out
+    }
+
+    /// write the mnemonic string representation in the given
+    /// [`Language`](./dictionary/trait.Language.html) to the given writer,
+    /// without any allocation.
+    ///
+    pub fn write_to<D, F>(&self, dict: &D, writer: &mut F) -> fmt::Result
+    where
+        D: dictionary::Language,
+        F: fmt::Write,
+    {
         for (i, m) in self.0.iter().enumerate() {
             if i > 0 {
-                out.push_str(dict.separator());
+                writer.write_str(dict.separator())?;
             }
-            out.push_str(&m.to_word(dict))
+            writer.write_str(m.to_word(dict))?;
+        }
+        Ok(())
+    }
+
+    /// get an object implementing `Display` that render the mnemonic string
+    /// representation in the given [`Language`](./dictionary/trait.Language.html).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bip39_dict::{ENGLISH, Entropy};
+    /// let mnemonics = Entropy::<16>([0; 16]).to_mnemonics::<12, 4>().unwrap();
+    /// assert_eq!(
+    ///     format!("{}", mnemonics.display(&ENGLISH)),
+    ///     "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about"
+    /// );
+    /// ```
+    pub fn display<'a, D>(&'a self, dict: &'a D) -> MnemonicsDisplay<'a, D, W>
+    where
+        D: dictionary::Language,
+    {
+        MnemonicsDisplay {
+            mnemonics: self,
+            dict,
         }
-        out
     }
 
     /// Construct the `Mnemonics` from its string representation in the given
@@ -115,8 +192,81 @@ impl<const W: usize> Mnemonics<W> {
         }
     }
 
+    /// Construct the `Mnemonics` from a sequence of words in the given
+    /// [`Language`](./dictionary/trait.Language.html).
+    ///
+    pub fn from_words<'a, D, I>(dic: &D, words: I) -> Result<Self, MnemonicError>
+    where
+        D: dictionary::Language,
+        I: IntoIterator<Item = &'a str>,
+    {
+        let mut output = [MnemonicIndex(0); W];
+        let mut len = 0;
+        for (i, word) in words.into_iter().enumerate() {
+            if i < W {
+                output[i] = MnemonicIndex::from_word(dic, word)
+                    .map_err(|err| MnemonicError::WordError { index: i, err })?;
+            }
+            len += 1;
+        }
+        if len == W {
+            Ok(Self(output))
+        } else {
+            Err(MnemonicError::InvalidWords {
+                expected_words: W,
+                got_words: len,
+            })
+        }
+    }
+
+    /// Check that the mnemonics has a valid checksum for the standard
+    /// BIP39 encoding, i.e. 12, 15, 18, 21 or 24 words with 1 bit of
+    /// checksum for every 3 words.
+    ///
+    pub fn check_standard_checksum(&self) -> Result<(), EntropyError> {
+        match W {
+            12 => Entropy::<16>::from_mnemonics::<W, 4>(self).map(|_| ()),
+            15 => Entropy::<20>::from_mnemonics::<W, 5>(self).map(|_| ()),
+            18 => Entropy::<24>::from_mnemonics::<W, 6>(self).map(|_| ()),
+            21 => Entropy::<28>::from_mnemonics::<W, 7>(self).map(|_| ()),
+            24 => Entropy::<32>::from_mnemonics::<W, 8>(self).map(|_| ()),
+            _ => Err(EntropyError::InvalidParameters {
+                checksum_bits: W / 3,
+                total_bits: Self::BITS,
+                words: W,
+            }),
+        }
+    }
+
+    /// Same as [`Mnemonics::from_string`] but any invalid word is reported
+    /// with [`MnemonicError::RedactedWordError`], so that the error
+    /// doesn't hold any of the user input.
+    ///
+    pub fn from_string_redacted<D>(dic: &D, mnemonics: &str) -> Result<Self, MnemonicError>
+    where
+        D: dictionary::Language,
+    {
+        Self::from_string(dic, mnemonics).map_err(MnemonicError::redact)
+    }
+
     /// Indices iterator for each mnemonic words
     pub fn indices(&self) -> impl Iterator<Item = &MnemonicIndex> {
         self.0.iter()
     }
 }
+
+/// Render [`Mnemonics`] in a given [`Language`](./dictionary/trait.Language.html)
+/// through its `Display` implementation.
+///
+/// Created by [`Mnemonics::display`]. Contrary to `Mnemonics`, the `Display`
+/// implementation reveal the words.
+pub struct MnemonicsDisplay<'a, D, const W: usize> {
+    mnemonics: &'a Mnemonics<W>,
+    dict: &'a D,
+}
+
+impl<'a, D: dictionary::Language, const W: usize> fmt::Display for MnemonicsDisplay<'a, D, W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.mnemonics.write_to(self.dict, f)
+    }
+}
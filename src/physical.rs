@@ -0,0 +1,342 @@
+//! Entropy from physical sources
+//!
+//! Users without a trusted random generator (e.g. on an air-gapped machine) can
+//! generate entropy with dice, coins or a deck of cards. The conversions follow
+//! the tools commonly used for this, so the same values give the same entropy:
+//!
+//! | Source       | Conversion                                         | Compatible with | Maximum N |
+//! | ------------ | -------------------------------------------------- | --------------- | --------- |
+//! | dice rolls   | SHA256 of the rolls as ASCII digits `1`-`6`        | Coldcard        | 32        |
+//! | dice rolls   | rolls as a base 6 number, `6` being `0`            | Ian Coleman     | 32        |
+//! | coin flips   | one bit per flip, most significant bit first       |                 | unbounded |
+//! | card draws   | SHA256 of the cards as 2 characters codes and `:0` | Ian Coleman     | 28        |
+//!
+//! The number of rolls of the Coldcard conversion is the one required by
+//! Coldcard: 50 rolls for 16 bytes, and 99 rolls for 32 bytes, which carry
+//! ~255.9 bits of entropy. In general, the rolls need to carry at least
+//! `N * 8 - 1` bits of entropy.
+//!
+//! The conversions of Ian Coleman's BIP39 tool are the ones of its raw entropy
+//! mode: the entropy is the last `N * 8` bits of the binary entropy shown by the
+//! tool, which is the whole entropy of the tool when it gives `N` bytes. The base 6
+//! conversion needs at least `N * 8` bits of entropy (e.g. 100 rolls for 32 bytes),
+//! and the number of cards is the one for which the tool counts `N * 8` bits,
+//! assuming ~4.34 bits per card (e.g. 30 cards for 16 bytes).
+
+use super::entropy::Entropy;
+use cryptoxide::hashing::sha2::Sha256;
+
+#[cfg(not(feature = "std"))]
+use core::fmt;
+#[cfg(feature = "std")]
+use {std::error::Error, std::fmt};
+
+/// Possible error when creating entropy from a physical source
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PhysicalEntropyError {
+    /// The value at the given index is not valid for the source
+    InvalidValue {
+        /// index of the value
+        index: usize,
+    },
+    /// The card at the given index has already been drawn
+    DuplicateCard {
+        /// index of the card
+        index: usize,
+    },
+    /// Not enough values to reach the requested entropy size
+    NotEnoughEntropy {
+        /// number of values required
+        required: usize,
+        /// number of values received
+        got: usize,
+    },
+    /// The requested entropy size cannot be generated from this source
+    UnsupportedSize {
+        /// number of bytes of entropy asked
+        bytes: usize,
+    },
+}
+
+impl PhysicalEntropyError {
+    /// number of values still needed to reach the requested entropy size
+    pub fn remaining(&self) -> Option<usize> {
+        match self {
+            Self::NotEnoughEntropy { required, got } => Some(required.saturating_sub(*got)),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for PhysicalEntropyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidValue { index } => write!(f, "Invalid value at index {}", index),
+            Self::DuplicateCard { index } => write!(f, "Duplicate card at index {}", index),
+            Self::NotEnoughEntropy { required, got } => write!(
+                f,
+                "Not enough entropy, {} more values needed ({} required)",
+                required.saturating_sub(*got),
+                required
+            ),
+            Self::UnsupportedSize { bytes } => {
+                write!(f, "Unsupported entropy size of {} bytes", bytes)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for PhysicalEntropyError {}
+
+/// number of cards in a deck
+const DECK_SIZE: usize = 52;
+
+/// log2(52!), the bits of entropy of a shuffled deck
+const DECK_BITS: f64 = 225.58100312370277;
+
+const CARD_RANKS: &[u8; 13] = b"A23456789TJQK";
+const CARD_SUITS: &[u8; 4] = b"CDHS";
+
+// Minimal unsigned big integer, only capable of multiplication by a small
+// factor, used to compute exactly the number of values required.
+struct Accumulator([u32; 10]);
+
+impl Accumulator {
+    fn one() -> Self {
+        let mut limbs = [0; 10];
+        limbs[0] = 1;
+        Accumulator(limbs)
+    }
+
+    fn mul(&mut self, factor: u32) {
+        let mut carry = 0u64;
+        for limb in self.0.iter_mut() {
+            let v = (*limb as u64) * (factor as u64) + carry;
+            *limb = v as u32;
+            carry = v >> 32;
+        }
+    }
+
+    // check if the accumulated value is greater or equal to 2^bits
+    fn has_bits(&self, bits: usize) -> bool {
+        match self.0.iter().rposition(|l| *l != 0) {
+            None => false,
+            Some(i) => i * 32 + (32 - self.0[i].leading_zeros() as usize) > bits,
+        }
+    }
+}
+
+// the bits of entropy counted by Ian Coleman's tool for a number of cards drawn
+// from a single deck: the entropy of the full deck, pro rata of the cards drawn
+fn card_bits(cards: usize) -> usize {
+    (DECK_BITS * cards as f64 / DECK_SIZE as f64) as usize
+}
+
+// the number of values needed so that the product of the number of possibilities
+// for each value is at least 2^bits, or None if more than `max_values` are needed
+fn values_required<F>(bits: usize, max_values: usize, possibilities: F) -> Option<usize>
+where
+    F: Fn(usize) -> u32,
+{
+    let mut acc = Accumulator::one();
+    for i in 0..=max_values {
+        if acc.has_bits(bits) {
+            return Some(i);
+        }
+        if i < max_values {
+            acc.mul(possibilities(i));
+        }
+    }
+    None
+}
+
+impl<const N: usize> Entropy<N> {
+    /// number of 6-sided dice rolls required by [`Entropy::from_dice_rolls`]
+    /// for this entropy size, as required by Coldcard
+    ///
+    /// Returns None if the entropy size cannot be generated from dice rolls
+    pub fn dice_rolls_required() -> Option<usize> {
+        if N > 32 {
+            return None;
+        }
+        values_required((N * 8).saturating_sub(1), usize::MAX, |_| 6)
+    }
+
+    /// number of 6-sided dice rolls required by [`Entropy::from_dice_rolls_base6`]
+    /// for this entropy size
+    ///
+    /// Returns None if the entropy size cannot be generated from dice rolls
+    pub fn dice_rolls_base6_required() -> Option<usize> {
+        if N > 32 {
+            return None;
+        }
+        values_required(N * 8, usize::MAX, |_| 6)
+    }
+
+    /// number of coin flips required for this entropy size
+    pub fn coin_flips_required() -> usize {
+        N * 8
+    }
+
+    /// number of cards to draw from a shuffled 52 cards deck for this entropy size
+    ///
+    /// Returns None if a full deck doesn't carry enough entropy
+    pub fn card_draws_required() -> Option<usize> {
+        (1..=DECK_SIZE).find(|cards| card_bits(*cards) >= N * 8)
+    }
+
+    /// create entropy from a sequence of 6-sided dice rolls
+    ///
+    /// Each roll is a value between 1 and 6, and all the rolls are hashed
+    /// with SHA256 as their ASCII digits, as done by Coldcard. At least
+    /// [`Entropy::dice_rolls_required`] rolls need to be given (99 rolls for
+    /// 32 bytes), and only entropy up to 32 bytes is supported.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bip39_dict::{Entropy, PhysicalEntropyError};
+    /// let rolls = [3, 1, 6, 6, 2];
+    /// match Entropy::<16>::from_dice_rolls(&rolls) {
+    ///     Err(PhysicalEntropyError::NotEnoughEntropy { required, got }) => {
+    ///         assert_eq!(required - got, 45);
+    ///     }
+    ///     _ => panic!("expecting more rolls"),
+    /// }
+    /// ```
+    pub fn from_dice_rolls(rolls: &[u8]) -> Result<Self, PhysicalEntropyError> {
+        let required = Self::dice_rolls_required()
+            .ok_or(PhysicalEntropyError::UnsupportedSize { bytes: N })?;
+        if let Some(index) = rolls.iter().position(|r| !(1..=6).contains(r)) {
+            return Err(PhysicalEntropyError::InvalidValue { index });
+        }
+        if rolls.len() < required {
+            return Err(PhysicalEntropyError::NotEnoughEntropy {
+                required,
+                got: rolls.len(),
+            });
+        }
+
+        let mut context = Sha256::new();
+        for roll in rolls {
+            context.update_mut(&[b'0' + roll]);
+        }
+        Ok(Self::from_digest(&context.finalize()))
+    }
+
+    /// create entropy from a sequence of 6-sided dice rolls, as a base 6 number
+    ///
+    /// Each roll is a value between 1 and 6, used as a base 6 digit where 6 is 0,
+    /// the first roll being the most significant digit. The entropy is the last
+    /// `N * 8` bits of this number, as the raw entropy of Ian Coleman's BIP39 tool.
+    /// At least [`Entropy::dice_rolls_base6_required`] rolls need to be given
+    /// (100 rolls for 32 bytes), and only entropy up to 32 bytes is supported.
+    pub fn from_dice_rolls_base6(rolls: &[u8]) -> Result<Self, PhysicalEntropyError> {
+        let required = Self::dice_rolls_base6_required()
+            .ok_or(PhysicalEntropyError::UnsupportedSize { bytes: N })?;
+        if let Some(index) = rolls.iter().position(|r| !(1..=6).contains(r)) {
+            return Err(PhysicalEntropyError::InvalidValue { index });
+        }
+        if rolls.len() < required {
+            return Err(PhysicalEntropyError::NotEnoughEntropy {
+                required,
+                got: rolls.len(),
+            });
+        }
+
+        // the number modulo 2^(N*8), big endian
+        let mut bytes = [0u8; N];
+        for roll in rolls {
+            let mut carry = (roll % 6) as u16;
+            for byte in bytes.iter_mut().rev() {
+                let v = (*byte as u16) * 6 + carry;
+                *byte = v as u8;
+                carry = v >> 8;
+            }
+        }
+        Ok(Self(bytes))
+    }
+
+    /// create entropy from a sequence of coin flips
+    ///
+    /// Each flip represents one bit (`true` for 1), the first flip being the most
+    /// significant bit of the first byte. At least [`Entropy::coin_flips_required`]
+    /// flips need to be given, and any flips after this are ignored.
+    pub fn from_coin_flips(flips: &[bool]) -> Result<Self, PhysicalEntropyError> {
+        let required = Self::coin_flips_required();
+        if flips.len() < required {
+            return Err(PhysicalEntropyError::NotEnoughEntropy {
+                required,
+                got: flips.len(),
+            });
+        }
+
+        let mut bytes = [0u8; N];
+        for (i, flip) in flips[0..required].iter().enumerate() {
+            bytes[i / 8] |= (*flip as u8) << (7 - (i % 8));
+        }
+        Ok(Self(bytes))
+    }
+
+    /// create entropy from a sequence of cards drawn from a shuffled 52 cards deck
+    ///
+    /// Each card is a value between 0 and 51: `suit * 13 + rank` where the suits
+    /// are in the order clubs, diamonds, hearts, spades and the ranks in the order
+    /// ace, 2 to 10, jack, queen, king. The cards are drawn without replacement,
+    /// so each card can only appear once.
+    ///
+    /// As in Ian Coleman's BIP39 tool, the cards are hashed with SHA256 as 2 characters
+    /// codes (rank `A23456789TJQK` followed by suit `CDHS`, e.g. `AS` for the ace of
+    /// spades) followed by `:0`. The tool only keeps the first ~4.34 bits per card of
+    /// the hash, and the entropy is the last `N * 8` bits of those. At least
+    /// [`Entropy::card_draws_required`] cards need to be given.
+    pub fn from_card_draws(cards: &[u8]) -> Result<Self, PhysicalEntropyError> {
+        let required = Self::card_draws_required()
+            .ok_or(PhysicalEntropyError::UnsupportedSize { bytes: N })?;
+        let mut seen = 0u64;
+        for (index, card) in cards.iter().enumerate() {
+            if *card as usize >= DECK_SIZE {
+                return Err(PhysicalEntropyError::InvalidValue { index });
+            }
+            let bit = 1u64 << card;
+            if seen & bit != 0 {
+                return Err(PhysicalEntropyError::DuplicateCard { index });
+            }
+            seen |= bit;
+        }
+        if cards.len() < required {
+            return Err(PhysicalEntropyError::NotEnoughEntropy {
+                required,
+                got: cards.len(),
+            });
+        }
+
+        let mut context = Sha256::new();
+        for card in cards {
+            let (suit, rank) = (card / 13, card % 13);
+            context.update_mut(&[CARD_RANKS[rank as usize], CARD_SUITS[suit as usize]]);
+        }
+        context.update_mut(b":0");
+        let digest = context.finalize();
+
+        // bits [start, start + N * 8) of the digest, N * 8 <= bits <= 225
+        let start = card_bits(cards.len()) - N * 8;
+        let mut bytes = [0u8; N];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            let bit = start + i * 8;
+            let (index, shift) = (bit / 8, bit % 8);
+            *byte = digest[index] << shift;
+            if shift != 0 {
+                *byte |= digest[index + 1] >> (8 - shift);
+            }
+        }
+        Ok(Self(bytes))
+    }
+
+    fn from_digest(digest: &[u8; 32]) -> Self {
+        let mut bytes = [0u8; N];
+        bytes.copy_from_slice(&digest[0..N]);
+        Self(bytes)
+    }
+}
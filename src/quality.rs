@@ -0,0 +1,346 @@
+//! Entropy quality checks
+//!
+//! Detect entropy and mnemonics which are obviously not random (all zeros,
+//! repeated patterns, well known test vectors, ...), so that a wallet can
+//! warn its user before accepting a seed.
+//!
+//! Those checks only catch the most common mistakes: a random looking value
+//! can still be weak (e.g. generated by a broken random generator), but a
+//! value flagged by those checks is certainly weak.
+
+use super::bits::{BitWriterBy11, Msb};
+use super::ct;
+use super::entropy::Entropy;
+use super::mnemonics::Mnemonics;
+
+#[cfg(not(feature = "std"))]
+use core::fmt;
+#[cfg(feature = "std")]
+use std::fmt;
+
+/// Maximum number of weaknesses recorded in a [`QualityReport`]
+const MAX_WEAKNESSES: usize = 8;
+
+/// Minimum number of values to report an arithmetic sequence: any 2 values
+/// follow one, and shorter runs happen by chance
+const MIN_SEQUENCE: usize = 6;
+
+/// Bytes repeated in the entropy of the other BIP39 test vectors
+const KNOWN_REPEATED_BYTES: [u8; 4] = [0x00, 0x7f, 0x80, 0xff];
+
+/// Entropy of the BIP39 test vectors (excluding the ones made of a single repeated byte)
+const KNOWN_TEST_VECTORS: &[&[u8]] = &[
+    &[
+        0x04, 0x60, 0xef, 0x47, 0x58, 0x56, 0x04, 0xc5, 0x66, 0x06, 0x18, 0xdb, 0x2e, 0x6a, 0x7e,
+        0x7f,
+    ],
+    &[
+        0x18, 0xab, 0x19, 0xa9, 0xf5, 0x4a, 0x92, 0x74, 0xf0, 0x3e, 0x52, 0x09, 0xa2, 0xac, 0x8a,
+        0x91,
+    ],
+    &[
+        0x23, 0xdb, 0x81, 0x60, 0xa3, 0x1d, 0x3e, 0x0d, 0xca, 0x36, 0x88, 0xed, 0x94, 0x1a, 0xdb,
+        0xf3,
+    ],
+    &[
+        0x77, 0xc2, 0xb0, 0x07, 0x16, 0xce, 0xc7, 0x21, 0x38, 0x39, 0x15, 0x9e, 0x40, 0x4d, 0xb5,
+        0x0d,
+    ],
+    &[
+        0x9e, 0x88, 0x5d, 0x95, 0x2a, 0xd3, 0x62, 0xca, 0xeb, 0x4e, 0xfe, 0x34, 0xa8, 0xe9, 0x1b,
+        0xd2,
+    ],
+    &[
+        0xc0, 0xba, 0x5a, 0x8e, 0x91, 0x41, 0x11, 0x21, 0x0f, 0x2b, 0xd1, 0x31, 0xf3, 0xd5, 0xe0,
+        0x8d,
+    ],
+    &[
+        0xea, 0xeb, 0xab, 0xb2, 0x38, 0x33, 0x51, 0xfd, 0x31, 0xd7, 0x03, 0x84, 0x0b, 0x32, 0xe9,
+        0xe2,
+    ],
+    &[
+        0xf3, 0x0f, 0x8c, 0x1d, 0xa6, 0x65, 0x47, 0x8f, 0x49, 0xb0, 0x01, 0xd9, 0x4c, 0x5f, 0xc4,
+        0x52,
+    ],
+    &[
+        0x18, 0xa2, 0xe1, 0xd8, 0x1b, 0x8e, 0xcf, 0xb2, 0xa3, 0x33, 0xad, 0xcb, 0x0c, 0x17, 0xa5,
+        0xb9, 0xeb, 0x76, 0xcc, 0x5d, 0x05, 0xdb, 0x91, 0xa4,
+    ],
+    &[
+        0x66, 0x10, 0xb2, 0x59, 0x67, 0xcd, 0xcc, 0xa9, 0xd5, 0x98, 0x75, 0xf5, 0xcb, 0x50, 0xb0,
+        0xea, 0x75, 0x43, 0x33, 0x11, 0x86, 0x9e, 0x93, 0x0b,
+    ],
+    &[
+        0x6d, 0x9b, 0xe1, 0xee, 0x6e, 0xbd, 0x27, 0xa2, 0x58, 0x11, 0x5a, 0xad, 0x99, 0xb7, 0x31,
+        0x7b, 0x9c, 0x8d, 0x28, 0xb6, 0xd7, 0x64, 0x31, 0xc3,
+    ],
+    &[
+        0x72, 0xf6, 0x0e, 0xba, 0xc5, 0xdd, 0x8a, 0xdd, 0x8d, 0x2a, 0x25, 0xa7, 0x97, 0x10, 0x2c,
+        0x3c, 0xe2, 0x1b, 0xc0, 0x29, 0xc2, 0x00, 0x07, 0x6f,
+    ],
+    &[
+        0x7a, 0xc4, 0x5c, 0xfe, 0x77, 0x22, 0xee, 0x6c, 0x7b, 0xa8, 0x4f, 0xbc, 0x2d, 0x5b, 0xd6,
+        0x1b, 0x45, 0xcb, 0x2f, 0xe5, 0xeb, 0x65, 0xaa, 0x78,
+    ],
+    &[
+        0x81, 0x97, 0xa4, 0xa4, 0x7f, 0x04, 0x25, 0xfa, 0xea, 0xa6, 0x9d, 0xee, 0xbc, 0x05, 0xca,
+        0x29, 0xc0, 0xa5, 0xb5, 0xcc, 0x76, 0xce, 0xac, 0xc0,
+    ],
+    &[
+        0xb6, 0x3a, 0x9c, 0x59, 0xa6, 0xe6, 0x41, 0xf2, 0x88, 0xeb, 0xc1, 0x03, 0x01, 0x7f, 0x1d,
+        0xa9, 0xf8, 0x29, 0x0b, 0x3d, 0xa6, 0xbd, 0xef, 0x7b,
+    ],
+    &[
+        0xc1, 0x0e, 0xc2, 0x0d, 0xc3, 0xcd, 0x9f, 0x65, 0x2c, 0x7f, 0xac, 0x2f, 0x12, 0x30, 0xf7,
+        0xa3, 0xc8, 0x28, 0x38, 0x9a, 0x14, 0x39, 0x2f, 0x05,
+    ],
+    &[
+        0x06, 0x6d, 0xca, 0x1a, 0x2b, 0xb7, 0xe8, 0xa1, 0xdb, 0x28, 0x32, 0x14, 0x8c, 0xe9, 0x93,
+        0x3e, 0xea, 0x0f, 0x3a, 0xc9, 0x54, 0x8d, 0x79, 0x31, 0x12, 0xd9, 0xa9, 0x5c, 0x94, 0x07,
+        0xef, 0xad,
+    ],
+    &[
+        0x15, 0xda, 0x87, 0x2c, 0x95, 0xa1, 0x3d, 0xd7, 0x38, 0xfb, 0xf5, 0x0e, 0x42, 0x75, 0x83,
+        0xad, 0x61, 0xf1, 0x8f, 0xd9, 0x9f, 0x62, 0x8c, 0x41, 0x7a, 0x61, 0xcf, 0x83, 0x43, 0xc9,
+        0x04, 0x19,
+    ],
+    &[
+        0x2c, 0x85, 0xef, 0xc7, 0xf2, 0x4e, 0xe4, 0x57, 0x3d, 0x2b, 0x81, 0xa6, 0xec, 0x66, 0xce,
+        0xe2, 0x09, 0xb2, 0xdc, 0xbd, 0x09, 0xd8, 0xed, 0xdc, 0x51, 0xe0, 0x21, 0x5b, 0x0b, 0x68,
+        0xe4, 0x16,
+    ],
+    &[
+        0x3e, 0x14, 0x16, 0x09, 0xb9, 0x79, 0x33, 0xb6, 0x6a, 0x06, 0x0d, 0xcd, 0xdc, 0x71, 0xfa,
+        0xd1, 0xd9, 0x16, 0x77, 0xdb, 0x87, 0x20, 0x31, 0xe8, 0x5f, 0x4c, 0x01, 0x5c, 0x5e, 0x7e,
+        0x89, 0x82,
+    ],
+    &[
+        0x4f, 0xa1, 0xa8, 0xbc, 0x3e, 0x6d, 0x80, 0xee, 0x13, 0x16, 0x05, 0x0e, 0x86, 0x2c, 0x18,
+        0x12, 0x03, 0x14, 0x93, 0x21, 0x2b, 0x7e, 0xc3, 0xf3, 0xbb, 0x1b, 0x08, 0xf1, 0x68, 0xca,
+        0xbe, 0xef,
+    ],
+    &[
+        0x68, 0xa7, 0x9e, 0xac, 0xa2, 0x32, 0x48, 0x73, 0xea, 0xcc, 0x50, 0xcb, 0x9c, 0x6e, 0xca,
+        0x8c, 0xc6, 0x8e, 0xa5, 0xd9, 0x36, 0xf9, 0x87, 0x87, 0xc6, 0x0c, 0x7e, 0xbc, 0x74, 0xe6,
+        0xce, 0x7c,
+    ],
+    &[
+        0x9f, 0x6a, 0x28, 0x78, 0xb2, 0x52, 0x07, 0x99, 0xa4, 0x4e, 0xf1, 0x8b, 0xc7, 0xdf, 0x39,
+        0x4e, 0x70, 0x61, 0xa2, 0x24, 0xd2, 0xc3, 0x3c, 0xd0, 0x15, 0xb1, 0x57, 0xd7, 0x46, 0x86,
+        0x98, 0x63,
+    ],
+    &[
+        0xf5, 0x85, 0xc1, 0x1a, 0xec, 0x52, 0x0d, 0xb5, 0x7d, 0xd3, 0x53, 0xc6, 0x95, 0x54, 0xb2,
+        0x1a, 0x89, 0xb2, 0x0f, 0xb0, 0x65, 0x09, 0x66, 0xfa, 0x0a, 0x9d, 0x6f, 0x74, 0xfd, 0x98,
+        0x9d, 0x8f,
+    ],
+];
+
+// whether the entropy is one of the BIP39 test vectors
+fn is_known_test_vector(entropy: &[u8]) -> bool {
+    matches!(entropy.len(), 16 | 24 | 32)
+        && (KNOWN_TEST_VECTORS.contains(&entropy)
+            || KNOWN_REPEATED_BYTES
+                .iter()
+                .any(|r| entropy.iter().all(|b| b == r)))
+}
+
+/// A weakness detected in some entropy or mnemonics
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Weakness {
+    /// all the bytes (or words) are identical
+    Repeated,
+    /// a short pattern of bytes (or words) is repeated
+    RepeatedPattern {
+        /// length of the repeated pattern
+        period: usize,
+    },
+    /// the bytes (or words) follow an arithmetic sequence (e.g. 01 02 03 04 ...)
+    Sequence,
+    /// the entropy is a well known test vector
+    KnownTestVector,
+    /// very few distinct bytes (or words) compared to the size
+    FewDistinctValues {
+        /// number of distinct values
+        distinct: usize,
+    },
+    /// the number of bits set is far from half of the bits
+    UnbalancedBits {
+        /// number of bits set
+        ones: usize,
+        /// total number of bits
+        bits: usize,
+    },
+}
+
+impl fmt::Display for Weakness {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Repeated => write!(f, "the same value is repeated"),
+            Self::RepeatedPattern { period } => {
+                write!(f, "a pattern of {} values is repeated", period)
+            }
+            Self::Sequence => write!(f, "the values follow a simple sequence"),
+            Self::KnownTestVector => write!(f, "this is a well known test vector"),
+            Self::FewDistinctValues { distinct } => {
+                write!(f, "only {} distinct values", distinct)
+            }
+            Self::UnbalancedBits { ones, bits } => {
+                write!(f, "only {} bits out of {} are set", ones, bits)
+            }
+        }
+    }
+}
+
+/// Result of the quality checks
+///
+/// A report without any weakness doesn't mean the value is random,
+/// only that none of the checks detected a problem.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QualityReport {
+    weaknesses: [Option<Weakness>; MAX_WEAKNESSES],
+}
+
+impl QualityReport {
+    fn new() -> Self {
+        QualityReport {
+            weaknesses: [None; MAX_WEAKNESSES],
+        }
+    }
+
+    fn push(&mut self, weakness: Weakness) {
+        if let Some(slot) = self.weaknesses.iter_mut().find(|w| w.is_none()) {
+            *slot = Some(weakness)
+        }
+    }
+
+    /// Whether any weakness has been detected
+    pub fn is_weak(&self) -> bool {
+        self.weaknesses[0].is_some()
+    }
+
+    /// All the weaknesses detected
+    pub fn weaknesses(&self) -> impl Iterator<Item = &Weakness> {
+        self.weaknesses.iter().flatten()
+    }
+
+    // checks that apply to both bytes and words
+    fn check_values<T: Copy + PartialEq + Into<i32>>(&mut self, values: &[T]) {
+        if values.len() < 2 {
+            return;
+        }
+        if values.iter().all(|v| *v == values[0]) {
+            self.push(Weakness::Repeated);
+            return;
+        }
+        if let Some(period) =
+            (2..=values.len() / 2).find(|p| values.iter().zip(&values[*p..]).all(|(a, b)| a == b))
+        {
+            self.push(Weakness::RepeatedPattern { period });
+        }
+        let step = values[1].into() - values[0].into();
+        if values.len() >= MIN_SEQUENCE
+            && values.windows(2).all(|w| w[1].into() - w[0].into() == step)
+        {
+            self.push(Weakness::Sequence);
+        }
+        // a random sequence of at least 8 values has very few collisions
+        if values.len() >= 8 {
+            let distinct = values
+                .iter()
+                .enumerate()
+                .filter(|(i, v)| !values[..*i].contains(v))
+                .count();
+            if distinct * 2 < values.len() {
+                self.push(Weakness::FewDistinctValues { distinct });
+            }
+        }
+    }
+}
+
+impl<const N: usize> Entropy<N> {
+    /// Check the entropy for obvious weaknesses
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bip39_dict::{Entropy, Weakness};
+    /// let report = Entropy::<16>([0; 16]).analyze();
+    /// assert!(report.is_weak());
+    /// assert_eq!(report.weaknesses().next(), Some(&Weakness::Repeated));
+    /// ```
+    pub fn analyze(&self) -> QualityReport {
+        let mut report = QualityReport::new();
+        report.check_values(&self.0);
+        if is_known_test_vector(&self.0) {
+            report.push(Weakness::KnownTestVector);
+        }
+        // random bits follow a binomial distribution, with a standard deviation of
+        // sqrt(bits)/2: being off by more than a fourth of the bits is extremely unlikely
+        // as soon as there's a reasonable number of bits
+        let bits = N * 8;
+        if bits >= 64 {
+            let ones = self
+                .0
+                .iter()
+                .map(|b| b.count_ones() as usize)
+                .sum::<usize>();
+            if ones < bits / 4 || ones > bits - bits / 4 {
+                report.push(Weakness::UnbalancedBits { ones, bits });
+            }
+        }
+        report
+    }
+}
+
+impl<const W: usize> Mnemonics<W> {
+    /// Check the mnemonics for obvious weaknesses
+    ///
+    /// The mnemonics are expected to encode some entropy followed by `CS` bits of
+    /// checksum, as in [`Entropy::from_mnemonics`]. The checks are done on the
+    /// words, ignoring the words carrying checksum bits (the last word in the
+    /// standard BIP39 encoding): a phrase like `abandon abandon ... abandon about`
+    /// is reported as repeated. When the entropy is a whole number of bytes, it is
+    /// also compared to the BIP39 test vectors.
+    /// To check the bits of the entropy, use [`Entropy::analyze`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bip39_dict::{Entropy, Weakness};
+    /// let mnemonics = Entropy::<16>([0; 16]).to_mnemonics::<12, 4>().unwrap();
+    /// let report = mnemonics.analyze::<4>();
+    /// assert!(report.weaknesses().any(|w| *w == Weakness::KnownTestVector));
+    /// ```
+    pub fn analyze<const CS: usize>(&self) -> QualityReport {
+        let mut report = QualityReport::new();
+        let mut words = [0u16; W];
+        for (w, m) in words.iter_mut().zip(self.indices()) {
+            *w = m.0;
+        }
+        let checksum_words = CS.div_ceil(11);
+        report.check_values(&words[0..W.saturating_sub(checksum_words)]);
+
+        let bits = (W * 11).saturating_sub(CS);
+        if bits.is_multiple_of(8) && bits <= 256 {
+            let mut entropy = [0u8; 32];
+            let mut pos = 0;
+            let mut writer = BitWriterBy11::<_, Msb>::new(|b| {
+                if pos < entropy.len() {
+                    entropy[pos] = b;
+                }
+                pos += 1;
+            });
+            for w in words {
+                writer.write(w);
+            }
+            writer.finalize();
+            if is_known_test_vector(&entropy[0..bits / 8]) {
+                report.push(Weakness::KnownTestVector);
+            }
+            ct::zeroize(&mut entropy);
+        }
+        ct::zeroize(&mut words);
+        report
+    }
+}
@@ -0,0 +1,148 @@
+//! Generation of entropy and mnemonics from a cryptographically secure
+//! random number generator, using the [`rand_core`] traits.
+//!
+//! Contrary to [`Entropy::generate`], failure of the random number
+//! generator are reported to the caller.
+
+use super::entropy::{Entropy, EntropyError};
+use super::mnemonics::Mnemonics;
+use rand_core::{CryptoRng, RngCore};
+
+#[cfg(not(feature = "std"))]
+use core::fmt;
+#[cfg(feature = "std")]
+use {std::error::Error, std::fmt};
+
+/// Possible error when generating mnemonics from a random number generator
+#[derive(Debug)]
+pub enum GenerateError {
+    /// The random number generator failed
+    Rng(rand_core::Error),
+    /// The number of words asked is not one of the standard BIP39 values
+    NonStandardWords {
+        /// number of words asked
+        words: usize,
+    },
+    /// Error while converting the generated entropy to mnemonics
+    Entropy(EntropyError),
+}
+
+impl fmt::Display for GenerateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Rng(err) => write!(f, "Random generator error: {}", err),
+            Self::NonStandardWords { words } => {
+                write!(f, "Non standard number of words {}", words)
+            }
+            Self::Entropy(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for GenerateError {}
+
+impl From<rand_core::Error> for GenerateError {
+    fn from(err: rand_core::Error) -> Self {
+        Self::Rng(err)
+    }
+}
+
+impl From<EntropyError> for GenerateError {
+    fn from(err: EntropyError) -> Self {
+        Self::Entropy(err)
+    }
+}
+
+impl<const N: usize> Entropy<N> {
+    /// generate entropy using the given cryptographically secure random generator.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bip39_dict::Entropy;
+    /// # struct Rng;
+    /// # impl rand_core::RngCore for Rng {
+    /// #     fn next_u32(&mut self) -> u32 { 4 }
+    /// #     fn next_u64(&mut self) -> u64 { 4 }
+    /// #     fn fill_bytes(&mut self, dest: &mut [u8]) { dest.fill(4) }
+    /// #     fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> { Ok(dest.fill(4)) }
+    /// # }
+    /// # impl rand_core::CryptoRng for Rng {}
+    /// # let mut rng = Rng;
+    /// let entropy = Entropy::<32>::generate_from_rng(&mut rng)
+    ///     .expect("random generator working");
+    /// ```
+    ///
+    /// # Error
+    ///
+    /// Any error from the random generator is returned.
+    ///
+    pub fn generate_from_rng<R>(rng: &mut R) -> Result<Self, rand_core::Error>
+    where
+        R: RngCore + CryptoRng,
+    {
+        let mut bytes = [0u8; N];
+        rng.try_fill_bytes(&mut bytes)?;
+        Ok(Self(bytes))
+    }
+
+    /// generate entropy using the operating system random generator.
+    #[cfg(feature = "getrandom")]
+    pub fn generate_os() -> Result<Self, rand_core::Error> {
+        Self::generate_from_rng(&mut rand_core::OsRng)
+    }
+}
+
+fn generate_standard<R, const N: usize, const W: usize, const CS: usize>(
+    rng: &mut R,
+) -> Result<Mnemonics<W>, GenerateError>
+where
+    R: RngCore + CryptoRng,
+{
+    let entropy = Entropy::<N>::generate_from_rng(rng)?;
+    Ok(entropy.to_mnemonics::<W, CS>()?)
+}
+
+impl<const W: usize> Mnemonics<W> {
+    /// generate standard BIP39 mnemonics using the given cryptographically secure
+    /// random generator.
+    ///
+    /// The entropy size and the checksum size are the standard values associated
+    /// with the number of words:
+    ///
+    /// | Words | Entropy bytes | Checksum bits |
+    /// | ----- | ------------- | ------------- |
+    /// | 12    | 16            | 4             |
+    /// | 15    | 20            | 5             |
+    /// | 18    | 24            | 6             |
+    /// | 21    | 28            | 7             |
+    /// | 24    | 32            | 8             |
+    ///
+    /// # Error
+    ///
+    /// Any error from the random generator is returned, and any other number of words
+    /// is rejected with [`GenerateError::NonStandardWords`].
+    ///
+    pub fn generate_from_rng<R>(rng: &mut R) -> Result<Self, GenerateError>
+    where
+        R: RngCore + CryptoRng,
+    {
+        match W {
+            12 => generate_standard::<R, 16, W, 4>(rng),
+            15 => generate_standard::<R, 20, W, 5>(rng),
+            18 => generate_standard::<R, 24, W, 6>(rng),
+            21 => generate_standard::<R, 28, W, 7>(rng),
+            24 => generate_standard::<R, 32, W, 8>(rng),
+            _ => Err(GenerateError::NonStandardWords { words: W }),
+        }
+    }
+
+    /// generate standard BIP39 mnemonics using the operating system random generator.
+    ///
+    /// See [`Mnemonics::generate_from_rng`] for the supported number of words.
+    #[cfg(feature = "getrandom")]
+    pub fn generate_os() -> Result<Self, GenerateError> {
+        Self::generate_from_rng(&mut rand_core::OsRng)
+    }
+}
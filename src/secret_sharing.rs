@@ -0,0 +1,212 @@
+//! Shamir's secret sharing over GF(256), as specified by SLIP-39
+//!
+//! The shares of a threshold T are points of a polynomial of degree T-1,
+//! defined by T-2 random points, the secret at x = 255 and its digest at x = 254,
+//! so that a wrong set of shares is detected when recovering the secret.
+use crate::ct;
+use crate::hmac::Hmac;
+use cryptoxide::hashing::sha2::Context256;
+#[cfg(feature = "rand")]
+use rand_core::{CryptoRng, RngCore};
+
+/// Maximum number of shares (and threshold) of a split
+pub(crate) const MAX_SHARES: usize = 16;
+
+const DIGEST_INDEX: u8 = 254;
+const SECRET_INDEX: u8 = 255;
+const DIGEST_LENGTH: usize = 4;
+
+// exponentials and logarithms of the generator 3, in GF(256) with the Rijndael polynomial
+const fn tables() -> ([u8; 255], [u8; 256]) {
+    let mut exp = [0u8; 255];
+    let mut log = [0u8; 256];
+    let mut poly: u16 = 1;
+    let mut i = 0;
+    while i < 255 {
+        exp[i] = poly as u8;
+        log[poly as usize] = i as u8;
+        // multiply by 3
+        poly ^= poly << 1;
+        if poly & 0x100 != 0 {
+            poly ^= 0x11b;
+        }
+        i += 1;
+    }
+    (exp, log)
+}
+
+const EXP: [u8; 255] = tables().0;
+const LOG: [u8; 256] = tables().1;
+
+/// Evaluate at x the polynomial going through the points (with distinct x), into out
+pub(crate) fn interpolate(points: &[(u8, &[u8])], x: u8, out: &mut [u8]) {
+    if let Some((_, value)) = points.iter().find(|(px, _)| *px == x) {
+        out.copy_from_slice(value);
+        return;
+    }
+    // Lagrange basis polynomials, computed with logarithms:
+    // l_i(x) = prod((x - x_j) / (x_i - x_j)) for j != i, and substraction is xor
+    let log_prod: u32 = points
+        .iter()
+        .map(|(px, _)| LOG[(px ^ x) as usize] as u32)
+        .sum();
+    out.fill(0);
+    for (i, (xi, value)) in points.iter().enumerate() {
+        let log_denominator: u32 = points
+            .iter()
+            .enumerate()
+            .filter(|(j, _)| *j != i)
+            .map(|(_, (xj, _))| LOG[(xi ^ xj) as usize] as u32)
+            .sum();
+        let log_basis =
+            (log_prod + 255 * 32 - LOG[(xi ^ x) as usize] as u32 - log_denominator) % 255;
+        for (o, v) in out.iter_mut().zip(value.iter()) {
+            if *v != 0 {
+                *o ^= EXP[((LOG[*v as usize] as u32 + log_basis) % 255) as usize];
+            }
+        }
+    }
+}
+
+// first bytes of HMAC-SHA256(random part, secret)
+fn digest(random_part: &[u8], secret: &[u8]) -> [u8; DIGEST_LENGTH] {
+    let mut mac = [0u8; 32];
+    Hmac::<Context256>::new(random_part).mac([secret], &mut mac);
+    let mut out = [0u8; DIGEST_LENGTH];
+    out.copy_from_slice(&mac[0..DIGEST_LENGTH]);
+    out
+}
+
+/// Split the secret in `count` shares with the given threshold (both at most MAX_SHARES),
+/// giving each share index and value to `out`.
+///
+/// All the randomness is drawn before the first share is given: on a random
+/// generator error, no share is given and the error is returned.
+#[cfg(feature = "rand")]
+pub(crate) fn split_secret<const N: usize, R: RngCore + CryptoRng, F: FnMut(u8, &[u8; N])>(
+    threshold: u8,
+    count: u8,
+    secret: &[u8; N],
+    rng: &mut R,
+    mut out: F,
+) -> Result<(), rand_core::Error> {
+    assert!(threshold >= 1 && threshold <= count && count as usize <= MAX_SHARES);
+    if threshold == 1 {
+        for i in 0..count {
+            out(i, secret);
+        }
+        return Ok(());
+    }
+
+    // T-2 random shares, and the digest and secret shares
+    let random_count = threshold as usize - 2;
+    let mut base = [[0u8; N]; MAX_SHARES];
+    let mut xs = [0u8; MAX_SHARES];
+    let mut filled = Ok(());
+    for (i, value) in base[0..random_count + 1].iter_mut().enumerate() {
+        if filled.is_ok() {
+            filled = rng.try_fill_bytes(value);
+        }
+        xs[i] = i as u8;
+    }
+    if let Err(e) = filled {
+        for value in base.iter_mut() {
+            ct::zeroize(value);
+        }
+        return Err(e);
+    }
+    let digest_share = &mut base[random_count];
+    let d = digest(&digest_share[DIGEST_LENGTH..], secret);
+    digest_share[0..DIGEST_LENGTH].copy_from_slice(&d);
+    xs[random_count] = DIGEST_INDEX;
+    base[random_count + 1] = *secret;
+    xs[random_count + 1] = SECRET_INDEX;
+
+    let mut points: [(u8, &[u8]); MAX_SHARES] = [(0, &[]); MAX_SHARES];
+    for (p, (x, value)) in points.iter_mut().zip(xs.iter().zip(base.iter())) {
+        *p = (*x, &value[..]);
+    }
+    let points = &points[0..threshold as usize];
+
+    let mut share = [0u8; N];
+    for (i, value) in base[0..random_count].iter().enumerate() {
+        out(i as u8, value);
+    }
+    for i in random_count as u8..count {
+        interpolate(points, i, &mut share);
+        out(i, &share);
+    }
+    ct::zeroize(&mut share);
+    for value in base.iter_mut() {
+        ct::zeroize(value);
+    }
+    Ok(())
+}
+
+/// Recover the secret from exactly `threshold` shares of distinct indices,
+/// returning None if its digest doesn't match.
+pub(crate) fn recover_secret<const N: usize>(
+    threshold: u8,
+    points: &[(u8, &[u8])],
+) -> Option<[u8; N]> {
+    let mut secret = [0u8; N];
+    if threshold == 1 {
+        secret.copy_from_slice(points.first()?.1);
+        return Some(secret);
+    }
+    let mut digest_share = [0u8; N];
+    interpolate(points, SECRET_INDEX, &mut secret);
+    interpolate(points, DIGEST_INDEX, &mut digest_share);
+    let valid = ct::eq_bits(
+        &digest(&digest_share[DIGEST_LENGTH..], &secret),
+        &digest_share[0..DIGEST_LENGTH],
+        DIGEST_LENGTH * 8,
+    );
+    ct::zeroize(&mut digest_share);
+    if valid {
+        Some(secret)
+    } else {
+        ct::zeroize(&mut secret);
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn field() {
+        // 3 is a generator: all the non zero elements are reached once
+        let mut seen = [false; 256];
+        for e in EXP {
+            assert!(!seen[e as usize]);
+            seen[e as usize] = true;
+        }
+        assert!(!seen[0]);
+        // 0x53 * 0xca = 1 in the Rijndael field
+        assert_eq!((LOG[0x53] as u32 + LOG[0xca] as u32) % 255, 0);
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn split_recover() {
+        let secret: [u8; 16] = core::array::from_fn(|i| i as u8 * 17);
+        let mut rng = crate::tests::StepRng(0);
+        for (threshold, count) in [(1, 1), (1, 3), (2, 3), (3, 5), (16, 16)] {
+            let mut shares = [(0u8, [0u8; 16]); MAX_SHARES];
+            split_secret(threshold, count, &secret, &mut rng, |i, value| {
+                shares[i as usize] = (i, *value);
+            })
+            .unwrap();
+            // any subset of threshold shares works, here the last ones
+            let start = (count - threshold) as usize;
+            let mut points: [(u8, &[u8]); MAX_SHARES] = [(0, &[]); MAX_SHARES];
+            for (p, (x, value)) in points.iter_mut().zip(shares[start..count as usize].iter()) {
+                *p = (*x, &value[..]);
+            }
+            let recovered = recover_secret::<16>(threshold, &points[0..threshold as usize]);
+            assert_eq!(recovered, Some(secret));
+        }
+    }
+}
@@ -8,18 +8,379 @@
 //!
 //! The output size and the number of iteration are both configurable,
 //! and the original BIP39 values are iteration=2048 and output-size=64 bytes.
+//!
+//! Other schemes using PBKDF2 over the mnemonic phrase (e.g. with a different
+//! salt prefix or a different hash function) can be defined with [`SeedScheme`].
+//!
+//! The [`Seed`] type wraps the output, and erases it from memory when dropped.
+//!
+//! The PBKDF2 based derivations don't allocate: the mnemonic phrase is rendered
+//! directly into the HMAC key schedule.
+//!
+//! On slow targets, the derivation can be run incrementally with [`SeedDerivation`],
+//! allowing to report progress and to cancel it.
+//!
+//! For non standard deployments, memory-hard alternatives to PBKDF2 are available
+//! with [`seed_from_mnemonics_scrypt`] and [`seed_from_mnemonics_argon2id`], when
+//! the `alloc` feature is enabled.
+//!
+//! The Substrate (Polkadot) seed, made from the entropy instead of the mnemonic
+//! phrase, is available with [`substrate_seed_from_entropy`].
 
-#[cfg(not(feature = "std"))]
+#[cfg(all(feature = "alloc", not(feature = "std")))]
 use alloc::vec::Vec;
+#[cfg(not(feature = "std"))]
+use core::fmt;
+#[cfg(feature = "std")]
+use {std::error::Error, std::fmt, std::vec::Vec};
+
+use cryptoxide::hashing::sha2::{Context256, Context512};
+#[cfg(feature = "alloc")]
+use {
+    cryptoxide::kdf::argon2,
+    cryptoxide::scrypt::{scrypt, ScryptParams},
+};
+
+use super::ct;
+use super::dictionary::{self, WordNotFound};
+use super::entropy::{Entropy, EntropyError};
+use super::hmac::{self, Hash, Hmac, KeyStream};
+use super::index::{MnemonicIndex, MAX_MNEMONIC_VALUE};
+use super::mnemonics::{MnemonicError, Mnemonics};
+
+/// Seed made from the mnemonics, of N bytes (64 bytes for BIP39)
+///
+/// The seed is overwritten with zeros when dropped. The `Debug` implementation
+/// doesn't display the content, while the `Display` implementation shows
+/// the seed in hexadecimal.
+///
+/// # Example
+///
+/// ```
+/// # use bip39_dict::{ENGLISH, Mnemonics, Seed};
+/// # let mnemonics = Mnemonics::<12>::from_string(&ENGLISH, "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about").unwrap();
+/// let seed = Seed::<64>::from_mnemonics(&ENGLISH, &mnemonics, b"TREZOR");
+/// assert_eq!(seed.to_string(), "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04");
+/// ```
+#[derive(Clone)]
+pub struct Seed<const N: usize>([u8; N]);
+
+impl<const N: usize> Seed<N> {
+    /// get the standard BIP39 seed (2048 iterations) from the given [`Mnemonics`]
+    /// and the given password.
+    pub fn from_mnemonics<D: dictionary::Language, const W: usize>(
+        dict: &D,
+        mnemonics: &Mnemonics<W>,
+        password: &[u8],
+    ) -> Self {
+        Seed(seed_from_mnemonics(dict, mnemonics, password, 2048))
+    }
+
+    /// Create a seed from its bytes
+    pub fn from_bytes(bytes: [u8; N]) -> Self {
+        Seed(bytes)
+    }
+
+    /// The bytes of the seed
+    pub fn as_bytes(&self) -> &[u8; N] {
+        &self.0
+    }
+}
+
+impl<const N: usize> From<[u8; N]> for Seed<N> {
+    fn from(bytes: [u8; N]) -> Self {
+        Seed(bytes)
+    }
+}
+
+impl<const N: usize> AsRef<[u8]> for Seed<N> {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl<const N: usize> PartialEq for Seed<N> {
+    fn eq(&self, other: &Self) -> bool {
+        ct::eq_bits(&self.0, &other.0, N * 8)
+    }
+}
+
+impl<const N: usize> Eq for Seed<N> {}
+
+impl<const N: usize> Drop for Seed<N> {
+    fn drop(&mut self) {
+        ct::zeroize(&mut self.0)
+    }
+}
+
+impl<const N: usize> fmt::Debug for Seed<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Seed<{}>(<redacted>)", N)
+    }
+}
+
+impl<const N: usize> fmt::Display for Seed<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for b in self.0.iter() {
+            write!(f, "{:02x}", b)?;
+        }
+        Ok(())
+    }
+}
+
+/// Possible error when checking a phrase before making the seed
+#[derive(Debug, Clone)]
+pub enum PhraseError {
+    /// The phrase doesn't contains the expected words
+    Words(MnemonicError),
+    /// The phrase doesn't have a valid standard BIP39 checksum
+    Checksum(EntropyError),
+}
+
+impl fmt::Display for PhraseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Words(err) => write!(f, "{}", err),
+            Self::Checksum(err) => write!(f, "{}", err),
+        }
+    }
+}
+
 #[cfg(feature = "std")]
-use std::vec::Vec;
+impl Error for PhraseError {}
 
-use cryptoxide::hmac::Hmac;
-use cryptoxide::pbkdf2::pbkdf2;
-use cryptoxide::sha2::Sha512;
+/// Error when the cost parameters of a key derivation function are not valid
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidCost;
 
-use super::dictionary;
-use super::mnemonics::Mnemonics;
+impl fmt::Display for InvalidCost {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid key derivation cost parameters")
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for InvalidCost {}
+
+#[cfg(feature = "alloc")]
+/// Cost parameters of scrypt
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScryptCost {
+    /// log2 of the CPU/memory cost N
+    pub log_n: u8,
+    /// block size r
+    pub r: u32,
+    /// parallelization p
+    pub p: u32,
+}
+
+#[cfg(feature = "alloc")]
+impl ScryptCost {
+    fn params(&self) -> Result<ScryptParams, InvalidCost> {
+        let ScryptCost { log_n, r, p } = *self;
+        // same checks as ScryptParams::new, which panics on invalid values
+        let valid = r > 0
+            && p > 0
+            && log_n > 0
+            && (log_n as u32) < usize::BITS
+            && (log_n as u64) < (r as u64) * 16
+            && (r as u64) * (p as u64) < 0x4000_0000
+            && (r as usize)
+                .checked_mul(128)
+                .and_then(|r128| {
+                    r128.checked_mul(1 << log_n)
+                        .and(r128.checked_mul(p as usize))
+                })
+                .is_some();
+        if valid {
+            Ok(ScryptParams::new(log_n, r, p))
+        } else {
+            Err(InvalidCost)
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+/// Cost parameters of Argon2id
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Argon2Cost {
+    /// memory size in kilobytes
+    pub memory_kb: u32,
+    /// number of iterations
+    pub iterations: u32,
+    /// degree of parallelism
+    pub parallelism: u32,
+}
+
+#[cfg(feature = "alloc")]
+impl Argon2Cost {
+    fn params(&self) -> Result<argon2::Params, InvalidCost> {
+        argon2::Params::argon2id()
+            .parallelism(self.parallelism)
+            .and_then(|p| p.memory_kb(self.memory_kb))
+            .and_then(|p| p.iterations(self.iterations))
+            .map_err(|_| InvalidCost)
+    }
+}
+
+/// Pseudo random function used with PBKDF2
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Prf {
+    /// HMAC-SHA512, as used by BIP39
+    HmacSha512,
+    /// HMAC-SHA256
+    HmacSha256,
+}
+
+/// Seed derivation scheme, based on PBKDF2 over the mnemonic phrase.
+///
+/// The seed is computed as:
+///
+/// ```text
+/// seed = PBKDF2-PRF(key = phrase, salt = salt-prefix || password || extra-salt, iterations)
+/// ```
+///
+/// The standard BIP39 scheme is available with [`SeedScheme::bip39`],
+/// and other schemes can be defined by changing its parameters.
+///
+/// # Example
+///
+/// ```
+/// # use bip39_dict::{ENGLISH, Mnemonics, Prf, SeedScheme};
+/// # let mnemonics = Mnemonics::<12>::from_string(&ENGLISH, "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about").unwrap();
+/// let scheme = SeedScheme::new(b"my-wallet")
+///     .prf(Prf::HmacSha256)
+///     .iterations(100_000)
+///     .expect("at least one iteration");
+/// let seed: [u8; 32] = scheme.derive(&ENGLISH, &mnemonics, b"password");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SeedScheme<'a> {
+    salt_prefix: &'a [u8],
+    extra_salt: &'a [u8],
+    prf: Prf,
+    iterations: u32,
+}
+
+impl<'a> SeedScheme<'a> {
+    /// Create a new scheme with the given salt prefix, and the
+    /// other parameters set to the BIP39 values.
+    pub const fn new(salt_prefix: &'a [u8]) -> Self {
+        SeedScheme {
+            salt_prefix,
+            extra_salt: &[],
+            prf: Prf::HmacSha512,
+            iterations: 2048,
+        }
+    }
+
+    /// The standard BIP39 scheme: "mnemonic" salt prefix, HMAC-SHA512 and 2048 iterations
+    pub const fn bip39() -> Self {
+        Self::new(b"mnemonic")
+    }
+
+    /// The Electrum scheme: "electrum" salt prefix, HMAC-SHA512 and 2048 iterations
+    pub const fn electrum() -> Self {
+        Self::new(b"electrum")
+    }
+
+    /// Set the salt prefix
+    pub const fn salt_prefix(mut self, salt_prefix: &'a [u8]) -> Self {
+        self.salt_prefix = salt_prefix;
+        self
+    }
+
+    /// Set the extra salt, appended after the password
+    pub const fn extra_salt(mut self, extra_salt: &'a [u8]) -> Self {
+        self.extra_salt = extra_salt;
+        self
+    }
+
+    /// Set the pseudo random function
+    pub const fn prf(mut self, prf: Prf) -> Self {
+        self.prf = prf;
+        self
+    }
+
+    /// Set the number of iterations
+    ///
+    /// PBKDF2 needs at least one iteration, [`InvalidCost`] is returned when `iterations` is 0.
+    pub const fn iterations(mut self, iterations: u32) -> Result<Self, InvalidCost> {
+        if iterations == 0 {
+            return Err(InvalidCost);
+        }
+        self.iterations = iterations;
+        Ok(self)
+    }
+
+    /// Derive the seed from the given [`Mnemonics`] rendered in the given
+    /// [`Language`](./dictionary/trait.Language.html), and the given password.
+    pub fn derive<D: dictionary::Language, const W: usize, const OUTPUT: usize>(
+        &self,
+        dict: &D,
+        mnemonics: &Mnemonics<W>,
+        password: &[u8],
+    ) -> [u8; OUTPUT] {
+        let mut result = [0; OUTPUT];
+        match self.prf {
+            Prf::HmacSha512 => self.run(
+                &phrase_key::<Context512, _, W>(dict, mnemonics),
+                password,
+                &mut result,
+            ),
+            Prf::HmacSha256 => self.run(
+                &phrase_key::<Context256, _, W>(dict, mnemonics),
+                password,
+                &mut result,
+            ),
+        }
+        result
+    }
+
+    /// Derive the seed from the given phrase used as is, and the given password.
+    pub fn derive_phrase<const OUTPUT: usize>(
+        &self,
+        phrase: &str,
+        password: &[u8],
+    ) -> [u8; OUTPUT] {
+        let mut result = [0; OUTPUT];
+        self.derive_phrase_into(phrase, password, &mut result);
+        result
+    }
+
+    /// Derive the seed from the given phrase used as is, and the given password,
+    /// filling the whole output slice.
+    pub fn derive_phrase_into(&self, phrase: &str, password: &[u8], output: &mut [u8]) {
+        match self.prf {
+            Prf::HmacSha512 => self.run(
+                &Hmac::<Context512>::new(phrase.as_bytes()),
+                password,
+                output,
+            ),
+            Prf::HmacSha256 => self.run(
+                &Hmac::<Context256>::new(phrase.as_bytes()),
+                password,
+                output,
+            ),
+        }
+    }
+
+    fn run<H: Hash>(&self, mac: &Hmac<H>, password: &[u8], output: &mut [u8]) {
+        let salt = [self.salt_prefix, password, self.extra_salt];
+        hmac::pbkdf2(mac, &salt, self.iterations, output)
+    }
+}
+
+// render the mnemonics directly in the HMAC key schedule
+fn phrase_key<H: Hash, D: dictionary::Language, const W: usize>(
+    dict: &D,
+    mnemonics: &Mnemonics<W>,
+) -> Hmac<H> {
+    let mut key = KeyStream::new();
+    // writing to the key stream never fails
+    let _ = mnemonics.write_to(dict, &mut key);
+    key.finalize()
+}
 
 /// get the seed from the given [`Mnemonics`] and the given password.
 ///
@@ -49,10 +410,410 @@ pub fn seed_from_mnemonics<D: dictionary::Language, const W: usize, const OUTPUT
     password: &[u8],
     iter: u32,
 ) -> [u8; OUTPUT] {
-    let mut salt = Vec::from("mnemonic".as_bytes());
-    salt.extend_from_slice(password);
-    let mut mac = Hmac::new(Sha512::new(), mnemonics.to_string(dict).as_bytes());
+    SeedScheme {
+        iterations: iter,
+        ..SeedScheme::bip39()
+    }
+    .derive(dict, mnemonics, password)
+}
+
+/// get the seed from the given phrase and the given password.
+///
+/// Contrary to [`seed_from_mnemonics`], the phrase is used as is, and is not
+/// rebuilt from the words: this allow to reproduce seeds made from non canonical
+/// phrases (e.g. with different separators). The phrase is expected to be normalized
+/// (UTF-8 NFKD) by the caller.
+///
+/// # Example
+///
+/// ```
+/// # use bip39_dict::seed_from_phrase;
+/// let seed : [u8; 64] = seed_from_phrase("abandon  abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about", b"", 2048);
+/// ```
+///
+pub fn seed_from_phrase<const OUTPUT: usize>(
+    phrase: &str,
+    password: &[u8],
+    iter: u32,
+) -> [u8; OUTPUT] {
+    SeedScheme {
+        iterations: iter,
+        ..SeedScheme::bip39()
+    }
+    .derive_phrase(phrase, password)
+}
+
+/// Same as [`seed_from_phrase`], but the phrase is first checked to be
+/// a standard BIP39 mnemonic phrase of W words in the given dictionary.
+///
+/// The words can be separated by any whitespaces, and can be typed as the
+/// prefix of a single word of the dictionary (e.g. the first 4 letters of the
+/// English words), but the seed is still made from the phrase as is.
+///
+/// # Error
+///
+/// Any invalid or ambiguous word, unexpected number of words or invalid checksum
+/// is reported, in which case no seed is computed.
+///
+pub fn seed_from_phrase_checked<D: dictionary::Language, const W: usize, const OUTPUT: usize>(
+    dict: &D,
+    phrase: &str,
+    password: &[u8],
+    iter: u32,
+) -> Result<[u8; OUTPUT], PhraseError> {
+    let mut words = [MnemonicIndex(0); W];
+    let mut len = 0;
+    for (index, word) in phrase.split_whitespace().enumerate() {
+        if index < W {
+            words[index] = lookup_prefix(dict, word)
+                .map_err(|err| PhraseError::Words(MnemonicError::WordError { index, err }))?;
+        }
+        len += 1;
+    }
+    if len != W {
+        return Err(PhraseError::Words(MnemonicError::InvalidWords {
+            expected_words: W,
+            got_words: len,
+        }));
+    }
+    let mnemonics = Mnemonics::from(words);
+    words.fill(MnemonicIndex(0));
+    mnemonics
+        .check_standard_checksum()
+        .map_err(PhraseError::Checksum)?;
+    Ok(seed_from_phrase(phrase, password, iter))
+}
+
+// the word, or the only word of the dictionary starting with it
+fn lookup_prefix<D: dictionary::Language>(
+    dict: &D,
+    word: &str,
+) -> Result<MnemonicIndex, WordNotFound> {
+    let err = match dict.lookup_mnemonic(word) {
+        Ok(index) => return Ok(index),
+        Err(err) => err,
+    };
+    let mut found = None;
+    let mut count = 0;
+    for i in 0..=MAX_MNEMONIC_VALUE {
+        let candidate = MnemonicIndex(i);
+        if !word.is_empty() && dict.lookup_word(candidate).starts_with(word) {
+            found = Some(candidate);
+            count += 1;
+        }
+    }
+    match found {
+        Some(index) if count == 1 => Ok(index),
+        _ => Err(err),
+    }
+}
+
+/// get the Substrate (Polkadot) seed from the given [`Entropy`] and the given password.
+///
+/// As done by `substrate-bip39`, the BIP39 inputs are used with the entropy
+/// in place of the mnemonic phrase:
+///
+/// ```text
+/// seed = PBKDF2-HMAC-SHA512(salt = "mnemonic" || password, key = entropy, iterations = 2048)
+/// ```
+///
+/// `substrate-bip39` only accepts the entropy of the standard phrases (16, 20, 24,
+/// 28 or 32 bytes). The first 32 bytes of the seed are the sr25519 / ed25519
+/// mini secret key, see [`substrate_mini_secret`].
+///
+/// As the HMAC key is padded with zeros, entropies only differing by trailing
+/// zero bytes give the same seed (e.g. the all zero entropies of 12 and 24 words).
+///
+/// # Example
+///
+/// ```
+/// # use bip39_dict::{substrate_seed_from_entropy, Entropy};
+/// let seed = substrate_seed_from_entropy(&Entropy([0; 16]), b"Substrate");
+/// assert_eq!(&seed.to_string()[..16], "44e9d125f037ac1d");
+/// ```
+pub fn substrate_seed_from_entropy<const N: usize>(
+    entropy: &Entropy<N>,
+    password: &[u8],
+) -> Seed<64> {
+    let mut seed = [0u8; 64];
+    hmac::pbkdf2(
+        &Hmac::<Context512>::new(&entropy.0),
+        &[b"mnemonic", password],
+        2048,
+        &mut seed,
+    );
+    Seed(seed)
+}
+
+/// get the Substrate (Polkadot) mini secret key from the given [`Entropy`] and
+/// the given password: the first 32 bytes of [`substrate_seed_from_entropy`].
+pub fn substrate_mini_secret<const N: usize>(entropy: &Entropy<N>, password: &[u8]) -> Seed<32> {
+    let seed = substrate_seed_from_entropy(entropy, password);
+    let mut mini_secret = [0u8; 32];
+    mini_secret.copy_from_slice(&seed.0[..32]);
+    Seed(mini_secret)
+}
+
+// heap buffer for the inputs of the memory hard derivations, overwritten with
+// zeros when dropped, and when its content is moved to a larger allocation
+#[cfg(feature = "alloc")]
+struct SecretBuffer(Vec<u8>);
+
+#[cfg(feature = "alloc")]
+impl SecretBuffer {
+    // the rendered phrase
+    fn phrase<D: dictionary::Language, const W: usize>(dict: &D, mnemonics: &Mnemonics<W>) -> Self {
+        let mut buffer = SecretBuffer(Vec::with_capacity(W * 9));
+        // writing to the buffer never fails
+        let _ = mnemonics.write_to(dict, &mut buffer);
+        buffer
+    }
+
+    // "mnemonic" || password
+    fn salt(password: &[u8]) -> Self {
+        let mut buffer = SecretBuffer(Vec::with_capacity(8 + password.len()));
+        buffer.extend(b"mnemonic");
+        buffer.extend(password);
+        buffer
+    }
+
+    fn extend(&mut self, data: &[u8]) {
+        if self.0.len() + data.len() > self.0.capacity() {
+            let mut grown = Vec::with_capacity((self.0.len() + data.len()) * 2);
+            grown.extend_from_slice(&self.0);
+            ct::zeroize(&mut self.0);
+            self.0 = grown;
+        }
+        self.0.extend_from_slice(data);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Write for SecretBuffer {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.extend(s.as_bytes());
+        Ok(())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Drop for SecretBuffer {
+    fn drop(&mut self) {
+        ct::zeroize(&mut self.0);
+    }
+}
+
+#[cfg(feature = "alloc")]
+/// get the seed from the given [`Mnemonics`] and the given password, using scrypt
+/// instead of PBKDF2.
+///
+/// The inputs are laid out as in BIP39:
+///
+/// ```text
+/// seed = scrypt(password = "abandon abandon ... about", salt = "mnemonic" || password, N, r, p)
+/// ```
+///
+/// This is not compatible with BIP39, and should only be used when all the
+/// parties agree on this scheme and its cost parameters.
+///
+/// # Error
+///
+/// Fails if the cost parameters are not valid for scrypt
+///
+pub fn seed_from_mnemonics_scrypt<D: dictionary::Language, const W: usize, const OUTPUT: usize>(
+    dict: &D,
+    mnemonics: &Mnemonics<W>,
+    password: &[u8],
+    cost: &ScryptCost,
+) -> Result<[u8; OUTPUT], InvalidCost> {
+    let params = cost.params()?;
+    let phrase = SecretBuffer::phrase(dict, mnemonics);
+    let salt = SecretBuffer::salt(password);
     let mut result = [0; OUTPUT];
-    pbkdf2(&mut mac, &salt, iter, &mut result);
-    result
+    scrypt(&phrase.0, &salt.0, &params, &mut result);
+    Ok(result)
+}
+
+#[cfg(feature = "alloc")]
+/// get the seed from the given [`Mnemonics`] and the given password, using Argon2id
+/// instead of PBKDF2.
+///
+/// The inputs are laid out as in BIP39, without any secret key or associated data:
+///
+/// ```text
+/// seed = Argon2id(password = "abandon abandon ... about", salt = "mnemonic" || password, m, t, p)
+/// ```
+///
+/// This is not compatible with BIP39, and should only be used when all the
+/// parties agree on this scheme and its cost parameters.
+///
+/// # Error
+///
+/// Fails if the cost parameters are not valid for Argon2
+///
+pub fn seed_from_mnemonics_argon2id<
+    D: dictionary::Language,
+    const W: usize,
+    const OUTPUT: usize,
+>(
+    dict: &D,
+    mnemonics: &Mnemonics<W>,
+    password: &[u8],
+    cost: &Argon2Cost,
+) -> Result<[u8; OUTPUT], InvalidCost> {
+    let params = cost.params()?;
+    let phrase = SecretBuffer::phrase(dict, mnemonics);
+    let salt = SecretBuffer::salt(password);
+    Ok(argon2::argon2(&params, &phrase.0, &salt.0, &[], &[]))
+}
+
+/// Incremental BIP39 seed derivation
+///
+/// This compute the same seed as [`seed_from_mnemonics`], but the PBKDF2 iterations
+/// are run in chunks chosen by the caller, which allows to report the progress and
+/// to cancel the derivation between chunks, simply by not continuing it.
+///
+/// # Example
+///
+/// ```
+/// # use bip39_dict::{ENGLISH, Mnemonics, SeedDerivation, seed_from_mnemonics};
+/// # let mnemonics = Mnemonics::<12>::from_string(&ENGLISH, "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about").unwrap();
+/// let mut derivation = SeedDerivation::<64>::new(&ENGLISH, &mnemonics, b"password", 2048).unwrap();
+/// while !derivation.step(256) {
+///     println!("{:.0}%", derivation.progress() * 100.0);
+/// }
+/// let seed = derivation.finalize().unwrap();
+/// assert_eq!(seed, seed_from_mnemonics(&ENGLISH, &mnemonics, b"password", 2048));
+/// ```
+pub struct SeedDerivation<'a, const OUTPUT: usize> {
+    mac: Hmac<Context512>,
+    password: &'a [u8],
+    iterations: u32,
+    // current PBKDF2 block, starting at 0
+    block: usize,
+    // number of iterations done in the current block
+    iteration: u32,
+    u: [u8; 64],
+    t: [u8; 64],
+    output: [u8; OUTPUT],
+}
+
+impl<'a, const OUTPUT: usize> SeedDerivation<'a, OUTPUT> {
+    const BLOCKS: usize = OUTPUT.div_ceil(64);
+
+    /// Prepare the derivation of the seed from the given [`Mnemonics`] and the given password.
+    ///
+    /// No PBKDF2 iteration is done until [`SeedDerivation::step`] is called.
+    /// PBKDF2 needs at least one iteration, [`InvalidCost`] is returned when `iter` is 0.
+    pub fn new<D: dictionary::Language, const W: usize>(
+        dict: &D,
+        mnemonics: &Mnemonics<W>,
+        password: &'a [u8],
+        iter: u32,
+    ) -> Result<Self, InvalidCost> {
+        if iter == 0 {
+            return Err(InvalidCost);
+        }
+        Ok(SeedDerivation {
+            mac: phrase_key(dict, mnemonics),
+            password,
+            iterations: iter,
+            block: 0,
+            iteration: 0,
+            u: [0; 64],
+            t: [0; 64],
+            output: [0; OUTPUT],
+        })
+    }
+
+    /// Total number of PBKDF2 iterations needed for this derivation
+    pub fn total_iterations(&self) -> u64 {
+        Self::BLOCKS as u64 * self.iterations as u64
+    }
+
+    /// Number of PBKDF2 iterations already done
+    pub fn done_iterations(&self) -> u64 {
+        self.block as u64 * self.iterations as u64 + self.iteration as u64
+    }
+
+    /// Fraction of the derivation already done, between 0 and 1
+    pub fn progress(&self) -> f32 {
+        match self.total_iterations() {
+            0 => 1.0,
+            total => self.done_iterations() as f32 / total as f32,
+        }
+    }
+
+    /// Whether the derivation is finished
+    pub fn is_done(&self) -> bool {
+        self.block >= Self::BLOCKS
+    }
+
+    /// Run at most `iterations` PBKDF2 iterations, and return whether the derivation is finished
+    pub fn step(&mut self, iterations: u32) -> bool {
+        let mut remaining = iterations;
+        while remaining > 0 && !self.is_done() {
+            if self.iteration == 0 {
+                // U1 = PRF(password, salt || INT(i))
+                let index = (self.block as u32 + 1).to_be_bytes();
+                let salt: [&[u8]; 3] = [b"mnemonic", self.password, &index];
+                self.mac.mac(salt, &mut self.u);
+                self.t = self.u;
+            } else {
+                // Uj = PRF(password, Uj-1)
+                let previous = self.u;
+                self.mac.mac([&previous[..]], &mut self.u);
+                for (t, u) in self.t.iter_mut().zip(self.u.iter()) {
+                    *t ^= u;
+                }
+            }
+            self.iteration += 1;
+            remaining -= 1;
+
+            if self.iteration >= self.iterations {
+                let start = self.block * 64;
+                let end = OUTPUT.min(start + 64);
+                self.output[start..end].copy_from_slice(&self.t[0..end - start]);
+                self.block += 1;
+                self.iteration = 0;
+            }
+        }
+        self.is_done()
+    }
+
+    /// Run the derivation to the end, calling `progress` after every chunk of
+    /// `chunk` iterations (at least one) with the fraction done.
+    ///
+    /// The derivation is cancelled, and None returned, as soon as `progress` returns false
+    pub fn run<F: FnMut(f32) -> bool>(
+        mut self,
+        chunk: u32,
+        mut progress: F,
+    ) -> Option<[u8; OUTPUT]> {
+        let chunk = chunk.max(1);
+        while !self.step(chunk) {
+            if !progress(self.progress()) {
+                return None;
+            }
+        }
+        self.finalize()
+    }
+
+    /// Get the seed, if the derivation is finished
+    pub fn finalize(self) -> Option<[u8; OUTPUT]> {
+        if self.is_done() {
+            Some(self.output)
+        } else {
+            None
+        }
+    }
+}
+
+impl<const OUTPUT: usize> Drop for SeedDerivation<'_, OUTPUT> {
+    fn drop(&mut self) {
+        // the keyed HMAC state is wiped when `mac` is dropped
+        ct::zeroize(&mut self.u);
+        ct::zeroize(&mut self.t);
+        ct::zeroize(&mut self.output);
+    }
 }
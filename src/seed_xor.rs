@@ -0,0 +1,265 @@
+//! Seed XOR: split an entropy in parts which XOR to it
+//!
+//! As done by Coldcard, each part is a complete BIP39 phrase of the same size
+//! as the original, with a valid checksum: each part is a working wallet on its
+//! own and can serve as a decoy, while all of them together are needed to recover
+//! the original entropy.
+//!
+//! Only the 12, 18 and 24 words phrases (16, 24 and 32 bytes of entropy) are supported.
+//!
+//! # Example
+//!
+//! ```
+//! use bip39_dict::{seed_xor, Mnemonics, ENGLISH};
+//!
+//! let parts = [
+//!     "romance wink lottery autumn shop bring dawn tongue range crater truth ability miss spice fitness easy legal release recall obey exchange recycle dragon room",
+//!     "lion misery divide hurry latin fluid camp advance illegal lab pyramid unaware eager fringe sick camera series noodle toy crowd jeans select depth lounge",
+//!     "vault nominee cradle silk own frown throw leg cactus recall talent worry gadget surface shy planet purpose coffee drip few seven term squeeze educate",
+//! ];
+//! let parts = parts.map(|part| Mnemonics::<24>::from_string(&ENGLISH, part).unwrap());
+//! let mnemonics = seed_xor::combine_mnemonics(&parts).unwrap();
+//! assert_eq!(
+//!     mnemonics.display(&ENGLISH).to_string(),
+//!     "silent toe meat possible chair blossom wait occur this worth option bag nurse find fish scene bench asthma bike wage world quit primary indoor"
+//! );
+//! ```
+use super::ct;
+use super::entropy::{Entropy, EntropyError};
+use super::mnemonics::Mnemonics;
+#[cfg(feature = "rand")]
+use rand_core::{CryptoRng, RngCore};
+
+#[cfg(all(feature = "alloc", feature = "rand", not(feature = "std")))]
+use alloc::vec::Vec;
+#[cfg(not(feature = "std"))]
+use core::fmt;
+#[cfg(feature = "std")]
+use {std::error::Error, std::fmt};
+
+/// Possible errors when splitting or combining with Seed XOR
+#[derive(Debug)]
+pub enum SeedXorError {
+    /// The entropy size is not 16, 24 or 32 bytes
+    InvalidLength(usize),
+    /// The number of words is not 12, 18 or 24
+    InvalidWordCount(usize),
+    /// Splitting needs at least 2 parts, and combining at least 1
+    InvalidParts(usize),
+    /// The mnemonics of a part are not valid
+    Entropy(EntropyError),
+    /// The random generator failed
+    #[cfg(feature = "rand")]
+    Rng(rand_core::Error),
+}
+
+impl fmt::Display for SeedXorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidLength(length) => write!(f, "Invalid entropy length {}", length),
+            Self::InvalidWordCount(words) => write!(f, "Invalid word count {}", words),
+            Self::InvalidParts(parts) => write!(f, "Invalid number of parts {}", parts),
+            Self::Entropy(err) => write!(f, "{}", err),
+            #[cfg(feature = "rand")]
+            Self::Rng(err) => write!(f, "Random generator error: {}", err),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for SeedXorError {}
+
+impl From<EntropyError> for SeedXorError {
+    fn from(e: EntropyError) -> Self {
+        SeedXorError::Entropy(e)
+    }
+}
+
+#[cfg(feature = "rand")]
+impl From<rand_core::Error> for SeedXorError {
+    fn from(e: rand_core::Error) -> Self {
+        SeedXorError::Rng(e)
+    }
+}
+
+fn check_length<const N: usize>() -> Result<(), SeedXorError> {
+    match N {
+        16 | 24 | 32 => Ok(()),
+        _ => Err(SeedXorError::InvalidLength(N)),
+    }
+}
+
+fn xor<const N: usize>(acc: &mut [u8; N], part: &[u8; N]) {
+    for (a, p) in acc.iter_mut().zip(part.iter()) {
+        *a ^= p
+    }
+}
+
+/// Split the entropy in `parts` random entropies (at least 2), giving each to `out`
+/// (with the `rand` feature)
+///
+/// All the parts but the last are generated with the cryptographically secure
+/// random generator, the last one is the XOR of the entropy with the others.
+///
+/// # Error
+///
+/// Any error from the random generator is returned, the parts already given
+/// to `out` are then incomplete and must be discarded.
+#[cfg(feature = "rand")]
+pub fn split_with<const N: usize, R, F>(
+    entropy: &Entropy<N>,
+    parts: usize,
+    rng: &mut R,
+    mut out: F,
+) -> Result<(), SeedXorError>
+where
+    R: RngCore + CryptoRng,
+    F: FnMut(Entropy<N>),
+{
+    check_length::<N>()?;
+    if parts < 2 {
+        return Err(SeedXorError::InvalidParts(parts));
+    }
+    let mut last = entropy.0;
+    for _ in 1..parts {
+        let part = match Entropy::<N>::generate_from_rng(rng) {
+            Ok(part) => part,
+            Err(e) => {
+                ct::zeroize(&mut last);
+                return Err(e.into());
+            }
+        };
+        xor(&mut last, &part.0);
+        out(part);
+    }
+    out(Entropy(last));
+    ct::zeroize(&mut last);
+    Ok(())
+}
+
+/// Split the entropy in `parts` random entropies (at least 2) (with the `rand` feature)
+#[cfg(all(feature = "alloc", feature = "rand"))]
+pub fn split<const N: usize, R: RngCore + CryptoRng>(
+    entropy: &Entropy<N>,
+    parts: usize,
+    rng: &mut R,
+) -> Result<Vec<Entropy<N>>, SeedXorError> {
+    let mut out = Vec::with_capacity(parts);
+    split_with(entropy, parts, rng, |part| out.push(part))?;
+    Ok(out)
+}
+
+/// Recover the entropy by XORing all its parts
+pub fn combine<const N: usize>(parts: &[Entropy<N>]) -> Result<Entropy<N>, SeedXorError> {
+    check_length::<N>()?;
+    if parts.is_empty() {
+        return Err(SeedXorError::InvalidParts(0));
+    }
+    let mut acc = [0u8; N];
+    for part in parts {
+        xor(&mut acc, &part.0);
+    }
+    let entropy = Entropy(acc);
+    ct::zeroize(&mut acc);
+    Ok(entropy)
+}
+
+/// Split standard BIP39 mnemonics of W words (12, 18 or 24) in `parts` mnemonics
+/// of W words (at least 2), each with a valid checksum, giving each to `out`
+/// (with the `rand` feature)
+///
+/// # Error
+///
+/// Any error from the random generator is returned, see [`split_with`].
+#[cfg(feature = "rand")]
+pub fn split_mnemonics_with<const W: usize, R, F>(
+    mnemonics: &Mnemonics<W>,
+    parts: usize,
+    rng: &mut R,
+    out: F,
+) -> Result<(), SeedXorError>
+where
+    R: RngCore + CryptoRng,
+    F: FnMut(Mnemonics<W>),
+{
+    match W {
+        12 => split_standard::<16, W, 4, R, F>(mnemonics, parts, rng, out),
+        18 => split_standard::<24, W, 6, R, F>(mnemonics, parts, rng, out),
+        24 => split_standard::<32, W, 8, R, F>(mnemonics, parts, rng, out),
+        _ => Err(SeedXorError::InvalidWordCount(W)),
+    }
+}
+
+#[cfg(feature = "rand")]
+fn split_standard<const N: usize, const W: usize, const CS: usize, R, F>(
+    mnemonics: &Mnemonics<W>,
+    parts: usize,
+    rng: &mut R,
+    mut out: F,
+) -> Result<(), SeedXorError>
+where
+    R: RngCore + CryptoRng,
+    F: FnMut(Mnemonics<W>),
+{
+    let mut entropy = Entropy::<N>::from_mnemonics::<W, CS>(mnemonics)?;
+    let mut result = Ok(());
+    let split = split_with(&entropy, parts, rng, |mut part| {
+        match part.to_mnemonics::<W, CS>() {
+            Ok(mnemonics) => out(mnemonics),
+            Err(e) => result = Err(e.into()),
+        }
+        ct::zeroize(&mut part.0);
+    });
+    ct::zeroize(&mut entropy.0);
+    split?;
+    result
+}
+
+/// Split standard BIP39 mnemonics of W words (12, 18 or 24) in `parts` mnemonics
+/// of W words (at least 2), each with a valid checksum (with the `rand` feature)
+#[cfg(all(feature = "alloc", feature = "rand"))]
+pub fn split_mnemonics<const W: usize, R: RngCore + CryptoRng>(
+    mnemonics: &Mnemonics<W>,
+    parts: usize,
+    rng: &mut R,
+) -> Result<Vec<Mnemonics<W>>, SeedXorError> {
+    let mut out = Vec::with_capacity(parts);
+    split_mnemonics_with(mnemonics, parts, rng, |part| out.push(part))?;
+    Ok(out)
+}
+
+/// Recover the standard BIP39 mnemonics of W words (12, 18 or 24) from all its parts,
+/// checking the checksum of every part
+pub fn combine_mnemonics<const W: usize>(
+    parts: &[Mnemonics<W>],
+) -> Result<Mnemonics<W>, SeedXorError> {
+    match W {
+        12 => combine_standard::<16, W, 4>(parts),
+        18 => combine_standard::<24, W, 6>(parts),
+        24 => combine_standard::<32, W, 8>(parts),
+        _ => Err(SeedXorError::InvalidWordCount(W)),
+    }
+}
+
+fn combine_standard<const N: usize, const W: usize, const CS: usize>(
+    parts: &[Mnemonics<W>],
+) -> Result<Mnemonics<W>, SeedXorError> {
+    if parts.is_empty() {
+        return Err(SeedXorError::InvalidParts(0));
+    }
+    let mut acc = Entropy([0u8; N]);
+    for part in parts {
+        let mut part = match Entropy::<N>::from_mnemonics::<W, CS>(part) {
+            Ok(part) => part,
+            Err(e) => {
+                ct::zeroize(&mut acc.0);
+                return Err(e.into());
+            }
+        };
+        xor(&mut acc.0, &part.0);
+        ct::zeroize(&mut part.0);
+    }
+    let mnemonics = acc.to_mnemonics::<W, CS>();
+    ct::zeroize(&mut acc.0);
+    Ok(mnemonics?)
+}
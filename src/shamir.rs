@@ -0,0 +1,402 @@
+//! Shamir's secret sharing of entropy, with the shares as mnemonic phrases
+//!
+//! Split an [`Entropy`] in up to 16 shares, any `threshold` of which recover it.
+//! Each share is written as mnemonics in any [`Language`], with the same flexible
+//! words / checksum scheme as [`Entropy::to_mnemonics`], preceded by a header
+//! word holding the share index and the threshold.
+//!
+//! The secret is shared with a digest (as in SLIP-39), so that combining the
+//! wrong shares is detected instead of silently giving another entropy.
+//!
+//! # Example
+//!
+//! ```
+//! # #[cfg(feature = "rand")]
+//! # {
+//! use bip39_dict::{Entropy, ENGLISH};
+//! use bip39_dict::shamir::{self, Share, ShareMnemonics};
+//!
+//! # struct Rng;
+//! # impl rand_core::RngCore for Rng {
+//! #     fn next_u32(&mut self) -> u32 { 4 }
+//! #     fn next_u64(&mut self) -> u64 { 4 }
+//! #     fn fill_bytes(&mut self, dest: &mut [u8]) { dest.fill(4) }
+//! #     fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> { Ok(dest.fill(4)) }
+//! # }
+//! # impl rand_core::CryptoRng for Rng {}
+//! # let mut rng = Rng;
+//! let entropy = Entropy::<16>([0x42; 16]);
+//! // 2 of 3 shares, each of 13 words: the header, and 12 words with 4 bits of checksum
+//! let mut phrases = [String::new(), String::new(), String::new()];
+//! shamir::split_with(&entropy, 2, 3, &mut rng, |share| {
+//!     let mnemonics = share.to_mnemonics::<12, 4>().unwrap();
+//!     phrases[share.index() as usize] = mnemonics.display(&ENGLISH).to_string();
+//! })
+//! .unwrap();
+//!
+//! let mut shares = Vec::new();
+//! for phrase in &phrases[1..3] {
+//!     let mnemonics = ShareMnemonics::<12>::from_string(&ENGLISH, phrase).unwrap();
+//!     shares.push(Share::<16>::from_mnemonics::<12, 4>(&mnemonics).unwrap());
+//! }
+//! let (recovered, mnemonics) = shamir::combine::<16, 12>(&shares).unwrap();
+//! assert_eq!(recovered, entropy);
+//! assert_eq!(mnemonics, entropy.to_mnemonics::<12, 4>().unwrap());
+//! # }
+//! ```
+use super::ct;
+use super::dictionary::Language;
+use super::entropy::{Entropy, EntropyError};
+use super::index::MnemonicIndex;
+use super::mnemonics::{MnemonicError, Mnemonics};
+use super::secret_sharing::{self, MAX_SHARES};
+use cryptoxide::hashing::sha2::Sha256;
+#[cfg(feature = "rand")]
+use rand_core::{CryptoRng, RngCore};
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::string::String;
+#[cfg(all(feature = "alloc", feature = "rand", not(feature = "std")))]
+use alloc::vec::Vec;
+#[cfg(not(feature = "std"))]
+use core::fmt;
+#[cfg(feature = "std")]
+use {std::error::Error, std::fmt, std::string::String};
+
+/// Possible errors when splitting or combining shares
+#[derive(Debug)]
+pub enum ShareError {
+    /// The entropy size is not one of the standard BIP39 sizes (16, 20, 24, 28 or 32 bytes)
+    InvalidLength(usize),
+    /// The threshold is zero, or more than the number of shares (at most 16)
+    InvalidThreshold,
+    /// The share mnemonics are not valid
+    Entropy(EntropyError),
+    /// The header word doesn't match the share
+    InvalidHeader,
+    /// Fewer shares than the threshold were given
+    NotEnoughShares,
+    /// The shares don't have the same threshold
+    MismatchedThreshold,
+    /// Two different shares have the same index
+    DuplicateShare(u8),
+    /// The digest of the recovered secret doesn't match: the shares are not
+    /// from the same split
+    InvalidDigest,
+    /// The share of the index, beyond the threshold, doesn't match the others
+    InconsistentShare(u8),
+    /// The random generator failed
+    #[cfg(feature = "rand")]
+    Rng(rand_core::Error),
+}
+
+impl fmt::Display for ShareError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidLength(length) => write!(f, "Invalid entropy length {}", length),
+            Self::InvalidThreshold => write!(f, "Invalid threshold"),
+            Self::Entropy(err) => write!(f, "{}", err),
+            Self::InvalidHeader => write!(f, "Invalid share header"),
+            Self::NotEnoughShares => write!(f, "Not enough shares"),
+            Self::MismatchedThreshold => write!(f, "Shares have different thresholds"),
+            Self::DuplicateShare(index) => write!(f, "Duplicate share {}", index),
+            Self::InvalidDigest => write!(f, "Invalid digest of the shared secret"),
+            Self::InconsistentShare(index) => write!(f, "Inconsistent share {}", index),
+            #[cfg(feature = "rand")]
+            Self::Rng(err) => write!(f, "Random generator error: {}", err),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for ShareError {}
+
+impl From<EntropyError> for ShareError {
+    fn from(e: EntropyError) -> Self {
+        ShareError::Entropy(e)
+    }
+}
+
+#[cfg(feature = "rand")]
+impl From<rand_core::Error> for ShareError {
+    fn from(e: rand_core::Error) -> Self {
+        ShareError::Rng(e)
+    }
+}
+
+fn check_length<const N: usize>() -> Result<(), ShareError> {
+    match N {
+        16 | 20 | 24 | 28 | 32 => Ok(()),
+        _ => Err(ShareError::InvalidLength(N)),
+    }
+}
+
+/// A share of an entropy of N bytes
+///
+/// The value of the share is overwritten with zeros when dropped, and is
+/// not displayed by the `Debug` implementation.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Share<const N: usize> {
+    index: u8,
+    threshold: u8,
+    value: [u8; N],
+}
+
+impl<const N: usize> Share<N> {
+    /// Index of the share, from 0 to 15
+    pub fn index(&self) -> u8 {
+        self.index
+    }
+
+    /// Number of shares needed to recover the entropy
+    pub fn threshold(&self) -> u8 {
+        self.threshold
+    }
+
+    // header word: 4 bits of index, 4 bits of threshold - 1, and 3 bits binding
+    // the header to the value
+    fn header(&self) -> MnemonicIndex {
+        let params = self.index << 4 | (self.threshold - 1);
+        let check = Sha256::new()
+            .update(&[params])
+            .update(&self.value)
+            .finalize()[0]
+            >> 5;
+        MnemonicIndex((params as u16) << 3 | check as u16)
+    }
+
+    /// Encode the share in W words (after the header word) with CS bits of checksum,
+    /// which must satisfy `N * 8 + CS = W * 11`, as for [`Entropy::to_mnemonics`]
+    pub fn to_mnemonics<const W: usize, const CS: usize>(
+        &self,
+    ) -> Result<ShareMnemonics<W>, EntropyError> {
+        let value = Entropy(self.value);
+        Ok(ShareMnemonics {
+            header: self.header(),
+            mnemonics: value.to_mnemonics::<W, CS>()?,
+        })
+    }
+
+    /// Decode the share from its mnemonics, checking the checksum and the header
+    pub fn from_mnemonics<const W: usize, const CS: usize>(
+        mnemonics: &ShareMnemonics<W>,
+    ) -> Result<Self, ShareError> {
+        let Entropy(value) = Entropy::<N>::from_mnemonics::<W, CS>(&mnemonics.mnemonics)?;
+        let params = (mnemonics.header.0 >> 3) as u8;
+        let share = Share {
+            index: params >> 4,
+            threshold: (params & 0xf) + 1,
+            value,
+        };
+        if share.header() != mnemonics.header {
+            return Err(ShareError::InvalidHeader);
+        }
+        Ok(share)
+    }
+}
+
+impl<const N: usize> Drop for Share<N> {
+    fn drop(&mut self) {
+        ct::zeroize(&mut self.value)
+    }
+}
+
+impl<const N: usize> fmt::Debug for Share<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Share")
+            .field("index", &self.index)
+            .field("threshold", &self.threshold)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Mnemonics of a share: the header word followed by W words
+///
+/// As for [`Mnemonics`], the `Debug` implementation doesn't display the words.
+#[derive(Clone, PartialEq, Eq)]
+pub struct ShareMnemonics<const W: usize> {
+    header: MnemonicIndex,
+    mnemonics: Mnemonics<W>,
+}
+
+impl<const W: usize> fmt::Debug for ShareMnemonics<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ShareMnemonics<{}>(<redacted>)", W)
+    }
+}
+
+impl<const W: usize> ShareMnemonics<W> {
+    /// Parse the W + 1 words of the share in the given [`Language`]
+    pub fn from_string<D: Language>(dic: &D, phrase: &str) -> Result<Self, MnemonicError> {
+        let mut words = phrase.split(dic.separator());
+        let count = phrase.split(dic.separator()).count();
+        if count != W + 1 {
+            return Err(MnemonicError::InvalidWords {
+                expected_words: W + 1,
+                got_words: count,
+            });
+        }
+        let header = words.next().unwrap_or_default();
+        let header = MnemonicIndex::from_word(dic, header)
+            .map_err(|err| MnemonicError::WordError { index: 0, err })?;
+        let mnemonics = Mnemonics::from_words(dic, words).map_err(|e| match e {
+            MnemonicError::WordError { index, err } => MnemonicError::WordError {
+                index: index + 1,
+                err,
+            },
+            MnemonicError::RedactedWordError { index, length } => {
+                MnemonicError::RedactedWordError {
+                    index: index + 1,
+                    length,
+                }
+            }
+            e => e,
+        })?;
+        Ok(ShareMnemonics { header, mnemonics })
+    }
+
+    /// Write the words of the share in the given [`Language`] to the writer
+    pub fn write_to<D: Language, F: fmt::Write>(&self, dict: &D, writer: &mut F) -> fmt::Result {
+        writer.write_str(self.header.to_word(dict))?;
+        writer.write_str(dict.separator())?;
+        self.mnemonics.write_to(dict, writer)
+    }
+
+    /// Get an object displaying the words of the share in the given [`Language`]
+    pub fn display<'a, D: Language>(&'a self, dict: &'a D) -> ShareMnemonicsDisplay<'a, D, W> {
+        ShareMnemonicsDisplay {
+            mnemonics: self,
+            dict,
+        }
+    }
+
+    /// Get the words of the share in the given [`Language`]
+    ///
+    /// Only available with the `alloc` feature, see [`ShareMnemonics::write_to`]
+    /// and [`ShareMnemonics::display`] otherwise.
+    #[cfg(feature = "alloc")]
+    pub fn to_string<D: Language>(&self, dict: &D) -> String {
+        let mut out = String::new();
+        // writing to a String never fails
+        let _ = self.write_to(dict, &mut out);
+        out
+    }
+}
+
+/// Render [`ShareMnemonics`] in a given [`Language`] through its `Display` implementation
+///
+/// Created by [`ShareMnemonics::display`].
+pub struct ShareMnemonicsDisplay<'a, D, const W: usize> {
+    mnemonics: &'a ShareMnemonics<W>,
+    dict: &'a D,
+}
+
+impl<'a, D: Language, const W: usize> fmt::Display for ShareMnemonicsDisplay<'a, D, W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.mnemonics.write_to(self.dict, f)
+    }
+}
+
+/// Split the entropy in `count` shares (at most 16), any `threshold` of which
+/// recover it, giving each share to `out` (with the `rand` feature)
+///
+/// The cryptographically secure random generator is used for the random coefficients
+/// of the sharing polynomial.
+///
+/// # Error
+///
+/// Any error from the random generator is returned, before any share is given to `out`.
+#[cfg(feature = "rand")]
+pub fn split_with<const N: usize, R, F>(
+    entropy: &Entropy<N>,
+    threshold: u8,
+    count: u8,
+    rng: &mut R,
+    mut out: F,
+) -> Result<(), ShareError>
+where
+    R: RngCore + CryptoRng,
+    F: FnMut(Share<N>),
+{
+    check_length::<N>()?;
+    if threshold == 0 || threshold > count || count as usize > MAX_SHARES {
+        return Err(ShareError::InvalidThreshold);
+    }
+    secret_sharing::split_secret(threshold, count, &entropy.0, rng, |index, value| {
+        out(Share {
+            index,
+            threshold,
+            value: *value,
+        })
+    })?;
+    Ok(())
+}
+
+/// Split the entropy in `count` shares (at most 16), any `threshold` of which recover it
+/// (with the `rand` feature)
+#[cfg(all(feature = "alloc", feature = "rand"))]
+pub fn split<const N: usize, R: RngCore + CryptoRng>(
+    entropy: &Entropy<N>,
+    threshold: u8,
+    count: u8,
+    rng: &mut R,
+) -> Result<Vec<Share<N>>, ShareError> {
+    let mut shares = Vec::with_capacity(count as usize);
+    split_with(entropy, threshold, count, rng, |share| shares.push(share))?;
+    Ok(shares)
+}
+
+/// Recover the entropy from at least `threshold` shares, along with its standard
+/// BIP39 mnemonics of W words
+///
+/// The shares beyond the threshold are checked to be consistent with the others.
+pub fn combine<const N: usize, const W: usize>(
+    shares: &[Share<N>],
+) -> Result<(Entropy<N>, Mnemonics<W>), ShareError> {
+    check_length::<N>()?;
+    let threshold = shares.first().ok_or(ShareError::NotEnoughShares)?.threshold;
+
+    let mut points: [(u8, &[u8]); MAX_SHARES] = [(0, &[]); MAX_SHARES];
+    let mut count = 0;
+    for share in shares {
+        if share.threshold != threshold {
+            return Err(ShareError::MismatchedThreshold);
+        }
+        match points[0..count].iter().find(|(x, _)| *x == share.index) {
+            Some((_, value)) if *value == share.value => {}
+            Some(_) => return Err(ShareError::DuplicateShare(share.index)),
+            None => {
+                points[count] = (share.index, &share.value[..]);
+                count += 1;
+            }
+        }
+    }
+    if count < threshold as usize {
+        return Err(ShareError::NotEnoughShares);
+    }
+
+    let (used, extra) = points[0..count].split_at(threshold as usize);
+    let mut secret =
+        secret_sharing::recover_secret::<N>(threshold, used).ok_or(ShareError::InvalidDigest)?;
+    let mut expected = [0u8; N];
+    for (index, value) in extra {
+        secret_sharing::interpolate(used, *index, &mut expected);
+        if !ct::eq_bits(&expected, value, N * 8) {
+            ct::zeroize(&mut expected);
+            ct::zeroize(&mut secret);
+            return Err(ShareError::InconsistentShare(*index));
+        }
+    }
+    ct::zeroize(&mut expected);
+
+    let entropy = Entropy(secret);
+    ct::zeroize(&mut secret);
+    let mnemonics = match N {
+        16 => entropy.to_mnemonics::<W, 4>(),
+        20 => entropy.to_mnemonics::<W, 5>(),
+        24 => entropy.to_mnemonics::<W, 6>(),
+        28 => entropy.to_mnemonics::<W, 7>(),
+        _ => entropy.to_mnemonics::<W, 8>(),
+    }?;
+    Ok((entropy, mnemonics))
+}
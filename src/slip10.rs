@@ -0,0 +1,299 @@
+//! SLIP-10 hierarchical deterministic keys
+//!
+//! Derive the master private key from a seed (e.g. made with
+//! [`seed_from_mnemonics`](crate::seed_from_mnemonics)), and the child keys along
+//! derivation paths, as specified by
+//! [SLIP-10](https://github.com/satoshilabs/slips/blob/master/slip-0010.md)
+//! for the ed25519, NIST P-256 and secp256k1 curves.
+//!
+//! For secp256k1, the keys are the same as the BIP32 ones. For ed25519,
+//! only hardened children can be derived.
+use super::bip32::{hash160, ChildNumber, DerivationPath};
+use super::ct;
+use super::hmac::Hmac;
+use cryptoxide::hashing::sha2::Context512;
+
+#[cfg(not(feature = "std"))]
+use core::fmt;
+#[cfg(feature = "std")]
+use {std::error::Error, std::fmt};
+
+/// Possible errors when deriving SLIP-10 keys
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Slip10Error {
+    /// Only hardened children can be derived on this curve (ed25519)
+    HardenedOnly(ChildNumber),
+    /// The maximum depth of 255 is reached
+    MaxDepth,
+}
+
+impl fmt::Display for Slip10Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::HardenedOnly(child) => {
+                write!(f, "Only hardened children are supported, got {}", child)
+            }
+            Self::MaxDepth => write!(f, "Maximum derivation depth reached"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for Slip10Error {}
+
+/// Curves supported by SLIP-10
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Curve {
+    /// ed25519, with hardened derivation only
+    Ed25519,
+    /// NIST P-256, also known as secp256r1
+    Nist256p1,
+    /// secp256k1, same derivation as BIP32
+    Secp256k1,
+}
+
+impl Curve {
+    /// The HMAC key used to derive the master key
+    pub const fn hmac_key(self) -> &'static [u8] {
+        match self {
+            Curve::Ed25519 => b"ed25519 seed",
+            Curve::Nist256p1 => b"Nist256p1 seed",
+            Curve::Secp256k1 => b"Bitcoin seed",
+        }
+    }
+
+    // whether the bytes are a valid private key (non zero and less than the order)
+    fn is_valid(self, key: &[u8; 32]) -> bool {
+        match self {
+            Curve::Ed25519 => true,
+            Curve::Nist256p1 => nist256p1::scalar(key).is_some(),
+            Curve::Secp256k1 => secp256k1::scalar(key).is_some(),
+        }
+    }
+
+    // private key of the child from the parent one and the tweak (IL)
+    fn tweak(self, key: &[u8; 32], tweak: &[u8; 32]) -> Option<[u8; 32]> {
+        match self {
+            Curve::Ed25519 => Some(*tweak),
+            Curve::Nist256p1 => nist256p1::add(key, tweak),
+            Curve::Secp256k1 => secp256k1::add(key, tweak),
+        }
+    }
+
+    fn public_key(self, key: &[u8; 32]) -> [u8; 33] {
+        match self {
+            Curve::Ed25519 => {
+                let (_, public) = cryptoxide::ed25519::keypair(key);
+                let mut out = [0u8; 33];
+                out[1..33].copy_from_slice(&public);
+                out
+            }
+            Curve::Nist256p1 => nist256p1::public_key(key),
+            Curve::Secp256k1 => secp256k1::public_key(key),
+        }
+    }
+}
+
+// the same operations for the curves in short Weierstrass form
+macro_rules! weierstrass {
+    ($name:ident, $curve:ident) => {
+        mod $name {
+            use $curve::elliptic_curve::sec1::ToEncodedPoint;
+            use $curve::elliptic_curve::{Field, PrimeField};
+            use $curve::{ProjectivePoint, Scalar};
+
+            pub(super) fn scalar(bytes: &[u8; 32]) -> Option<Scalar> {
+                let scalar: Option<Scalar> = Scalar::from_repr((*bytes).into()).into();
+                scalar.filter(|s| !bool::from(Field::is_zero(s)))
+            }
+
+            pub(super) fn add(key: &[u8; 32], tweak: &[u8; 32]) -> Option<[u8; 32]> {
+                let tweak: Option<Scalar> = Scalar::from_repr((*tweak).into()).into();
+                let sum = tweak? + scalar(key)?;
+                if bool::from(Field::is_zero(&sum)) {
+                    None
+                } else {
+                    Some(sum.to_repr().into())
+                }
+            }
+
+            pub(super) fn public_key(key: &[u8; 32]) -> [u8; 33] {
+                let point = ProjectivePoint::GENERATOR * scalar(key).unwrap_or(Scalar::ONE);
+                let mut out = [0u8; 33];
+                out.copy_from_slice(point.to_affine().to_encoded_point(true).as_bytes());
+                out
+            }
+        }
+    };
+}
+
+weierstrass!(nist256p1, p256);
+weierstrass!(secp256k1, k256);
+
+/// SLIP-10 extended private key
+///
+/// The key is overwritten with zeros when dropped, and is not displayed
+/// by the `Debug` implementation.
+///
+/// # Example
+///
+/// ```
+/// # use bip39_dict::{ENGLISH, Mnemonics, seed_from_mnemonics};
+/// use bip39_dict::bip32::DerivationPath;
+/// use bip39_dict::slip10::{Curve, ExtendedKey};
+/// # let mnemonics = Mnemonics::<12>::from_string(&ENGLISH, "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about").unwrap();
+///
+/// let seed: [u8; 64] = seed_from_mnemonics(&ENGLISH, &mnemonics, b"", 2048);
+/// // solana account
+/// let key = ExtendedKey::master(Curve::Ed25519, &seed)
+///     .derive_path(&DerivationPath::parse("m/44'/501'/0'/0'").unwrap())
+///     .unwrap();
+/// let public_key = key.public_key();
+/// ```
+#[derive(Clone)]
+pub struct ExtendedKey {
+    curve: Curve,
+    depth: u8,
+    parent_fingerprint: [u8; 4],
+    child_number: ChildNumber,
+    chain_code: [u8; 32],
+    key: [u8; 32],
+}
+
+// split the HMAC-SHA512 output in its left and right halves
+fn split(i: &[u8; 64]) -> ([u8; 32], [u8; 32]) {
+    let mut left = [0u8; 32];
+    let mut right = [0u8; 32];
+    left.copy_from_slice(&i[0..32]);
+    right.copy_from_slice(&i[32..64]);
+    (left, right)
+}
+
+impl ExtendedKey {
+    /// Derive the master key of the given curve from the seed
+    pub fn master(curve: Curve, seed: &[u8]) -> Self {
+        let mac = Hmac::<Context512>::new(curve.hmac_key());
+        let mut i = [0u8; 64];
+        mac.mac([seed], &mut i);
+        // retry with the previous output as seed, until the key is valid
+        loop {
+            let (mut key, chain_code) = split(&i);
+            if curve.is_valid(&key) {
+                ct::zeroize(&mut i);
+                return ExtendedKey {
+                    curve,
+                    depth: 0,
+                    parent_fingerprint: [0; 4],
+                    child_number: ChildNumber::from(0),
+                    chain_code,
+                    key,
+                };
+            }
+            ct::zeroize(&mut key);
+            let previous = i;
+            mac.mac([&previous[..]], &mut i);
+        }
+    }
+
+    /// Derive the child key at the given index
+    pub fn derive_child(&self, child: ChildNumber) -> Result<Self, Slip10Error> {
+        if self.curve == Curve::Ed25519 && !child.is_hardened() {
+            return Err(Slip10Error::HardenedOnly(child));
+        }
+        let depth = self.depth.checked_add(1).ok_or(Slip10Error::MaxDepth)?;
+        let index = u32::from(child).to_be_bytes();
+        let mac = Hmac::<Context512>::new(&self.chain_code);
+        let mut i = [0u8; 64];
+        if child.is_hardened() {
+            mac.mac([&[0u8][..], &self.key, &index], &mut i);
+        } else {
+            mac.mac([&self.public_key()[..], &index], &mut i);
+        }
+        // retry with 0x01 || IR || index, until the key is valid
+        loop {
+            let (mut tweak, chain_code) = split(&i);
+            let key = self.curve.tweak(&self.key, &tweak);
+            ct::zeroize(&mut tweak);
+            if let Some(key) = key {
+                ct::zeroize(&mut i);
+                return Ok(ExtendedKey {
+                    curve: self.curve,
+                    depth,
+                    parent_fingerprint: self.fingerprint(),
+                    child_number: child,
+                    chain_code,
+                    key,
+                });
+            }
+            mac.mac([&[1u8][..], &chain_code, &index], &mut i);
+        }
+    }
+
+    /// Derive the key along the given path, from this key
+    pub fn derive_path(&self, path: &DerivationPath) -> Result<Self, Slip10Error> {
+        let mut key = self.clone();
+        for child in path.children() {
+            key = key.derive_child(child)?;
+        }
+        Ok(key)
+    }
+
+    /// The curve of the key
+    pub fn curve(&self) -> Curve {
+        self.curve
+    }
+
+    /// The private key (32 bytes)
+    pub fn private_key(&self) -> &[u8; 32] {
+        &self.key
+    }
+
+    /// The chain code
+    pub fn chain_code(&self) -> &[u8; 32] {
+        &self.chain_code
+    }
+
+    /// The public key, compressed for the Weierstrass curves, and
+    /// prefixed by a zero byte for ed25519.
+    pub fn public_key(&self) -> [u8; 33] {
+        self.curve.public_key(&self.key)
+    }
+
+    /// The fingerprint of this key, which is the parent fingerprint of its children
+    pub fn fingerprint(&self) -> [u8; 4] {
+        let hash = hash160(&self.public_key());
+        [hash[0], hash[1], hash[2], hash[3]]
+    }
+
+    /// The fingerprint of the parent key, all zeros for the master key
+    pub fn parent_fingerprint(&self) -> [u8; 4] {
+        self.parent_fingerprint
+    }
+
+    /// Depth of the key, 0 for the master key
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    /// Index of this key in its parent
+    pub fn child_number(&self) -> ChildNumber {
+        self.child_number
+    }
+}
+
+impl Drop for ExtendedKey {
+    fn drop(&mut self) {
+        ct::zeroize(&mut self.key);
+        ct::zeroize(&mut self.chain_code);
+    }
+}
+
+impl fmt::Debug for ExtendedKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExtendedKey")
+            .field("curve", &self.curve)
+            .field("depth", &self.depth)
+            .field("child_number", &self.child_number)
+            .finish_non_exhaustive()
+    }
+}
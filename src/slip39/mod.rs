@@ -0,0 +1,754 @@
+//! SLIP-39 Shamir backup
+//!
+//! Split a master secret in share phrases, organized in groups with their own
+//! member threshold, and recover the master secret from enough of them, as
+//! specified by [SLIP-39](https://github.com/satoshilabs/slips/blob/master/slip-0039.md).
+//!
+//! The master secret is encrypted with the passphrase before being split,
+//! and any passphrase decrypts a valid set of shares, to a different secret.
+//!
+//! # Example
+//!
+//! ```
+//! # #[cfg(feature = "rand")]
+//! # {
+//! use bip39_dict::Entropy;
+//! use bip39_dict::slip39::{self, Scheme, Share};
+//!
+//! # struct Rng;
+//! # impl rand_core::RngCore for Rng {
+//! #     fn next_u32(&mut self) -> u32 { 4 }
+//! #     fn next_u64(&mut self) -> u64 { 4 }
+//! #     fn fill_bytes(&mut self, dest: &mut [u8]) { dest.fill(4) }
+//! #     fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> { Ok(dest.fill(4)) }
+//! # }
+//! # impl rand_core::CryptoRng for Rng {}
+//! # let mut rng = Rng;
+//! let entropy = Entropy::<16>([0x42; 16]);
+//! // 2 of 3 shares
+//! let scheme = Scheme::new(1).group(2, 3);
+//! let mut shares = [None, None, None];
+//! scheme
+//!     .split_with(&entropy, b"TREZOR", &mut rng, |share| {
+//!         let index = share.member_index() as usize;
+//!         shares[index] = Some(share);
+//!     })
+//!     .unwrap();
+//!
+//! let phrase = shares[2].as_ref().unwrap().to_string();
+//! let shares = [shares[0].take().unwrap(), Share::from_phrase(&phrase).unwrap()];
+//! assert_eq!(slip39::combine(&shares, b"TREZOR").unwrap(), entropy);
+//! # }
+//! ```
+mod words;
+
+pub use words::WORDS;
+
+use super::ct;
+use super::dictionary::WordNotFound;
+use super::entropy::Entropy;
+use super::hmac::{self, KeyStream};
+use super::secret_sharing::{self, MAX_SHARES};
+use cryptoxide::hashing::sha2::Context256;
+#[cfg(feature = "rand")]
+use rand_core::{CryptoRng, RngCore};
+
+#[cfg(all(feature = "alloc", feature = "rand", not(feature = "std")))]
+use alloc::vec::Vec;
+#[cfg(not(feature = "std"))]
+use core::fmt;
+#[cfg(feature = "std")]
+use {std::error::Error, std::fmt};
+
+const CUSTOMIZATION: &[u8] = b"shamir";
+const CUSTOMIZATION_EXTENDABLE: &[u8] = b"shamir_extendable";
+const BASE_ITERATION_COUNT: u32 = 10000;
+const ROUND_COUNT: u8 = 4;
+const HEADER_WORDS: usize = 4;
+const CHECKSUM_WORDS: usize = 3;
+
+/// Possible errors when splitting or combining SLIP-39 shares
+#[derive(Debug)]
+pub enum Slip39Error {
+    /// The secret size is not supported: it must be even, and at least 16 bytes
+    InvalidLength(usize),
+    /// The phrase doesn't have the number of words of a share of the secret size
+    InvalidWordCount(usize),
+    /// A word of the phrase is not in the SLIP-39 wordlist
+    WordNotFound(WordNotFound),
+    /// The checksum of the share is invalid
+    InvalidChecksum,
+    /// The padding bits of the share value are not zero
+    InvalidPadding,
+    /// The passphrase contains characters other than printable ASCII
+    InvalidPassphrase,
+    /// The iteration exponent is more than 15
+    InvalidIterationExponent(u8),
+    /// The group threshold is zero, or more than the number of groups (at most 16)
+    InvalidGroupThreshold,
+    /// The member threshold of the group is zero, or more than the number of
+    /// members (at most 16), or one while there are several members
+    InvalidMemberThreshold(u8),
+    /// No shares were given
+    NoShares,
+    /// The shares are not from the same split, or have inconsistent parameters
+    MismatchedShares,
+    /// Two different shares have the same index in the group
+    DuplicateShare(u8),
+    /// There are fewer groups of shares than the group threshold
+    NotEnoughGroups,
+    /// There are more groups of shares than the group threshold
+    TooManyGroups,
+    /// The number of shares of the group is not its member threshold
+    WrongShareCount(u8),
+    /// The digest of a recovered secret doesn't match
+    InvalidDigest,
+    /// The random generator failed
+    #[cfg(feature = "rand")]
+    Rng(rand_core::Error),
+}
+
+impl fmt::Display for Slip39Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidLength(length) => write!(f, "Invalid secret length {}", length),
+            Self::InvalidWordCount(words) => write!(f, "Invalid number of words {}", words),
+            Self::WordNotFound(err) => write!(f, "{}", err),
+            Self::InvalidChecksum => write!(f, "Invalid checksum"),
+            Self::InvalidPadding => write!(f, "Invalid padding"),
+            Self::InvalidPassphrase => write!(f, "Passphrase is not printable ASCII"),
+            Self::InvalidIterationExponent(e) => write!(f, "Invalid iteration exponent {}", e),
+            Self::InvalidGroupThreshold => write!(f, "Invalid group threshold"),
+            Self::InvalidMemberThreshold(group) => {
+                write!(f, "Invalid member threshold of group {}", group)
+            }
+            Self::NoShares => write!(f, "No shares"),
+            Self::MismatchedShares => write!(f, "Shares are not from the same set"),
+            Self::DuplicateShare(group) => write!(f, "Duplicate share in group {}", group),
+            Self::NotEnoughGroups => write!(f, "Not enough groups of shares"),
+            Self::TooManyGroups => write!(f, "Too many groups of shares"),
+            Self::WrongShareCount(group) => write!(f, "Wrong number of shares in group {}", group),
+            Self::InvalidDigest => write!(f, "Invalid digest of the shared secret"),
+            #[cfg(feature = "rand")]
+            Self::Rng(err) => write!(f, "Random generator error: {}", err),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for Slip39Error {}
+
+impl From<WordNotFound> for Slip39Error {
+    fn from(e: WordNotFound) -> Self {
+        Slip39Error::WordNotFound(e)
+    }
+}
+
+#[cfg(feature = "rand")]
+impl From<rand_core::Error> for Slip39Error {
+    fn from(e: rand_core::Error) -> Self {
+        Slip39Error::Rng(e)
+    }
+}
+
+// SLIP-39 supports secrets of an even number of bytes, at least 128 bits
+fn check_length<const N: usize>() -> Result<(), Slip39Error> {
+    if N >= 16 && N.is_multiple_of(2) {
+        Ok(())
+    } else {
+        Err(Slip39Error::InvalidLength(N))
+    }
+}
+
+fn check_passphrase(passphrase: &[u8]) -> Result<(), Slip39Error> {
+    if passphrase.iter().all(|c| (32..=126).contains(c)) {
+        Ok(())
+    } else {
+        Err(Slip39Error::InvalidPassphrase)
+    }
+}
+
+#[cfg(feature = "hardened")]
+fn lookup(word: &str) -> Result<u16, WordNotFound> {
+    ct::position(&WORDS, word)
+        .map(|i| i as u16)
+        .ok_or_else(|| WordNotFound::new(word))
+}
+
+#[cfg(not(feature = "hardened"))]
+fn lookup(word: &str) -> Result<u16, WordNotFound> {
+    WORDS
+        .binary_search(&word)
+        .map(|i| i as u16)
+        .map_err(|_| WordNotFound::new(word))
+}
+
+// RS1024 checksum step
+fn polymod(chk: u32, value: u16) -> u32 {
+    const GENERATOR: [u32; 10] = [
+        0xe0e040, 0x1c1c080, 0x3838100, 0x7070200, 0xe0e0009, 0x1c0c2412, 0x38086c24, 0x3090fc48,
+        0x21b1f890, 0x3f3f120,
+    ];
+    let top = chk >> 20;
+    let mut chk = ((chk & 0xfffff) << 10) ^ value as u32;
+    for (i, g) in GENERATOR.iter().enumerate() {
+        if (top >> i) & 1 == 1 {
+            chk ^= g;
+        }
+    }
+    chk
+}
+
+fn customization(extendable: bool) -> &'static [u8] {
+    if extendable {
+        CUSTOMIZATION_EXTENDABLE
+    } else {
+        CUSTOMIZATION
+    }
+}
+
+// initial checksum state, after the customization string
+fn checksum_init(extendable: bool) -> u32 {
+    customization(extendable)
+        .iter()
+        .fold(1, |chk, c| polymod(chk, *c as u16))
+}
+
+// Encrypt or decrypt the secret in place, with the 4 rounds Feistel network
+fn feistel<const N: usize>(
+    secret: &mut [u8; N],
+    passphrase: &[u8],
+    identifier: u16,
+    extendable: bool,
+    iteration_exponent: u8,
+    decrypt: bool,
+) {
+    let half = N / 2;
+    let mut salt = [0u8; 8];
+    let salt = if extendable {
+        &salt[0..0]
+    } else {
+        salt[0..6].copy_from_slice(CUSTOMIZATION);
+        salt[6..8].copy_from_slice(&identifier.to_be_bytes());
+        &salt[..]
+    };
+    let iterations = (BASE_ITERATION_COUNT << iteration_exponent) / ROUND_COUNT as u32;
+
+    let mut left = [0u8; N];
+    let mut right = [0u8; N];
+    let mut f = [0u8; N];
+    left[0..half].copy_from_slice(&secret[0..half]);
+    right[0..half].copy_from_slice(&secret[half..N]);
+    for round in 0..ROUND_COUNT {
+        let i = if decrypt {
+            ROUND_COUNT - 1 - round
+        } else {
+            round
+        };
+        // F = PBKDF2-HMAC-SHA256(i || passphrase, salt || R)
+        let mut key = KeyStream::<Context256>::new();
+        key.update(&[i]);
+        key.update(passphrase);
+        hmac::pbkdf2(
+            &key.finalize(),
+            &[salt, &right[0..half]],
+            iterations,
+            &mut f[0..half],
+        );
+        for (l, f) in left[0..half].iter_mut().zip(f.iter()) {
+            *l ^= f;
+        }
+        core::mem::swap(&mut left, &mut right);
+    }
+    secret[0..half].copy_from_slice(&right[0..half]);
+    secret[half..N].copy_from_slice(&left[0..half]);
+    ct::zeroize(&mut left);
+    ct::zeroize(&mut right);
+    ct::zeroize(&mut f);
+}
+
+/// A SLIP-39 share of a secret of N bytes
+///
+/// The share is displayed as its phrase. The value of the share is overwritten
+/// with zeros when dropped, and is not displayed by the `Debug` implementation.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Share<const N: usize> {
+    identifier: u16,
+    extendable: bool,
+    iteration_exponent: u8,
+    group_index: u8,
+    group_threshold: u8,
+    group_count: u8,
+    member_index: u8,
+    member_threshold: u8,
+    value: [u8; N],
+}
+
+impl<const N: usize> Share<N> {
+    /// Number of words of the phrase of a share
+    pub const WORD_COUNT: usize = HEADER_WORDS + (N * 8).div_ceil(10) + CHECKSUM_WORDS;
+
+    // number of zero bits before the value
+    const PADDING: usize = (N * 8).div_ceil(10) * 10 - N * 8;
+
+    /// Parse the share from its phrase, of [`Share::WORD_COUNT`] words
+    pub fn from_phrase(phrase: &str) -> Result<Self, Slip39Error> {
+        check_length::<N>()?;
+        let count = phrase.split_whitespace().count();
+        if count != Self::WORD_COUNT {
+            return Err(Slip39Error::InvalidWordCount(count));
+        }
+
+        // the customization string of the checksum depends on the extendable flag,
+        // in the second word: compute both checksums
+        let mut checksums = [checksum_init(false), checksum_init(true)];
+        let mut header = [0u16; HEADER_WORDS];
+        let mut value = [0u8; N];
+        let mut position = 0;
+        let mut acc = 0u32;
+        let mut bits = 0;
+        let mut padding = Self::PADDING;
+        let mut padding_valid = true;
+        for (i, word) in phrase.split_whitespace().enumerate() {
+            let w = match lookup(word) {
+                Ok(w) => w,
+                Err(e) => {
+                    ct::zeroize(&mut value);
+                    return Err(e.into());
+                }
+            };
+            for chk in checksums.iter_mut() {
+                *chk = polymod(*chk, w);
+            }
+            if i < HEADER_WORDS {
+                header[i] = w;
+            } else if i < count - CHECKSUM_WORDS {
+                acc = (acc << 10) | w as u32;
+                bits += 10;
+                if padding > 0 {
+                    bits -= padding;
+                    padding_valid &= acc >> bits == 0;
+                    acc &= (1 << bits) - 1;
+                    padding = 0;
+                }
+                while bits >= 8 {
+                    bits -= 8;
+                    value[position] = (acc >> bits) as u8;
+                    position += 1;
+                    acc &= (1 << bits) - 1;
+                }
+            }
+        }
+
+        let id_exp = (header[0] as u32) << 10 | header[1] as u32;
+        let extendable = (id_exp >> 4) & 1 == 1;
+        let share = Share {
+            identifier: (id_exp >> 5) as u16,
+            extendable,
+            iteration_exponent: (id_exp & 0xf) as u8,
+            group_index: (header[2] >> 6) as u8,
+            group_threshold: ((header[2] >> 2) & 0xf) as u8 + 1,
+            group_count: (((header[2] & 0x3) << 2) | (header[3] >> 8)) as u8 + 1,
+            member_index: ((header[3] >> 4) & 0xf) as u8,
+            member_threshold: (header[3] & 0xf) as u8 + 1,
+            value,
+        };
+        if checksums[extendable as usize] != 1 {
+            return Err(Slip39Error::InvalidChecksum);
+        }
+        if !padding_valid {
+            return Err(Slip39Error::InvalidPadding);
+        }
+        if share.group_threshold > share.group_count {
+            return Err(Slip39Error::InvalidGroupThreshold);
+        }
+        Ok(share)
+    }
+
+    /// Random identifier common to all the shares of a split (15 bits)
+    pub fn identifier(&self) -> u16 {
+        self.identifier
+    }
+
+    /// Whether the identifier is not used in the encryption, so that new
+    /// share sets of the same secret can be made with a different identifier
+    pub fn is_extendable(&self) -> bool {
+        self.extendable
+    }
+
+    /// The encryption cost, of 10000 * 2^e PBKDF2 iterations
+    pub fn iteration_exponent(&self) -> u8 {
+        self.iteration_exponent
+    }
+
+    /// Index of the group of the share
+    pub fn group_index(&self) -> u8 {
+        self.group_index
+    }
+
+    /// Number of groups needed to recover the secret
+    pub fn group_threshold(&self) -> u8 {
+        self.group_threshold
+    }
+
+    /// Number of groups
+    pub fn group_count(&self) -> u8 {
+        self.group_count
+    }
+
+    /// Index of the share in its group
+    pub fn member_index(&self) -> u8 {
+        self.member_index
+    }
+
+    /// Number of shares of the group needed to recover the group secret
+    pub fn member_threshold(&self) -> u8 {
+        self.member_threshold
+    }
+
+    // give all the data words (without the checksum) to f
+    fn for_each_word<F: FnMut(u16)>(&self, mut f: F) {
+        let id_exp = (self.identifier as u32) << 5
+            | (self.extendable as u32) << 4
+            | self.iteration_exponent as u32;
+        let params = (self.group_index as u32) << 16
+            | ((self.group_threshold - 1) as u32) << 12
+            | ((self.group_count - 1) as u32) << 8
+            | (self.member_index as u32) << 4
+            | (self.member_threshold - 1) as u32;
+        for w in [id_exp >> 10, id_exp & 1023, params >> 10, params & 1023] {
+            f(w as u16);
+        }
+        let mut acc = 0u32;
+        let mut bits = Self::PADDING;
+        for b in self.value.iter() {
+            acc = (acc << 8) | *b as u32;
+            bits += 8;
+            while bits >= 10 {
+                bits -= 10;
+                f(((acc >> bits) & 1023) as u16);
+                acc &= (1 << bits) - 1;
+            }
+        }
+    }
+}
+
+impl<const N: usize> Drop for Share<N> {
+    fn drop(&mut self) {
+        ct::zeroize(&mut self.value)
+    }
+}
+
+impl<const N: usize> fmt::Debug for Share<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Share")
+            .field("identifier", &self.identifier)
+            .field("group_index", &self.group_index)
+            .field("group_threshold", &self.group_threshold)
+            .field("group_count", &self.group_count)
+            .field("member_index", &self.member_index)
+            .field("member_threshold", &self.member_threshold)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<const N: usize> fmt::Display for Share<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut chk = checksum_init(self.extendable);
+        self.for_each_word(|w| chk = polymod(chk, w));
+        for _ in 0..CHECKSUM_WORDS {
+            chk = polymod(chk, 0);
+        }
+        chk ^= 1;
+
+        let mut result = Ok(());
+        let mut first = true;
+        let mut write = |w: u16| {
+            if result.is_ok() {
+                let separator = if first { "" } else { " " };
+                first = false;
+                result = write!(f, "{}{}", separator, WORDS[w as usize]);
+            }
+        };
+        self.for_each_word(&mut write);
+        for i in (0..CHECKSUM_WORDS).rev() {
+            write(((chk >> (10 * i)) & 1023) as u16);
+        }
+        result
+    }
+}
+
+/// Parameters of a split: the groups with their thresholds, and the encryption
+///
+/// # Example
+///
+/// ```
+/// use bip39_dict::slip39::Scheme;
+///
+/// // the owner's 2 of 3 shares, or 1 of them and 3 of the 5 shares of the family
+/// let scheme = Scheme::new(2).group(2, 3).group(3, 5).iteration_exponent(1);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Scheme {
+    group_threshold: u8,
+    groups: [(u8, u8); MAX_SHARES],
+    group_count: usize,
+    iteration_exponent: u8,
+    extendable: bool,
+}
+
+impl Scheme {
+    /// Create a new scheme with the given group threshold and no groups yet,
+    /// extendable, and with the iteration exponent 0
+    pub const fn new(group_threshold: u8) -> Self {
+        Scheme {
+            group_threshold,
+            groups: [(0, 0); MAX_SHARES],
+            group_count: 0,
+            iteration_exponent: 0,
+            extendable: true,
+        }
+    }
+
+    /// Add a group of `count` shares, of which `threshold` are needed
+    ///
+    /// At most 16 groups can be added, further groups make the split fail.
+    pub const fn group(mut self, threshold: u8, count: u8) -> Self {
+        if self.group_count < MAX_SHARES {
+            self.groups[self.group_count] = (threshold, count);
+        }
+        self.group_count += 1;
+        self
+    }
+
+    /// Set the iteration exponent (at most 15): the encryption costs 10000 * 2^e
+    /// PBKDF2-HMAC-SHA256 iterations
+    pub const fn iteration_exponent(mut self, iteration_exponent: u8) -> Self {
+        self.iteration_exponent = iteration_exponent;
+        self
+    }
+
+    /// Set whether the shares are extendable
+    pub const fn extendable(mut self, extendable: bool) -> Self {
+        self.extendable = extendable;
+        self
+    }
+
+    #[cfg(feature = "rand")]
+    fn check(&self) -> Result<(), Slip39Error> {
+        if self.iteration_exponent > 15 {
+            return Err(Slip39Error::InvalidIterationExponent(
+                self.iteration_exponent,
+            ));
+        }
+        if self.group_count == 0
+            || self.group_count > MAX_SHARES
+            || self.group_threshold == 0
+            || self.group_threshold as usize > self.group_count
+        {
+            return Err(Slip39Error::InvalidGroupThreshold);
+        }
+        for (i, (threshold, count)) in self.groups[0..self.group_count].iter().enumerate() {
+            if *threshold == 0
+                || threshold > count
+                || *count as usize > MAX_SHARES
+                || (*threshold == 1 && *count > 1)
+            {
+                return Err(Slip39Error::InvalidMemberThreshold(i as u8));
+            }
+        }
+        Ok(())
+    }
+
+    /// Split the master secret encrypted with the passphrase, giving each
+    /// share to `out`, group after group (with the `rand` feature).
+    ///
+    /// The cryptographically secure random generator is used for the identifier
+    /// and the random coefficients of the sharing polynomials.
+    ///
+    /// # Error
+    ///
+    /// Any error from the random generator is returned. As the groups are split
+    /// one after the other, the shares already given to `out` are then incomplete
+    /// and must be discarded.
+    #[cfg(feature = "rand")]
+    pub fn split_with<const N: usize, R, F>(
+        &self,
+        entropy: &Entropy<N>,
+        passphrase: &[u8],
+        rng: &mut R,
+        mut out: F,
+    ) -> Result<(), Slip39Error>
+    where
+        R: RngCore + CryptoRng,
+        F: FnMut(Share<N>),
+    {
+        check_length::<N>()?;
+        check_passphrase(passphrase)?;
+        self.check()?;
+
+        let mut identifier = [0u8; 2];
+        rng.try_fill_bytes(&mut identifier)?;
+        let identifier = u16::from_be_bytes(identifier) & 0x7fff;
+        let mut encrypted = entropy.0;
+        feistel(
+            &mut encrypted,
+            passphrase,
+            identifier,
+            self.extendable,
+            self.iteration_exponent,
+            false,
+        );
+
+        let group_count = self.group_count as u8;
+        let mut group_secrets = [[0u8; N]; MAX_SHARES];
+        let mut result = secret_sharing::split_secret(
+            self.group_threshold,
+            group_count,
+            &encrypted,
+            rng,
+            |i, value| group_secrets[i as usize] = *value,
+        );
+        ct::zeroize(&mut encrypted);
+
+        for (group_index, ((threshold, count), secret)) in
+            self.groups.iter().zip(group_secrets.iter()).enumerate()
+        {
+            if group_index == self.group_count || result.is_err() {
+                break;
+            }
+            result = secret_sharing::split_secret(*threshold, *count, secret, rng, |i, value| {
+                out(Share {
+                    identifier,
+                    extendable: self.extendable,
+                    iteration_exponent: self.iteration_exponent,
+                    group_index: group_index as u8,
+                    group_threshold: self.group_threshold,
+                    group_count,
+                    member_index: i,
+                    member_threshold: *threshold,
+                    value: *value,
+                })
+            });
+        }
+        for secret in group_secrets.iter_mut() {
+            ct::zeroize(secret);
+        }
+        Ok(result?)
+    }
+
+    /// Split the master secret encrypted with the passphrase, returning the
+    /// shares of each group (with the `rand` feature)
+    #[cfg(all(feature = "alloc", feature = "rand"))]
+    pub fn split<const N: usize, R: RngCore + CryptoRng>(
+        &self,
+        entropy: &Entropy<N>,
+        passphrase: &[u8],
+        rng: &mut R,
+    ) -> Result<Vec<Vec<Share<N>>>, Slip39Error> {
+        let mut groups: Vec<Vec<Share<N>>> = Vec::new();
+        self.split_with(entropy, passphrase, rng, |share| {
+            match groups.get_mut(share.group_index as usize) {
+                Some(group) => group.push(share),
+                None => {
+                    let mut group = Vec::with_capacity(share.member_threshold as usize);
+                    group.push(share);
+                    groups.push(group)
+                }
+            }
+        })?;
+        Ok(groups)
+    }
+}
+
+/// Recover the master secret from the shares, and decrypt it with the passphrase
+///
+/// The shares must come from exactly the group threshold of groups, with
+/// exactly the member threshold of shares in each.
+pub fn combine<const N: usize>(
+    shares: &[Share<N>],
+    passphrase: &[u8],
+) -> Result<Entropy<N>, Slip39Error> {
+    check_length::<N>()?;
+    check_passphrase(passphrase)?;
+    let first = shares.first().ok_or(Slip39Error::NoShares)?;
+    let same_split = shares.iter().all(|s| {
+        s.identifier == first.identifier
+            && s.extendable == first.extendable
+            && s.iteration_exponent == first.iteration_exponent
+            && s.group_threshold == first.group_threshold
+            && s.group_count == first.group_count
+    });
+    if !same_split {
+        return Err(Slip39Error::MismatchedShares);
+    }
+
+    let mut group_secrets = [[0u8; N]; MAX_SHARES];
+    let mut group_indices = [0u8; MAX_SHARES];
+    let mut groups = 0;
+    for group_index in 0..first.group_count {
+        // the distinct shares of the group
+        let mut points: [(u8, &[u8]); MAX_SHARES] = [(0, &[]); MAX_SHARES];
+        let mut count = 0;
+        let mut member_threshold = 0;
+        for share in shares.iter().filter(|s| s.group_index == group_index) {
+            if count == 0 {
+                member_threshold = share.member_threshold;
+            } else if share.member_threshold != member_threshold {
+                return Err(Slip39Error::MismatchedShares);
+            }
+            match points[0..count]
+                .iter()
+                .find(|(x, _)| *x == share.member_index)
+            {
+                Some((_, value)) if *value == share.value => continue,
+                Some(_) => return Err(Slip39Error::DuplicateShare(group_index)),
+                None if count == MAX_SHARES => {
+                    return Err(Slip39Error::WrongShareCount(group_index))
+                }
+                None => {
+                    points[count] = (share.member_index, &share.value[..]);
+                    count += 1;
+                }
+            }
+        }
+        if count == 0 {
+            continue;
+        }
+        if count != member_threshold as usize {
+            return Err(Slip39Error::WrongShareCount(group_index));
+        }
+        if groups == first.group_threshold as usize {
+            return Err(Slip39Error::TooManyGroups);
+        }
+        group_secrets[groups] = secret_sharing::recover_secret(member_threshold, &points[0..count])
+            .ok_or(Slip39Error::InvalidDigest)?;
+        group_indices[groups] = group_index;
+        groups += 1;
+    }
+    if groups < first.group_threshold as usize {
+        return Err(Slip39Error::NotEnoughGroups);
+    }
+
+    let mut points: [(u8, &[u8]); MAX_SHARES] = [(0, &[]); MAX_SHARES];
+    for (p, (x, value)) in points
+        .iter_mut()
+        .zip(group_indices.iter().zip(group_secrets.iter()))
+    {
+        *p = (*x, &value[..]);
+    }
+    let secret = secret_sharing::recover_secret(first.group_threshold, &points[0..groups]);
+    for value in group_secrets.iter_mut() {
+        ct::zeroize(value);
+    }
+    let mut secret = secret.ok_or(Slip39Error::InvalidDigest)?;
+    feistel(
+        &mut secret,
+        passphrase,
+        first.identifier,
+        first.extendable,
+        first.iteration_exponent,
+        true,
+    );
+    Ok(Entropy(secret))
+}
@@ -4,7 +4,7 @@ extern crate unicode_normalization;
 use self::unicode_normalization::UnicodeNormalization;
 
 #[cfg(not(feature = "std"))]
-use alloc::string::String;
+use std::string::String;
 
 #[test]
 fn mnemonic_zero() {
@@ -22,6 +22,2068 @@ fn mnemonic_zero() {
     assert_eq!(entropy.0, entropy2.0);
 }
 
+#[test]
+#[cfg(feature = "english")]
+fn checksum_invalid() {
+    const MNEMONICS: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon";
+    let mnemonics = Mnemonics::<12>::from_string(&dictionary::ENGLISH, MNEMONICS).unwrap();
+    assert!(matches!(
+        Entropy::<16>::from_mnemonics::<12, 4>(&mnemonics),
+        Err(EntropyError::ChecksumInvalid)
+    ));
+}
+
+#[test]
+#[cfg(feature = "english")]
+fn redacted_outputs() {
+    let entropy = Entropy::<16>([0; 16]);
+    let mnemonics = entropy.to_mnemonics::<12, 4>().unwrap();
+    assert_eq!(format!("{:?}", entropy), "Entropy<16>(<redacted>)");
+    assert_eq!(format!("{:?}", mnemonics), "Mnemonics<12>(<redacted>)");
+    assert_eq!(
+        format!("{:?}", MnemonicIndex(1234)),
+        "MnemonicIndex(<redacted>)"
+    );
+
+    const TYPO: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandn about";
+    let err = Mnemonics::<12>::from_string(&dictionary::ENGLISH, TYPO).unwrap_err();
+    assert!(!format!("{} {:?}", err, err).contains("abandn"));
+    match &err {
+        MnemonicError::WordError { index, err } => {
+            assert_eq!(*index, 10);
+            #[cfg(feature = "alloc")]
+            assert_eq!(err.word_searched, "abandn");
+            assert_eq!(err.length(), WordLength::Medium);
+        }
+        _ => panic!("expecting a word error"),
+    }
+
+    let err = Mnemonics::<12>::from_string_redacted(&dictionary::ENGLISH, TYPO).unwrap_err();
+    match err {
+        MnemonicError::RedactedWordError { index, length } => {
+            assert_eq!(index, 10);
+            assert_eq!(length, WordLength::Medium);
+        }
+        _ => panic!("expecting a redacted word error"),
+    }
+}
+
+#[test]
+#[cfg(feature = "english")]
+fn seed_from_raw_phrase() {
+    const CANONICAL: &str =
+        "legal winner thank year wave sausage worth useful legal winner thank yellow";
+    const SPACED: &str =
+        " legal winner thank year wave sausage worth useful legal winner  thank yellow";
+    let dic = &dictionary::ENGLISH;
+    let mnemonics = Mnemonics::<12>::from_string(dic, CANONICAL).unwrap();
+
+    let seed: [u8; 64] = seed_from_mnemonics(dic, &mnemonics, b"TREZOR", 2048);
+    let seed_raw: [u8; 64] = seed_from_phrase(CANONICAL, b"TREZOR", 2048);
+    assert_eq!(seed, seed_raw);
+
+    let seed_spaced: [u8; 64] = seed_from_phrase(SPACED, b"TREZOR", 2048);
+    assert_ne!(seed, seed_spaced);
+    let seed_checked: [u8; 64] =
+        seed_from_phrase_checked::<_, 12, 64>(dic, SPACED, b"TREZOR", 2048).unwrap();
+    assert_eq!(seed_spaced, seed_checked);
+
+    // words typed as unique prefixes, as accepted by some wallets
+    const PREFIXES: &str = "lega winn than year wave saus wort usef lega winn than yell";
+    let seed_prefixes: [u8; 64] =
+        seed_from_phrase_checked::<_, 12, 64>(dic, PREFIXES, b"TREZOR", 2048).unwrap();
+    assert_eq!(
+        seed_prefixes,
+        seed_from_phrase::<64>(PREFIXES, b"TREZOR", 2048)
+    );
+    assert_ne!(seed_prefixes, seed);
+    // "wa" is the prefix of several words
+    assert!(matches!(
+        seed_from_phrase_checked::<_, 12, 64>(
+            dic,
+            "legal winner thank year wa sausage worth useful legal winner thank yellow",
+            b"",
+            2048
+        ),
+        Err(PhraseError::Words(MnemonicError::WordError {
+            index: 4,
+            ..
+        }))
+    ));
+
+    assert!(matches!(
+        seed_from_phrase_checked::<_, 12, 64>(dic, "legal winner", b"", 2048),
+        Err(PhraseError::Words(MnemonicError::InvalidWords { .. }))
+    ));
+    assert!(matches!(
+        seed_from_phrase_checked::<_, 12, 64>(
+            dic,
+            "legal winner thank year wave sausage worth useful legal winner thank year",
+            b"",
+            2048
+        ),
+        Err(PhraseError::Checksum(EntropyError::ChecksumInvalid))
+    ));
+}
+
+#[test]
+#[cfg(feature = "english")]
+fn seed_scheme() {
+    const MNEMONICS: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    let dic = &dictionary::ENGLISH;
+    let mnemonics = Mnemonics::<12>::from_string(dic, MNEMONICS).unwrap();
+
+    let seed: [u8; 64] = SeedScheme::bip39().derive(dic, &mnemonics, b"TREZOR");
+    assert_eq!(seed, seed_from_mnemonics(dic, &mnemonics, b"TREZOR", 2048));
+
+    let seed: [u8; 32] = SeedScheme::new(b"electrum")
+        .prf(Prf::HmacSha256)
+        .extra_salt(b"extra")
+        .derive(dic, &mnemonics, b"password");
+    assert_eq!(
+        hex::encode(seed),
+        "372d37d0bd7f96e0b66270eb00ca615f012b2d0b90e1f901768dfdbcbecb3883"
+    );
+
+    let seed: [u8; 64] = SeedScheme::bip39()
+        .iterations(2048)
+        .unwrap()
+        .derive(dic, &mnemonics, b"TREZOR");
+    assert_eq!(seed, seed_from_mnemonics(dic, &mnemonics, b"TREZOR", 2048));
+    assert_eq!(SeedScheme::bip39().iterations(0), Err(InvalidCost));
+}
+
+#[test]
+#[cfg(feature = "english")]
+fn seed_substrate() {
+    // substrate-bip39 test vectors, with the "Substrate" password
+    let dic = &dictionary::ENGLISH;
+    let phrase = "ozone drill grab fiber curtain grace pudding thank cruise elder eight picnic";
+    let entropy =
+        Entropy::<16>::from_mnemonics::<12, 4>(&Mnemonics::from_string(dic, phrase).unwrap())
+            .unwrap();
+    assert_eq!(hex::encode(entropy.0), "9e885d952ad362caeb4efe34a8e91bd2");
+    let seed = substrate_seed_from_entropy(&entropy, b"Substrate");
+    assert_eq!(hex::encode(seed.as_bytes()), "f4956be6960bc145cdab782e649a5056598fd07cd3f32ceb73421c3da27833241324dc2c8b0a4d847eee457e6d4c5429f5e625ece22abaa6a976e82f1ec5531d");
+    let mini_secret = substrate_mini_secret(&entropy, b"Substrate");
+    assert_eq!(mini_secret.as_bytes()[..], seed.as_bytes()[..32]);
+
+    let phrase = "gravity machine north sort system female filter attitude volume fold club stay feature office ecology stable narrow fog";
+    let entropy =
+        Entropy::<24>::from_mnemonics::<18, 6>(&Mnemonics::from_string(dic, phrase).unwrap())
+            .unwrap();
+    assert_eq!(hex::encode(substrate_seed_from_entropy(&entropy, b"Substrate").as_bytes()), "fbcc5229ade0c0ff018cb7a329c5459f91876e4dde2a97ddf03c832eab7f26124366a543f1485479c31a9db0d421bda82d7e1fe562e57f3533cb1733b001d84d");
+
+    let phrase = "hamster diagram private dutch cause delay private meat slide toddler razor book happy fancy gospel tennis maple dilemma loan word shrug inflict delay length";
+    let entropy =
+        Entropy::<32>::from_mnemonics::<24, 8>(&Mnemonics::from_string(dic, phrase).unwrap())
+            .unwrap();
+    assert_eq!(hex::encode(substrate_seed_from_entropy(&entropy, b"Substrate").as_bytes()), "7c60c555126c297deddddd59f8cdcdc9e3608944455824dd604897984b5cc369cad749803bb36eb8b786b570c9cdc8db275dbe841486676a6adf389f3be3f076");
+
+    // the entropy is the HMAC key, padded with zeros to the hash block: the
+    // zero entropies of 12, 18 and 24 words give the same seed
+    let zero_seed = "44e9d125f037ac1d51f0a7d3649689d422c2af8b1ec8e00d71db4d7bf6d127e33f50c3d5c84fa3e5399c72d6cbbbbc4a49bf76f76d952f479d74655a2ef2d453";
+    let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    let entropy =
+        Entropy::<16>::from_mnemonics::<12, 4>(&Mnemonics::from_string(dic, phrase).unwrap())
+            .unwrap();
+    assert_eq!(
+        hex::encode(substrate_seed_from_entropy(&entropy, b"Substrate").as_bytes()),
+        zero_seed
+    );
+    let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon agent";
+    let entropy =
+        Entropy::<24>::from_mnemonics::<18, 6>(&Mnemonics::from_string(dic, phrase).unwrap())
+            .unwrap();
+    assert_eq!(
+        hex::encode(substrate_seed_from_entropy(&entropy, b"Substrate").as_bytes()),
+        zero_seed
+    );
+    let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon art";
+    let entropy =
+        Entropy::<32>::from_mnemonics::<24, 8>(&Mnemonics::from_string(dic, phrase).unwrap())
+            .unwrap();
+    assert_eq!(
+        hex::encode(substrate_seed_from_entropy(&entropy, b"Substrate").as_bytes()),
+        zero_seed
+    );
+
+    let phrase = "legal winner thank year wave sausage worth useful legal winner thank yellow";
+    let entropy =
+        Entropy::<16>::from_mnemonics::<12, 4>(&Mnemonics::from_string(dic, phrase).unwrap())
+            .unwrap();
+    assert_eq!(hex::encode(substrate_seed_from_entropy(&entropy, b"Substrate").as_bytes()), "4313249608fe8ac10fd5886c92c4579007272cb77c21551ee5b8d60b780416850f1e26c1f4b8d88ece681cb058ab66d6182bc2ce5a03181f7b74c27576b5c8bf");
+    let phrase = "letter advice cage absurd amount doctor acoustic avoid letter advice cage absurd amount doctor acoustic avoid letter always";
+    let entropy =
+        Entropy::<24>::from_mnemonics::<18, 6>(&Mnemonics::from_string(dic, phrase).unwrap())
+            .unwrap();
+    assert_eq!(hex::encode(substrate_seed_from_entropy(&entropy, b"Substrate").as_bytes()), "9ddecf32ce6bee77f867f3c4bb842d1f0151826a145cb4489598fe71ac29e3551b724f01052d1bc3f6d9514d6df6aa6d0291cfdf997a5afdb7b6a614c88ab36a");
+    let phrase = "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo vote";
+    let entropy =
+        Entropy::<32>::from_mnemonics::<24, 8>(&Mnemonics::from_string(dic, phrase).unwrap())
+            .unwrap();
+    assert_eq!(
+        hex::encode(substrate_mini_secret(&entropy, b"Substrate").as_bytes()),
+        "047e89ef7739cbfe30da0ad32eb1720d8f62441dd4f139b981b8e2d0bd412ed4"
+    );
+}
+
+#[test]
+#[cfg(all(feature = "english", feature = "alloc"))]
+fn seed_memory_hard() {
+    const MNEMONICS: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    let dic = &dictionary::ENGLISH;
+    let mnemonics = Mnemonics::<12>::from_string(dic, MNEMONICS).unwrap();
+
+    let cost = ScryptCost {
+        log_n: 10,
+        r: 8,
+        p: 1,
+    };
+    let seed: [u8; 64] = seed_from_mnemonics_scrypt(dic, &mnemonics, b"TREZOR", &cost).unwrap();
+    assert_eq!(hex::encode(seed), "bafa9e1857b26ca8455f685df3759f2b087fbc9c205e3b048e0d4349e3d0b31e377873ec0a11a78aa39400c7ad1e2cd2628cd17040056929c43a3e49f63c3e98");
+
+    let cost = Argon2Cost {
+        memory_kb: 64,
+        iterations: 3,
+        parallelism: 1,
+    };
+    let seed: [u8; 64] = seed_from_mnemonics_argon2id(dic, &mnemonics, b"TREZOR", &cost).unwrap();
+    assert_eq!(hex::encode(seed), "8ada1b85f4a6c98307422fb5fc23f15c4447760aa2f7296c28a5c7243336c2d75b8121b2cb381b47ff5540cb7bdf6791ebb92f039339a1b1d50e789b3239b983");
+
+    let invalid = ScryptCost {
+        log_n: 0,
+        r: 8,
+        p: 1,
+    };
+    assert_eq!(
+        seed_from_mnemonics_scrypt::<_, 12, 64>(dic, &mnemonics, b"", &invalid),
+        Err(InvalidCost)
+    );
+    let invalid = Argon2Cost {
+        iterations: 0,
+        ..cost
+    };
+    assert_eq!(
+        seed_from_mnemonics_argon2id::<_, 12, 64>(dic, &mnemonics, b"", &invalid),
+        Err(InvalidCost)
+    );
+}
+
+// fixed capacity writer, to check rendering without allocation
+#[cfg(feature = "english")]
+struct FixedWriter<const N: usize> {
+    buf: [u8; N],
+    len: usize,
+}
+
+#[cfg(feature = "english")]
+impl<const N: usize> core::fmt::Write for FixedWriter<N> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        let end = self.len + s.len();
+        if end > N {
+            return Err(core::fmt::Error);
+        }
+        self.buf[self.len..end].copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+#[test]
+#[cfg(feature = "english")]
+fn render_without_alloc() {
+    const MNEMONICS: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    let dic = &dictionary::ENGLISH;
+    let mnemonics = Mnemonics::<12>::from_string(dic, MNEMONICS).unwrap();
+
+    let mut writer = FixedWriter::<128> {
+        buf: [0; 128],
+        len: 0,
+    };
+    mnemonics.write_to(dic, &mut writer).unwrap();
+    assert_eq!(&writer.buf[0..writer.len], MNEMONICS.as_bytes());
+
+    let mut small = FixedWriter::<16> {
+        buf: [0; 16],
+        len: 0,
+    };
+    assert!(mnemonics.write_to(dic, &mut small).is_err());
+
+    assert_eq!(format!("{}", mnemonics.display(dic)), MNEMONICS);
+}
+
+#[test]
+#[cfg(feature = "english")]
+fn seed_incremental() {
+    const MNEMONICS: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    let dic = &dictionary::ENGLISH;
+    let mnemonics = Mnemonics::<12>::from_string(dic, MNEMONICS).unwrap();
+
+    // chunks not dividing the number of iterations, and more than one PBKDF2 block
+    let mut derivation = SeedDerivation::<100>::new(dic, &mnemonics, b"TREZOR", 2048).unwrap();
+    assert_eq!(derivation.total_iterations(), 4096);
+    let mut last = 0.0;
+    while !derivation.step(300) {
+        assert!(derivation.progress() > last);
+        last = derivation.progress();
+    }
+    assert_eq!(derivation.progress(), 1.0);
+    let seed = derivation.finalize().unwrap();
+    let expected: [u8; 100] = seed_from_mnemonics(dic, &mnemonics, b"TREZOR", 2048);
+    assert_eq!(seed, expected);
+
+    let seed = SeedDerivation::<64>::new(dic, &mnemonics, b"TREZOR", 2048)
+        .unwrap()
+        .run(512, |_| true);
+    assert_eq!(seed.map(hex::encode).as_deref(), Some("c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04"));
+
+    // cancellation after the first chunk
+    let mut reports = 0;
+    let seed = SeedDerivation::<64>::new(dic, &mnemonics, b"TREZOR", 2048)
+        .unwrap()
+        .run(512, |_| {
+            reports += 1;
+            false
+        });
+    assert_eq!(seed, None);
+    assert_eq!(reports, 1);
+
+    let mut derivation = SeedDerivation::<64>::new(dic, &mnemonics, b"TREZOR", 2048).unwrap();
+    derivation.step(1000);
+    assert!(!derivation.is_done());
+    assert_eq!(derivation.done_iterations(), 1000);
+    assert_eq!(derivation.finalize(), None);
+
+    // at least one iteration, and one iteration per chunk
+    assert!(matches!(
+        SeedDerivation::<64>::new(dic, &mnemonics, b"TREZOR", 0),
+        Err(InvalidCost)
+    ));
+    let derivation = SeedDerivation::<64>::new(dic, &mnemonics, b"TREZOR", 2048).unwrap();
+    let seed = derivation.run(0, |_| true).unwrap();
+    assert_eq!(
+        seed[..],
+        seed_from_mnemonics::<_, 12, 64>(dic, &mnemonics, b"TREZOR", 2048)
+    );
+}
+
+#[test]
+#[cfg(feature = "english")]
+fn seed_type() {
+    const MNEMONICS: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    let dic = &dictionary::ENGLISH;
+    let mnemonics = Mnemonics::<12>::from_string(dic, MNEMONICS).unwrap();
+
+    let seed = Seed::<64>::from_mnemonics(dic, &mnemonics, b"TREZOR");
+    let expected: [u8; 64] = seed_from_mnemonics(dic, &mnemonics, b"TREZOR", 2048);
+    assert_eq!(seed.as_bytes(), &expected);
+    assert_eq!(seed, Seed::from(expected));
+    assert_eq!(format!("{}", seed), hex::encode(expected));
+    assert_eq!(format!("{:?}", seed), "Seed<64>(<redacted>)");
+}
+
+#[test]
+#[cfg(feature = "bip32")]
+fn bip32_vectors() {
+    use bip32::{ChildNumber, DerivationPath, ExtendedPrivateKey, ExtendedPublicKey, Network};
+    use std::string::ToString;
+
+    // BIP32 test vector 1
+    const VECTORS: [(&str, &str, &str); 6] = [
+        ("m", "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi", "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8"),
+        ("m/0'", "xprv9uHRZZhk6KAJC1avXpDAp4MDc3sQKNxDiPvvkX8Br5ngLNv1TxvUxt4cV1rGL5hj6KCesnDYUhd7oWgT11eZG7XnxHrnYeSvkzY7d2bhkJ7", "xpub68Gmy5EdvgibQVfPdqkBBCHxA5htiqg55crXYuXoQRKfDBFA1WEjWgP6LHhwBZeNK1VTsfTFUHCdrfp1bgwQ9xv5ski8PX9rL2dZXvgGDnw"),
+        ("m/0'/1", "xprv9wTYmMFdV23N2TdNG573QoEsfRrWKQgWeibmLntzniatZvR9BmLnvSxqu53Kw1UmYPxLgboyZQaXwTCg8MSY3H2EU4pWcQDnRnrVA1xe8fs", "xpub6ASuArnXKPbfEwhqN6e3mwBcDTgzisQN1wXN9BJcM47sSikHjJf3UFHKkNAWbWMiGj7Wf5uMash7SyYq527Hqck2AxYysAA7xmALppuCkwQ"),
+        ("m/0'/1/2'", "xprv9z4pot5VBttmtdRTWfWQmoH1taj2axGVzFqSb8C9xaxKymcFzXBDptWmT7FwuEzG3ryjH4ktypQSAewRiNMjANTtpgP4mLTj34bhnZX7UiM", "xpub6D4BDPcP2GT577Vvch3R8wDkScZWzQzMMUm3PWbmWvVJrZwQY4VUNgqFJPMM3No2dFDFGTsxxpG5uJh7n7epu4trkrX7x7DogT5Uv6fcLW5"),
+        ("m/0'/1/2'/2", "xprvA2JDeKCSNNZky6uBCviVfJSKyQ1mDYahRjijr5idH2WwLsEd4Hsb2Tyh8RfQMuPh7f7RtyzTtdrbdqqsunu5Mm3wDvUAKRHSC34sJ7in334", "xpub6FHa3pjLCk84BayeJxFW2SP4XRrFd1JYnxeLeU8EqN3vDfZmbqBqaGJAyiLjTAwm6ZLRQUMv1ZACTj37sR62cfN7fe5JnJ7dh8zL4fiyLHV"),
+        ("m/0'/1/2'/2/1000000000", "xprvA41z7zogVVwxVSgdKUHDy1SKmdb533PjDz7J6N6mV6uS3ze1ai8FHa8kmHScGpWmj4WggLyQjgPie1rFSruoUihUZREPSL39UNdE3BBDu76", "xpub6H1LXWLaKsWFhvm6RVpEL9P4KfRZSW7abD2ttkWP3SSQvnyA8FSVqNTEcYFgJS2UaFcxupHiYkro49S8yGasTvXEYBVPamhGW6cFJodrTHy"),
+    ];
+    let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+    let master = ExtendedPrivateKey::master(&seed, Network::Mainnet).unwrap();
+    assert_eq!(master.fingerprint(), [0x34, 0x42, 0x19, 0x3e]);
+    for (path, xprv, xpub) in VECTORS {
+        let key = master
+            .derive_path(&DerivationPath::parse(path).unwrap())
+            .unwrap();
+        assert_eq!(key.to_string(), xprv, "{}", path);
+        assert_eq!(key.to_public().to_string(), xpub, "{}", path);
+
+        let parsed: ExtendedPrivateKey = xprv.parse().unwrap();
+        assert_eq!(parsed.serialize(), key.serialize());
+        let parsed: ExtendedPublicKey = xpub.parse().unwrap();
+        assert_eq!(parsed, key.to_public());
+    }
+
+    // public derivation of the non hardened children
+    let parent: ExtendedPublicKey = VECTORS[3].2.parse().unwrap();
+    let child = parent
+        .derive_path(&DerivationPath::parse("2/1000000000").unwrap())
+        .unwrap();
+    assert_eq!(child.to_string(), VECTORS[5].2);
+    assert_eq!(
+        parent.derive_child(ChildNumber::hardened(0).unwrap()),
+        Err(bip32::Bip32Error::HardenedFromPublic(
+            ChildNumber::hardened(0).unwrap()
+        ))
+    );
+
+    // testnet keys from a BIP39 seed
+    #[cfg(feature = "english")]
+    {
+        const MNEMONICS: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let mnemonics = Mnemonics::<12>::from_string(&dictionary::ENGLISH, MNEMONICS).unwrap();
+        let seed = Seed::<64>::from_mnemonics(&dictionary::ENGLISH, &mnemonics, b"");
+        let master = ExtendedPrivateKey::master(seed.as_ref(), Network::Testnet).unwrap();
+        assert_eq!(master.fingerprint(), [0x73, 0xc5, 0xda, 0x0a]);
+        let account = master
+            .derive_path(&DerivationPath::parse("m/84h/1h/0h").unwrap())
+            .unwrap();
+        assert_eq!(account.to_string(), "tprv8fSjiqEQ8YG7Ro7gw2ScwcvweYuuWi1ZzGUtrPz918HvDtBzL5s2voFTrN4y3yUwj5cYD54pLhxk6NKCzHUjcka3zbKjbTEcsuAnkzbjhkL");
+        assert_eq!(account.to_public().to_string(), "tpubDC8msFGeGuwnKG9Upg7DM2b4DaRqg3CUZa5g8v2SRQ6K4NSkxUgd7HsL2XVWbVm39yBA4LAxysQAm397zwQSQoQgewGiYZqrA9DsP4zbQ1M");
+        assert!(!format!("{:?}", account).contains("tprv"));
+    }
+
+    // invalid encodings
+    let mut corrupted = String::from(VECTORS[0].1);
+    corrupted.replace_range(10..11, "a");
+    assert!(corrupted.parse::<ExtendedPrivateKey>().is_err());
+    assert!(VECTORS[0].2.parse::<ExtendedPrivateKey>().is_err());
+    assert!(VECTORS[0].1.parse::<ExtendedPublicKey>().is_err());
+}
+
+#[test]
+#[cfg(feature = "slip10")]
+fn slip10_vectors() {
+    use bip32::{ChildNumber, DerivationPath};
+    use slip10::{Curve, ExtendedKey, Slip10Error};
+
+    // SLIP-10 test vector 1 for each curve, and derivation retry for nist256p1
+    const VECTORS: [(&str, &str, &str, &str, &str, &str); 20] = [
+        (
+            "ed25519",
+            "m",
+            "00000000",
+            "90046a93de5380a72b5e45010748567d5ea02bbf6522f979e05c0d8d8ca9fffb",
+            "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7",
+            "00a4b2856bfec510abab89753fac1ac0e1112364e7d250545963f135f2a33188ed",
+        ),
+        (
+            "ed25519",
+            "m/0'",
+            "ddebc675",
+            "8b59aa11380b624e81507a27fedda59fea6d0b779a778918a2fd3590e16e9c69",
+            "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3",
+            "008c8a13df77a28f3445213a0f432fde644acaa215fc72dcdf300d5efaa85d350c",
+        ),
+        (
+            "ed25519",
+            "m/0'/1'",
+            "13dab143",
+            "a320425f77d1b5c2505a6b1b27382b37368ee640e3557c315416801243552f14",
+            "b1d0bad404bf35da785a64ca1ac54b2617211d2777696fbffaf208f746ae84f2",
+            "001932a5270f335bed617d5b935c80aedb1a35bd9fc1e31acafd5372c30f5c1187",
+        ),
+        (
+            "ed25519",
+            "m/0'/1'/2'",
+            "ebe4cb29",
+            "2e69929e00b5ab250f49c3fb1c12f252de4fed2c1db88387094a0f8c4c9ccd6c",
+            "92a5b23c0b8a99e37d07df3fb9966917f5d06e02ddbd909c7e184371463e9fc9",
+            "00ae98736566d30ed0e9d2f4486a64bc95740d89c7db33f52121f8ea8f76ff0fc1",
+        ),
+        (
+            "ed25519",
+            "m/0'/1'/2'/2'",
+            "316ec1c6",
+            "8f6d87f93d750e0efccda017d662a1b31a266e4a6f5993b15f5c1f07f74dd5cc",
+            "30d1dc7e5fc04c31219ab25a27ae00b50f6fd66622f6e9c913253d6511d1e662",
+            "008abae2d66361c879b900d204ad2cc4984fa2aa344dd7ddc46007329ac76c429c",
+        ),
+        (
+            "ed25519",
+            "m/0'/1'/2'/2'/1000000000'",
+            "d6322ccd",
+            "68789923a0cac2cd5a29172a475fe9e0fb14cd6adb5ad98a3fa70333e7afa230",
+            "8f94d394a8e8fd6b1bc2f3f49f5c47e385281d5c17e65324b0f62483e37e8793",
+            "003c24da049451555d51a7014a37337aa4e12d41e485abccfa46b47dfb2af54b7a",
+        ),
+        (
+            "nist256p1",
+            "m",
+            "00000000",
+            "beeb672fe4621673f722f38529c07392fecaa61015c80c34f29ce8b41b3cb6ea",
+            "612091aaa12e22dd2abef664f8a01a82cae99ad7441b7ef8110424915c268bc2",
+            "0266874dc6ade47b3ecd096745ca09bcd29638dd52c2c12117b11ed3e458cfa9e8",
+        ),
+        (
+            "nist256p1",
+            "m/0'",
+            "be6105b5",
+            "3460cea53e6a6bb5fb391eeef3237ffd8724bf0a40e94943c98b83825342ee11",
+            "6939694369114c67917a182c59ddb8cafc3004e63ca5d3b84403ba8613debc0c",
+            "0384610f5ecffe8fda089363a41f56a5c7ffc1d81b59a612d0d649b2d22355590c",
+        ),
+        (
+            "nist256p1",
+            "m/0'/1",
+            "9b02312f",
+            "4187afff1aafa8445010097fb99d23aee9f599450c7bd140b6826ac22ba21d0c",
+            "284e9d38d07d21e4e281b645089a94f4cf5a5a81369acf151a1c3a57f18b2129",
+            "03526c63f8d0b4bbbf9c80df553fe66742df4676b241dabefdef67733e070f6844",
+        ),
+        (
+            "nist256p1",
+            "m/0'/1/2'",
+            "b98005c1",
+            "98c7514f562e64e74170cc3cf304ee1ce54d6b6da4f880f313e8204c2a185318",
+            "694596e8a54f252c960eb771a3c41e7e32496d03b954aeb90f61635b8e092aa7",
+            "0359cf160040778a4b14c5f4d7b76e327ccc8c4a6086dd9451b7482b5a4972dda0",
+        ),
+        (
+            "nist256p1",
+            "m/0'/1/2'/2",
+            "0e9f3274",
+            "ba96f776a5c3907d7fd48bde5620ee374d4acfd540378476019eab70790c63a0",
+            "5996c37fd3dd2679039b23ed6f70b506c6b56b3cb5e424681fb0fa64caf82aaa",
+            "029f871f4cb9e1c97f9f4de9ccd0d4a2f2a171110c61178f84430062230833ff20",
+        ),
+        (
+            "nist256p1",
+            "m/0'/1/2'/2/1000000000",
+            "8b2b5c4b",
+            "b9b7b82d326bb9cb5b5b121066feea4eb93d5241103c9e7a18aad40f1dde8059",
+            "21c4f269ef0a5fd1badf47eeacebeeaa3de22eb8e5b0adcd0f27dd99d34d0119",
+            "02216cd26d31147f72427a453c443ed2cde8a1e53c9cc44e5ddf739725413fe3f4",
+        ),
+        (
+            "secp256k1",
+            "m",
+            "00000000",
+            "873dff81c02f525623fd1fe5167eac3a55a049de3d314bb42ee227ffed37d508",
+            "e8f32e723decf4051aefac8e2c93c9c5b214313817cdb01a1494b917c8436b35",
+            "0339a36013301597daef41fbe593a02cc513d0b55527ec2df1050e2e8ff49c85c2",
+        ),
+        (
+            "secp256k1",
+            "m/0'",
+            "3442193e",
+            "47fdacbd0f1097043b78c63c20c34ef4ed9a111d980047ad16282c7ae6236141",
+            "edb2e14f9ee77d26dd93b4ecede8d16ed408ce149b6cd80b0715a2d911a0afea",
+            "035a784662a4a20a65bf6aab9ae98a6c068a81c52e4b032c0fb5400c706cfccc56",
+        ),
+        (
+            "secp256k1",
+            "m/0'/1",
+            "5c1bd648",
+            "2a7857631386ba23dacac34180dd1983734e444fdbf774041578e9b6adb37c19",
+            "3c6cb8d0f6a264c91ea8b5030fadaa8e538b020f0a387421a12de9319dc93368",
+            "03501e454bf00751f24b1b489aa925215d66af2234e3891c3b21a52bedb3cd711c",
+        ),
+        (
+            "secp256k1",
+            "m/0'/1/2'",
+            "bef5a2f9",
+            "04466b9cc8e161e966409ca52986c584f07e9dc81f735db683c3ff6ec7b1503f",
+            "cbce0d719ecf7431d88e6a89fa1483e02e35092af60c042b1df2ff59fa424dca",
+            "0357bfe1e341d01c69fe5654309956cbea516822fba8a601743a012a7896ee8dc2",
+        ),
+        (
+            "secp256k1",
+            "m/0'/1/2'/2",
+            "ee7ab90c",
+            "cfb71883f01676f587d023cc53a35bc7f88f724b1f8c2892ac1275ac822a3edd",
+            "0f479245fb19a38a1954c5c7c0ebab2f9bdfd96a17563ef28a6a4b1a2a764ef4",
+            "02e8445082a72f29b75ca48748a914df60622a609cacfce8ed0e35804560741d29",
+        ),
+        (
+            "secp256k1",
+            "m/0'/1/2'/2/1000000000",
+            "d880d7d8",
+            "c783e67b921d2beb8f6b389cc646d7263b4145701dadd2161548a8b078e65e9e",
+            "471b76e389e528d6de6d816857e012c5455051cad6660850e58372a6c3e6e7c8",
+            "022a471424da5e657499d1ff51cb43c47481a03b1e77f951fe64cec9f5a48f7011",
+        ),
+        (
+            "nist256p1",
+            "m/28578'",
+            "be6105b5",
+            "e94c8ebe30c2250a14713212f6449b20f3329105ea15b652ca5bdfc68f6c65c2",
+            "06f0db126f023755d0b8d86d4591718a5210dd8d024e3e14b6159d63f53aa669",
+            "02519b5554a4872e8c9c1c847115363051ec43e93400e030ba3c36b52a3e70a5b7",
+        ),
+        (
+            "nist256p1",
+            "m/28578'/33941",
+            "3e2b7bc6",
+            "9e87fe95031f14736774cd82f25fd885065cb7c358c1edf813c72af535e83071",
+            "092154eed4af83e078ff9b84322015aefe5769e31270f62c3f66c33888335f3a",
+            "0235bfee614c0d5b2cae260000bb1d0d84b270099ad790022c1ae0b2e782efe120",
+        ),
+    ];
+    let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+    for (curve, path, fingerprint, chain_code, private, public) in VECTORS {
+        let curve = match curve {
+            "ed25519" => Curve::Ed25519,
+            "nist256p1" => Curve::Nist256p1,
+            _ => Curve::Secp256k1,
+        };
+        let key = ExtendedKey::master(curve, &seed)
+            .derive_path(&DerivationPath::parse(path).unwrap())
+            .unwrap();
+        assert_eq!(
+            hex::encode(key.parent_fingerprint()),
+            fingerprint,
+            "{}",
+            path
+        );
+        assert_eq!(hex::encode(key.chain_code()), chain_code, "{}", path);
+        assert_eq!(hex::encode(key.private_key()), private, "{}", path);
+        assert_eq!(hex::encode(key.public_key()), public, "{}", path);
+    }
+
+    // seed retry for nist256p1
+    let seed =
+        hex::decode("a7305bc8df8d0951f0cb224c0e95d7707cbdf2c6ce7e8d481fec69c7ff5e9446").unwrap();
+    let key = ExtendedKey::master(Curve::Nist256p1, &seed);
+    assert_eq!(
+        hex::encode(key.chain_code()),
+        "7762f9729fed06121fd13f326884c82f59aa95c57ac492ce8c9654e60efd130c"
+    );
+    assert_eq!(
+        hex::encode(key.private_key()),
+        "3b8c18469a4634517d6d0b65448f8e6c62091b45540a1743c5846be55d47d88f"
+    );
+
+    let normal = ChildNumber::normal(0).unwrap();
+    assert_eq!(key.derive_child(normal).map(|k| k.depth()), Ok(1));
+    assert_eq!(
+        ExtendedKey::master(Curve::Ed25519, &seed)
+            .derive_child(normal)
+            .map(|k| k.depth()),
+        Err(Slip10Error::HardenedOnly(normal))
+    );
+}
+
+#[test]
+#[cfg(all(feature = "bip85", feature = "english"))]
+fn bip85_vectors() {
+    use bip32::ExtendedPrivateKey;
+    use std::string::ToString;
+
+    // BIP85 test vectors
+    let root: ExtendedPrivateKey = "xprv9s21ZrQH143K2LBWUUQRFXhucrQqBpKdRRxNVq2zBqsx8HVqFk2uYo8kmbaLLHRdqtQpUm98uKfu3vca1LqdGhUtyoFnCNkfmXRyPXLjbKb".parse().unwrap();
+    assert_eq!(
+        hex::encode(bip85::derive_entropy(&root, &[0, 0]).unwrap()),
+        "efecfbccffea313214232d29e71563d941229afb4338c21f9517c41aaa0d16f00b83d2a09ef747e7a64e8e2bd5a14869e693da66ce94ac2da570ab7ee48618f7"
+    );
+    assert_eq!(
+        hex::encode(bip85::derive_entropy(&root, &[0, 1]).unwrap()),
+        "70c6e3e8ebee8dc4c0dbba66076819bb8c09672527c4277ca8729532ad711872218f826919f6b67218adde99018a6df9095ab2b58d803b5b93ec9802085a690e"
+    );
+
+    let dic = &dictionary::ENGLISH;
+    let entropy = bip85::mnemonics_entropy::<_, 16>(&root, dic, 0).unwrap();
+    assert_eq!(hex::encode(entropy.0), "6250b68daf746d12a24d58b4787a714b");
+    assert_eq!(
+        bip85::mnemonics::<_, 12>(&root, dic, 0)
+            .unwrap()
+            .display(dic)
+            .to_string(),
+        "girl mad pet galaxy egg matter matrix prison refuse sense ordinary nose"
+    );
+    assert_eq!(
+        bip85::mnemonics::<_, 18>(&root, dic, 0).unwrap().display(dic).to_string(),
+        "near account window bike charge season chef number sketch tomorrow excuse sniff circle vital hockey outdoor supply token"
+    );
+    assert_eq!(
+        bip85::mnemonics::<_, 24>(&root, dic, 0).unwrap().display(dic).to_string(),
+        "puppy ocean match cereal symbol another shed magic wrap hammer bulb intact gadget divorce twin tonight reason outdoor destroy simple truth cigar social volcano"
+    );
+    assert!(matches!(
+        bip85::mnemonics::<_, 15>(&root, dic, 0),
+        Err(bip85::Bip85Error::UnsupportedWords(15))
+    ));
+
+    assert_eq!(
+        hex::encode(bip85::hex::<64>(&root, 0).unwrap()),
+        "492db4698cf3b73a5a24998aa3e9d7fa96275d85724a91e71aa2d645442f878555d078fd1f1f67e368976f04137b1f7a0d19232136ca50c44614af72b5582a5c"
+    );
+    assert_eq!(
+        format!("{}", bip85::wif(&root, 0).unwrap()),
+        "Kzyv4uF39d4Jrw2W7UryTHwZr1zQVNk4dAFyqE6BuMrMh1Za7uhp"
+    );
+    assert_eq!(
+        bip85::password_base64(&root, 21, 0).unwrap().as_str(),
+        "dKLoepugzdVJvdL56ogNV"
+    );
+    assert_eq!(
+        bip85::password_base85(&root, 12, 0).unwrap().as_str(),
+        "_s`{TW89)i4`"
+    );
+    assert!(bip85::password_base64(&root, 19, 0).is_err());
+    assert!(bip85::password_base85(&root, 81, 0).is_err());
+}
+
+#[test]
+#[cfg(all(feature = "bip32", feature = "english"))]
+fn account_addresses() {
+    use account::{Account, Chain, Purpose};
+    use bip32::Network;
+    use std::string::ToString;
+
+    let mnemonics = Mnemonics::<12>::from_string(&dictionary::ENGLISH, "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about").unwrap();
+    let seed = Seed::<64>::from_mnemonics(&dictionary::ENGLISH, &mnemonics, b"");
+
+    // BIP44, BIP49, BIP84 and BIP86 test vectors (account 0)
+    let account =
+        |purpose, network| Account::from_seed(seed.as_ref(), purpose, network, 0).unwrap();
+    let bip44 = account(Purpose::Bip44, Network::Mainnet);
+    assert_eq!(
+        bip44.address(Chain::Receive, 0).unwrap().to_string(),
+        "1LqBGSKuX5yYUonjxT5qGfpUsXKYYWeabA"
+    );
+    let bip49 = account(Purpose::Bip49, Network::Testnet);
+    assert_eq!(
+        bip49.address(Chain::Receive, 0).unwrap().to_string(),
+        "2Mww8dCYPUpKHofjgcXcBCEGmniw9CoaiD2"
+    );
+    let bip84 = account(Purpose::Bip84, Network::Mainnet);
+    assert_eq!(bip84.slip132().to_string(), "zpub6rFR7y4Q2AijBEqTUquhVz398htDFrtymD9xYYfG1m4wAcvPhXNfE3EfH1r1ADqtfSdVCToUG868RvUUkgDKf31mGDtKsAYz2oz2AGutZYs");
+    assert_eq!(
+        bip84.address(Chain::Receive, 0).unwrap().to_string(),
+        "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu"
+    );
+    let bip86 = account(Purpose::Bip86, Network::Mainnet);
+    assert_eq!(bip86.xpub().to_string(), "xpub6BgBgsespWvERF3LHQu6CnqdvfEvtMcQjYrcRzx53QJjSxarj2afYWcLteoGVky7D3UKDP9QyrLprQ3VCECoY49yfdDEHGCtMMj92pReUsQ");
+    assert_eq!(
+        bip86.address(Chain::Receive, 0).unwrap().to_string(),
+        "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr"
+    );
+    // account 1 of all the purposes (computed with rust-bitcoin)
+    let vectors: [(Purpose, Network, &str, [&str; 3]); 8] = [
+        (Purpose::Bip44, Network::Mainnet, "xpub6BosfCnifzxcJJ1wYuntGJfF2zPJkDeG9ELNHcKNjezuea4tumswN9sH1psMdSVqCMoJC21Bv8usSeqSP4Sp1tLzW7aY59fGn9GCYzx5UTo", ["15qucUWKf95Fo58FdCBhUTSAtsm22HHE2Q", "1Gb9eQ8tqEd1dHQEU2JB5V6p7C4ivQyBDq", "1DgjtFUiXvqxGic9A9fiDPrHNyKC4cGtTH"]),
+        (Purpose::Bip44, Network::Testnet, "tpubDC5FSnBiZDMmkoat4aZFfbJdEthnPqJ1jXZcKWJNKC4yJanLA55dRW5qKJRRvAo1SwaXeUx2ayUQyVJ6eCbABbBB8Wn3T7dAuVJRnZgntVC", ["n2VQDkgibQ3S8wPVH25Mea3TcQgVFFQqab", "mmw3WhZTskREP6mWFrt1kZbBcCDQk2nK7y", "mfjSVUxjdp684Bc3LGsJWCPsfwTszcQ6gR"]),
+        (Purpose::Bip49, Network::Mainnet, "xpub6C6nQwHaWbSs31DwQnenydXNeCXUqJwa9s8dzkWDTZ8N93drzszUSFQyKN7rgxUu8Gs84k4fprimVGp9qjVjgGttXdddo3Lbt9AXT7ZMfkX", ["35eszW2wmZ4hn7hfG5LGqxw5xCPjZcEJPM", "323g91YR7r6FWKF8SegvykHdmy5S2AVTv2", "35gZZo6xPJEPgcz1cj1mTQHRMiPP97NGRY"]),
+        (Purpose::Bip49, Network::Testnet, "tpubDD7tXK8KeQ3YbrdciY1wchrsZJcoTyQPs5KCcf6WJzi32eMLeKgAQdsbUM3GVDtv7TjDYahiijScM9pGBo7HVUB6eRuMykN725Cyk5Y8UvK", ["2N3QbAwmmFko9xpTp9hsXiLQ8xSXNhUEoF3", "2N4X69V9EiBediuZ72DWPJiVZ6sZh6Tk6HZ", "2NDcK9tZ9xdkdi8umE2RH1i2sNegYp6P2Ad"]),
+        (Purpose::Bip84, Network::Mainnet, "xpub6CatWdiZiodmYVtWLtEQsAg1H9ooS1bmsJUBwQ83FE1Fyk386FWcyicJgEZv3quZSJKA5dh5Lo2PbubMGxCfZtRthV6ST2qquL9w3HSzcUn", ["bc1qku0qh0mc00y8tk0n65x2tqw4trlspak0fnjmfz", "bc1qx0tpa0ctsy5v8xewdkpf69hhtz5cw0rf5uvyj6", "bc1qt0x83f5vmnapgl2gjj9r3d67rcghvjaqrvgpck"]),
+        (Purpose::Bip84, Network::Testnet, "tpubDC8msFGeGuwnP2xwTZBBZSie1BLgRAkJhgzpFYTdpGgZNzguXQhNDVWp7mJbHJUjQQvV2myLU9dkx67a7VAUnzY7yT7nvhHj7FgS4oNivvq", ["tb1qp7shgcwx3mpzgxjvff0d77vuhchcldzfy60x6s", "tb1qynt29nsj8j972la4lu3efu42m5us2svmc8ekx8", "tb1qkvjfredfz59jwvqru7a2spvugqd7dlx6e4aqvm"]),
+        (Purpose::Bip86, Network::Mainnet, "xpub6BgBgsespWvEUBtu8NPpew4suu4JeuYz1ryQBqRKYk6BCN4p6nugJwXyBFjwPS93FTP4Rvkgqzhoy4ZysXh6f6jPWrjwbtG5PBzqPJghDkT", ["bc1pkq6ayylfpe5hn05550ry25pkakuf72x9qkjc2sl06dfcet8sg25q9y3j3y", "bc1pqfhqcv85tqvlzhxcdde06k5arfkyyz6tvf9x03v2mgy2w25qpmjstejna5", "bc1phkewflmppg0ntjqdv7688xmpqmasfw3wqn96vcuzqw4h6znz98ysxmc2wg"]),
+        (Purpose::Bip86, Network::Testnet, "tpubDDfvzhdVV4unxTr3yqNZkM2u5c8u9ecmsdWK6bFzZ4hE2tbjhSjhDa1dzsVymqrr19pZ43YK9c5CQwp7fcTNwHZg59Yt6Mdsu5myM4oUX42", ["tb1p7d86ep54kqwzyke26cz6uux398tmweaf0valsqtc3aukp22wvzsqnyh456", "tb1pvems8940kk3xmdyfsqvwcafv95x66d3s7k2u8zc5pd0xmfq7cskqz2xwf6", "tb1plgqqxh2etpy05k0nqc7js29swyeqqw8s7l5x92ecxzeacytv46zqq69ms7"]),
+    ];
+    for (purpose, network, xpub, addresses) in vectors {
+        let account = Account::from_seed(seed.as_ref(), purpose, network, 1).unwrap();
+        assert_eq!(account.xpub().to_string(), xpub);
+        let receive: std::vec::Vec<_> = account
+            .addresses(Chain::Receive, 2)
+            .map(|a| a.unwrap().to_string())
+            .collect();
+        assert_eq!(receive, addresses[0..2]);
+        let change = account.address(Chain::Change, 0).unwrap();
+        assert!(change == addresses[2]);
+        assert_eq!(
+            account.find_address(addresses[2], 2),
+            Some((Chain::Change, 0))
+        );
+    }
+
+    // descriptors, with checksums computed by the BIP380 reference implementation
+    assert_eq!(
+        bip84.descriptor(Chain::Receive).to_string(),
+        "wpkh([73c5da0a/84'/0'/0']xpub6CatWdiZiodmUeTDp8LT5or8nmbKNcuyvz7WyksVFkKB4RHwCD3XyuvPEbvqAQY3rAPshWcMLoP2fMFMKHPJ4ZeZXYVUhLv1VMrjPC7PW6V/0/*)#wc3n3van"
+    );
+    let account = Account::from_seed(seed.as_ref(), Purpose::Bip49, Network::Testnet, 1).unwrap();
+    assert_eq!(
+        account.descriptor(Chain::Change).to_string(),
+        "sh(wpkh([73c5da0a/49'/1'/1']tpubDD7tXK8KeQ3YbrdciY1wchrsZJcoTyQPs5KCcf6WJzi32eMLeKgAQdsbUM3GVDtv7TjDYahiijScM9pGBo7HVUB6eRuMykN725Cyk5Y8UvK/1/*))#vzmh7kgv"
+    );
+    assert_eq!(
+        account.slip132().to_string(),
+        "upub5EFU65HtV5TenAsQmGfynXEziH5oeivqa7AEuvMhq4pW84Ewp8LPQ1EMAh5ixzyv6dqtgaQAoeYv17Uxdr21DKfzyp4nYVFQcwRdpNRFGRm"
+    );
+
+    // a wrong passphrase gives a different wallet
+    let seed = Seed::<64>::from_mnemonics(&dictionary::ENGLISH, &mnemonics, b"TREZOR");
+    let account = Account::from_seed(seed.as_ref(), Purpose::Bip84, Network::Mainnet, 0).unwrap();
+    assert_eq!(
+        account.find_address("bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu", 20),
+        None
+    );
+    assert_eq!(
+        account.address(Chain::Receive, 1 << 31),
+        Err(bip32::Bip32Error::InvalidIndex(1 << 31))
+    );
+    assert_eq!(
+        Account::from_seed(seed.as_ref(), Purpose::Bip84, Network::Mainnet, 1 << 31),
+        Err(bip32::Bip32Error::InvalidIndex(1 << 31))
+    );
+}
+
+#[test]
+#[cfg(feature = "slip39")]
+fn slip39_vectors() {
+    use slip39::{Share, Slip39Error};
+
+    fn combine<const N: usize>(phrases: &[&str]) -> Result<String, Slip39Error> {
+        let mut shares = std::vec::Vec::new();
+        for phrase in phrases {
+            let share = Share::<N>::from_phrase(phrase)?;
+            // phrases are rendered back identically
+            assert_eq!(format!("{}", share), *phrase);
+            shares.push(share);
+        }
+        slip39::combine(&shares, b"TREZOR").map(|e| hex::encode(e.0))
+    }
+
+    // SLIP-39 test vectors, with the passphrase "TREZOR"
+    let vectors: [(&[&str], Result<&str, Slip39Error>); 20] = [
+        // valid mnemonic without sharing (128 bits)
+        (
+            &["duckling enlarge academic academic agency result length solution fridge kidney coal piece deal husband erode duke ajar critical decision keyboard"],
+            Ok("bb54aac4b89dc868ba37d9cc21b2cece"),
+        ),
+        // mnemonic with invalid checksum (128 bits)
+        (
+            &["duckling enlarge academic academic agency result length solution fridge kidney coal piece deal husband erode duke ajar critical decision kidney"],
+            Err(Slip39Error::InvalidChecksum),
+        ),
+        // mnemonic with invalid padding (128 bits)
+        (
+            &["duckling enlarge academic academic email result length solution fridge kidney coal piece deal husband erode duke ajar music cargo fitness"],
+            Err(Slip39Error::InvalidPadding),
+        ),
+        // basic sharing 2-of-3 (128 bits)
+        (
+            &[
+                "shadow pistol academic always adequate wildlife fancy gross oasis cylinder mustang wrist rescue view short owner flip making coding armed",
+                "shadow pistol academic acid actress prayer class unknown daughter sweater depict flip twice unkind craft early superior advocate guest smoking",
+            ],
+            Ok("b43ceb7e57a0ea8766221624d01b0864"),
+        ),
+        // basic sharing 2-of-3, with only one share (128 bits)
+        (
+            &["shadow pistol academic always adequate wildlife fancy gross oasis cylinder mustang wrist rescue view short owner flip making coding armed"],
+            Err(Slip39Error::WrongShareCount(0)),
+        ),
+        // mnemonics with different identifiers (128 bits)
+        (
+            &[
+                "adequate smoking academic acid debut wine petition glen cluster slow rhyme slow simple epidemic rumor junk tracks treat olympic tolerate",
+                "adequate stay academic agency agency formal party ting frequent learn upstairs remember smear leaf damage anatomy ladle market hush corner",
+            ],
+            Err(Slip39Error::MismatchedShares),
+        ),
+        // mnemonics with different iteration exponents (128 bits)
+        (
+            &[
+                "peasant leaves academic acid desert exact olympic math alive axle trial tackle drug deny decent smear dominant desert bucket remind",
+                "peasant leader academic agency cultural blessing percent network envelope medal junk primary human pumps jacket fragment payroll ticket evoke voice",
+            ],
+            Err(Slip39Error::MismatchedShares),
+        ),
+        // mnemonics with mismatching group thresholds (128 bits)
+        (
+            &[
+                "liberty category beard email beyond should fancy romp founder easel pink holy hairy romp loyalty material victim owner toxic custody",
+                "liberty category academic easy being hazard crush diminish oral lizard reaction cluster force dilemma deploy force club veteran expect photo",
+            ],
+            Err(Slip39Error::MismatchedShares),
+        ),
+        // mnemonics with mismatching group counts (128 bits)
+        (
+            &[
+                "average senior academic leaf broken teacher expect surface hour capture obesity desire negative dynamic dominant pistol mineral mailman iris aide",
+                "average senior academic agency curious pants blimp spew clothes slice script dress wrap firm shaft regular slavery negative theater roster",
+            ],
+            Err(Slip39Error::MismatchedShares),
+        ),
+        // mnemonics with greater group threshold than group counts (128 bits)
+        (
+            &[
+                "music husband acrobat acid artist finance center either graduate swimming object bike medical clothes station aspect spider maiden bulb welcome",
+                "music husband acrobat agency advance hunting bike corner density careful material civil evil tactics remind hawk discuss hobo voice rainbow",
+                "music husband beard academic black tricycle clock mayor estimate level photo episode exclude ecology papa source amazing salt verify divorce",
+            ],
+            Err(Slip39Error::InvalidGroupThreshold),
+        ),
+        // mnemonics with duplicate member indices (128 bits)
+        (
+            &[
+                "device stay academic always dive coal antenna adult black exceed stadium herald advance soldier busy dryer daughter evaluate minister laser",
+                "device stay academic always dwarf afraid robin gravity crunch adjust soul branch walnut coastal dream costume scholar mortgage mountain pumps",
+            ],
+            Err(Slip39Error::DuplicateShare(0)),
+        ),
+        // mnemonics with mismatching member thresholds (128 bits)
+        (
+            &[
+                "hour painting academic academic device formal evoke guitar random modern justice filter withdraw trouble identify mailman insect general cover oven",
+                "hour painting academic agency artist again daisy capital beaver fiber much enjoy suitable symbolic identify photo editor romp float echo",
+            ],
+            Err(Slip39Error::MismatchedShares),
+        ),
+        // mnemonics giving an invalid digest (128 bits)
+        (
+            &[
+                "guilt walnut academic acid deliver remove equip listen vampire tactics nylon rhythm failure husband fatigue alive blind enemy teaspoon rebound",
+                "guilt walnut academic agency brave hamster hobo declare herd taste alpha slim criminal mild arcade formal romp branch pink ambition",
+            ],
+            Err(Slip39Error::InvalidDigest),
+        ),
+        // insufficient number of groups (128 bits, case 1)
+        (
+            &["eraser senior beard romp adorn nuclear spill corner cradle style ancient family general leader ambition exchange unusual garlic promise voice"],
+            Err(Slip39Error::NotEnoughGroups),
+        ),
+        // insufficient number of groups (128 bits, case 2)
+        (
+            &[
+                "eraser senior ceramic snake clay various huge numb argue hesitate auction category timber browser greatest hanger petition script leaf pickup",
+                "eraser senior ceramic shaft dynamic become junior wrist silver peasant force math alto coal amazing segment yelp velvet image paces",
+                "eraser senior ceramic round column hawk trust auction smug shame alive greatest sheriff living perfect corner chest sled fumes adequate",
+            ],
+            Err(Slip39Error::NotEnoughGroups),
+        ),
+        // threshold number of groups, but insufficient number of members in one group (128 bits)
+        (
+            &[
+                "eraser senior decision smug corner ruin rescue cubic angel tackle skin skunk program roster trash rumor slush angel flea amazing",
+                "eraser senior beard romp adorn nuclear spill corner cradle style ancient family general leader ambition exchange unusual garlic promise voice",
+            ],
+            Err(Slip39Error::WrongShareCount(3)),
+        ),
+        // threshold number of groups and members in each group (128 bits)
+        (
+            &[
+                "eraser senior ceramic snake clay various huge numb argue hesitate auction category timber browser greatest hanger petition script leaf pickup",
+                "eraser senior ceramic shaft dynamic become junior wrist silver peasant force math alto coal amazing segment yelp velvet image paces",
+                "eraser senior ceramic round column hawk trust auction smug shame alive greatest sheriff living perfect corner chest sled fumes adequate",
+                "eraser senior beard romp adorn nuclear spill corner cradle style ancient family general leader ambition exchange unusual garlic promise voice",
+            ],
+            Ok("7c3397a292a5941682d7a4ae2d898d11"),
+        ),
+        // valid mnemonics which can detect some errors in modular arithmetic
+        (
+            &[
+                "herald flea academic cage avoid space trend estate dryer hairy evoke eyebrow improve airline artwork garlic premium duration prevent oven",
+                "herald flea academic client blue skunk class goat luxury deny presence impulse graduate clay join blanket bulge survive dish necklace",
+                "herald flea academic acne advance fused brother frozen broken game ranked ajar already believe check install theory angry exercise adult",
+            ],
+            Ok("ad6f2ad8b59bbbaa01369b9006208d9a"),
+        ),
+        // valid extendable mnemonic without sharing (128 bits)
+        (
+            &["testify swimming academic academic column loyalty smear include exotic bedroom exotic wrist lobe cover grief golden smart junior estimate learn"],
+            Ok("1679b4516e0ee5954351d288a838f45e"),
+        ),
+        // extendable basic sharing 2-of-3, with only one share (128 bits)
+        (
+            &["enemy favorite academic acid cowboy phrase havoc level response walnut budget painting inside trash adjust froth kitchen learn tidy punish"],
+            Err(Slip39Error::WrongShareCount(0)),
+        ),
+    ];
+    // the errors are compared through Debug, as Slip39Error doesn't implement PartialEq
+    for (phrases, expected) in vectors {
+        assert_eq!(
+            format!("{:?}", combine::<16>(phrases)),
+            format!("{:?}", expected.map(String::from)),
+            "{:?}",
+            phrases
+        );
+    }
+
+    let vectors: [(&[&str], Result<&str, Slip39Error>); 6] = [
+        // valid mnemonic without sharing (256 bits)
+        (
+            &["theory painting academic academic armed sweater year military elder discuss acne wildlife boring employer fused large satoshi bundle carbon diagnose anatomy hamster leaves tracks paces beyond phantom capital marvel lips brave detect luck"],
+            Ok("989baf9dcaad5b10ca33dfd8cc75e42477025dce88ae83e75a230086a0e00e92"),
+        ),
+        // mnemonic with invalid checksum (256 bits)
+        (
+            &["theory painting academic academic armed sweater year military elder discuss acne wildlife boring employer fused large satoshi bundle carbon diagnose anatomy hamster leaves tracks paces beyond phantom capital marvel lips brave detect lunar"],
+            Err(Slip39Error::InvalidChecksum),
+        ),
+        // basic sharing 2-of-3 (256 bits)
+        (
+            &[
+                "humidity disease academic always aluminum jewelry energy woman receiver strategy amuse duckling lying evidence network walnut tactics forget hairy rebound impulse brother survive clothes stadium mailman rival ocean reward venture always armed unwrap",
+                "humidity disease academic agency actress jacket gross physics cylinder solution fake mortgage benefit public busy prepare sharp friar change work slow purchase ruler again tricycle involve viral wireless mixture anatomy desert cargo upgrade",
+            ],
+            Ok("c938b319067687e990e05e0da0ecce1278f75ff58d9853f19dcaeed5de104aae"),
+        ),
+        // basic sharing 2-of-3, with only one share (256 bits)
+        (
+            &["humidity disease academic always aluminum jewelry energy woman receiver strategy amuse duckling lying evidence network walnut tactics forget hairy rebound impulse brother survive clothes stadium mailman rival ocean reward venture always armed unwrap"],
+            Err(Slip39Error::WrongShareCount(0)),
+        ),
+        // valid extendable mnemonic without sharing (256 bits)
+        (
+            &["impulse calcium academic academic alcohol sugar lyrics pajamas column facility finance tension extend space birthday rainbow swimming purple syndrome facility trial warn duration snapshot shadow hormone rhyme public spine counter easy hawk album"],
+            Ok("8340611602fe91af634a5f4608377b5235fa2d757c51d720c0c7656249a3035f"),
+        ),
+        // extendable basic sharing 2-of-3 (256 bits)
+        (
+            &[
+                "western apart academic always artist resident briefing sugar woman oven coding club ajar merit pecan answer prisoner artist fraction amount desktop mild false necklace muscle photo wealthy alpha category unwrap spew losing making",
+                "western apart academic acid answer ancient auction flip image penalty oasis beaver multiple thunder problem switch alive heat inherit superior teaspoon explain blanket pencil numb lend punish endless aunt garlic humidity kidney observe",
+            ],
+            Ok("8dc652d6d6cd370d8c963141f6d79ba440300f25c467302c1d966bff8f62300d"),
+        ),
+    ];
+    for (phrases, expected) in vectors {
+        assert_eq!(
+            format!("{:?}", combine::<32>(phrases)),
+            format!("{:?}", expected.map(String::from)),
+            "{:?}",
+            phrases
+        );
+    }
+
+    // shares from unrelated sets, no shares, and a 128 bits share as a 256 bits share
+    assert!(matches!(
+        combine::<16>(&[
+            "duckling enlarge academic academic agency result length solution fridge kidney coal piece deal husband erode duke ajar critical decision keyboard",
+            "shadow pistol academic always adequate wildlife fancy gross oasis cylinder mustang wrist rescue view short owner flip making coding armed",
+        ]),
+        Err(Slip39Error::MismatchedShares)
+    ));
+    assert!(matches!(combine::<16>(&[]), Err(Slip39Error::NoShares)));
+    assert!(matches!(
+        combine::<32>(&["duckling enlarge academic academic agency result length solution fridge kidney coal piece deal husband erode duke ajar critical decision keyboard"]),
+        Err(Slip39Error::InvalidWordCount(20))
+    ));
+}
+
+#[test]
+#[cfg(all(feature = "slip39", feature = "rand"))]
+fn slip39_split() {
+    use slip39::{Scheme, Share, Slip39Error};
+
+    let mut rng = StepRng(11);
+
+    // 2 groups out of: 1 of 1, 2 of 3, 3 of 5
+    let entropy = Entropy::<32>(core::array::from_fn(|i| i as u8));
+    let scheme = Scheme::new(2).group(1, 1).group(2, 3).group(3, 5);
+    let mut shares = std::vec::Vec::new();
+    scheme
+        .split_with(&entropy, b"passphrase", &mut rng, |share| {
+            shares.push(share)
+        })
+        .unwrap();
+    assert_eq!(shares.len(), 9);
+    #[cfg(feature = "alloc")]
+    {
+        let groups = scheme.split(&entropy, b"passphrase", &mut rng).unwrap();
+        let sizes: std::vec::Vec<_> = groups.iter().map(|g| g.len()).collect();
+        assert_eq!(sizes, [1, 3, 5]);
+    }
+    let phrase = format!("{}", shares[4]);
+    assert_eq!(phrase.split(' ').count(), Share::<32>::WORD_COUNT);
+    assert!(Share::<32>::from_phrase(&phrase).unwrap() == shares[4]);
+
+    let pick = |indices: &[usize]| -> std::vec::Vec<Share<32>> {
+        indices.iter().map(|i| shares[*i].clone()).collect()
+    };
+    assert_eq!(
+        slip39::combine(&pick(&[0, 2, 3]), b"passphrase").unwrap(),
+        entropy
+    );
+    assert_eq!(
+        slip39::combine(&pick(&[8, 1, 6, 2, 4]), b"passphrase").unwrap(),
+        entropy
+    );
+    // duplicated shares are ignored
+    assert_eq!(
+        slip39::combine(&pick(&[0, 0, 2, 3]), b"passphrase").unwrap(),
+        entropy
+    );
+    // another passphrase gives another secret
+    assert!(slip39::combine(&pick(&[0, 2, 3]), b"Passphrase").unwrap() != entropy);
+
+    assert!(matches!(
+        slip39::combine(&pick(&[0, 2]), b"passphrase"),
+        Err(Slip39Error::WrongShareCount(1))
+    ));
+    assert!(matches!(
+        slip39::combine(&pick(&[2, 3]), b"passphrase"),
+        Err(Slip39Error::NotEnoughGroups)
+    ));
+    assert!(matches!(
+        slip39::combine(&pick(&[0, 2, 3, 4, 5, 6]), b"passphrase"),
+        Err(Slip39Error::TooManyGroups)
+    ));
+    assert!(matches!(
+        slip39::combine::<32>(&[], b"passphrase"),
+        Err(Slip39Error::NoShares)
+    ));
+    assert!(matches!(
+        slip39::combine(&pick(&[0, 2, 3]), b"caf\xc3\xa9"),
+        Err(Slip39Error::InvalidPassphrase)
+    ));
+
+    // a share of another split
+    let mut other = std::vec::Vec::new();
+    Scheme::new(1)
+        .group(1, 1)
+        .split_with(&entropy, b"", &mut rng, |share| other.push(share))
+        .unwrap();
+    let mut mixed = pick(&[2, 3]);
+    mixed.push(other.remove(0));
+    assert!(matches!(
+        slip39::combine(&mixed, b""),
+        Err(Slip39Error::MismatchedShares)
+    ));
+
+    // invalid schemes
+    let mut split = |scheme: Scheme| scheme.split_with(&entropy, b"", &mut rng, |_| ());
+    assert!(matches!(
+        split(Scheme::new(1)),
+        Err(Slip39Error::InvalidGroupThreshold)
+    ));
+    assert!(matches!(
+        split(Scheme::new(2).group(1, 1)),
+        Err(Slip39Error::InvalidGroupThreshold)
+    ));
+    assert!(matches!(
+        split(Scheme::new(1).group(1, 2)),
+        Err(Slip39Error::InvalidMemberThreshold(0))
+    ));
+    assert!(matches!(
+        split(Scheme::new(1).group(2, 17)),
+        Err(Slip39Error::InvalidMemberThreshold(0))
+    ));
+    assert!(matches!(
+        split(Scheme::new(1).group(1, 1).iteration_exponent(16)),
+        Err(Slip39Error::InvalidIterationExponent(16))
+    ));
+    assert!(matches!(
+        Scheme::new(1)
+            .group(1, 1)
+            .split_with(&Entropy([0; 15]), b"", &mut rng, |_| ()),
+        Err(Slip39Error::InvalidLength(15))
+    ));
+
+    // a failing random generator
+    assert!(matches!(
+        scheme.split_with(&entropy, b"", &mut CounterRng(0xff), |_| ()),
+        Err(Slip39Error::Rng(_))
+    ));
+    // failing after the identifier and the group secrets, when splitting the
+    // second group: only the share of the first group was given
+    let mut count = 0;
+    assert!(matches!(
+        scheme.split_with(&entropy, b"", &mut CounterRng(0xff - 2 - 32), |_| count +=
+            1),
+        Err(Slip39Error::Rng(_))
+    ));
+    assert_eq!(count, 1);
+}
+
+#[test]
+#[cfg(all(feature = "shamir", feature = "english", feature = "rand"))]
+fn shamir_shares() {
+    use shamir::{Share, ShareError, ShareMnemonics};
+
+    let mut rng = StepRng(11);
+
+    let entropy = Entropy::<20>(core::array::from_fn(|i| i as u8 * 7));
+    let mut shares = std::vec::Vec::new();
+    shamir::split_with(&entropy, 3, 5, &mut rng, |share| shares.push(share)).unwrap();
+    assert_eq!(shares.len(), 5);
+    assert!(shares.iter().all(|s| s.threshold() == 3));
+    #[cfg(feature = "alloc")]
+    assert_eq!(shamir::split(&entropy, 3, 5, &mut rng).unwrap().len(), 5);
+
+    // shares of 20 bytes in 1 + 16 words with 16 bits of checksum
+    let mut phrases = std::vec::Vec::new();
+    for share in &shares {
+        let mnemonics = share.to_mnemonics::<16, 16>().unwrap();
+        let phrase = format!("{}", mnemonics.display(&dictionary::ENGLISH));
+        assert_eq!(phrase.split(' ').count(), 17);
+        let parsed = ShareMnemonics::<16>::from_string(&dictionary::ENGLISH, &phrase).unwrap();
+        assert_eq!(parsed, mnemonics);
+        assert_eq!(
+            Share::<20>::from_mnemonics::<16, 16>(&parsed).as_ref().ok(),
+            Some(share)
+        );
+        phrases.push(phrase);
+    }
+
+    let pick = |indices: &[usize]| -> std::vec::Vec<Share<20>> {
+        indices.iter().map(|i| shares[*i].clone()).collect()
+    };
+    let standard = entropy.to_mnemonics::<15, 5>().unwrap();
+    for indices in [&[0, 1, 2][..], &[4, 2, 0], &[1, 3, 4, 0], &[3, 3, 1, 2]] {
+        let (recovered, mnemonics) = shamir::combine::<20, 15>(&pick(indices)).unwrap();
+        assert_eq!(recovered, entropy);
+        assert_eq!(mnemonics, standard);
+    }
+
+    assert!(matches!(
+        shamir::combine::<20, 15>(&pick(&[0, 1])),
+        Err(ShareError::NotEnoughShares)
+    ));
+    assert!(matches!(
+        shamir::combine::<20, 15>(&[]),
+        Err(ShareError::NotEnoughShares)
+    ));
+
+    // shares of another split
+    let mut others = std::vec::Vec::new();
+    shamir::split_with(&entropy, 3, 5, &mut rng, |share| others.push(share)).unwrap();
+    let mut mixed = pick(&[0, 1]);
+    mixed.push(others.remove(4));
+    assert!(matches!(
+        shamir::combine::<20, 15>(&mixed),
+        Err(ShareError::InvalidDigest)
+    ));
+    let mut mixed = pick(&[0, 1, 2]);
+    mixed.push(others.remove(3));
+    assert!(matches!(
+        shamir::combine::<20, 15>(&mixed),
+        Err(ShareError::InconsistentShare(3))
+    ));
+    let mut mixed = pick(&[0, 1, 2]);
+    mixed.push(others.remove(0));
+    assert!(matches!(
+        shamir::combine::<20, 15>(&mixed),
+        Err(ShareError::DuplicateShare(0))
+    ));
+    let mut mixed = pick(&[0, 1]);
+    shamir::split_with(&entropy, 2, 3, &mut rng, |share| {
+        if share.index() == 2 {
+            mixed.push(share)
+        }
+    })
+    .unwrap();
+    assert!(matches!(
+        shamir::combine::<20, 15>(&mixed),
+        Err(ShareError::MismatchedThreshold)
+    ));
+
+    // the header word is bound to the share
+    let mut words: std::vec::Vec<&str> = phrases[1].split(' ').collect();
+    let header = phrases[2].split(' ').next().unwrap();
+    words[0] = header;
+    let swapped = words.join(" ");
+    let parsed = ShareMnemonics::<16>::from_string(&dictionary::ENGLISH, &swapped).unwrap();
+    assert!(matches!(
+        Share::<20>::from_mnemonics::<16, 16>(&parsed),
+        Err(ShareError::InvalidHeader)
+    ));
+    assert!(matches!(
+        ShareMnemonics::<15>::from_string(&dictionary::ENGLISH, &phrases[0]),
+        Err(MnemonicError::InvalidWords {
+            expected_words: 16,
+            got_words: 17
+        })
+    ));
+    let invalid = phrases[0].replacen(' ', " notaword ", 1);
+    let invalid: std::vec::Vec<&str> = invalid.split(' ').take(17).collect();
+    assert!(matches!(
+        ShareMnemonics::<16>::from_string(&dictionary::ENGLISH, &invalid.join(" ")),
+        Err(MnemonicError::WordError { index: 1, .. })
+    ));
+
+    // invalid parameters
+    assert!(matches!(
+        shamir::split_with(&entropy, 0, 5, &mut rng, |_| ()),
+        Err(ShareError::InvalidThreshold)
+    ));
+    assert!(matches!(
+        shamir::split_with(&entropy, 3, 2, &mut rng, |_| ()),
+        Err(ShareError::InvalidThreshold)
+    ));
+    assert!(matches!(
+        shamir::split_with(&entropy, 3, 17, &mut rng, |_| ()),
+        Err(ShareError::InvalidThreshold)
+    ));
+    assert!(matches!(
+        shamir::split_with(&Entropy([0; 15]), 1, 1, &mut rng, |_| ()),
+        Err(ShareError::InvalidLength(15))
+    ));
+
+    // a failing random generator gives no share
+    let mut count = 0;
+    assert!(matches!(
+        shamir::split_with(&entropy, 3, 5, &mut CounterRng(0xff), |_| count += 1),
+        Err(ShareError::Rng(_))
+    ));
+    assert_eq!(count, 0);
+}
+
+#[test]
+#[cfg(all(feature = "seed_xor", feature = "english"))]
+fn seed_xor_parts() {
+    use seed_xor::SeedXorError;
+
+    let parse = |phrase: &str| Mnemonics::<24>::from_string(&dictionary::ENGLISH, phrase).unwrap();
+    // Coldcard documentation vector
+    let parts = [
+        parse("romance wink lottery autumn shop bring dawn tongue range crater truth ability miss spice fitness easy legal release recall obey exchange recycle dragon room"),
+        parse("lion misery divide hurry latin fluid camp advance illegal lab pyramid unaware eager fringe sick camera series noodle toy crowd jeans select depth lounge"),
+        parse("vault nominee cradle silk own frown throw leg cactus recall talent worry gadget surface shy planet purpose coffee drip few seven term squeeze educate"),
+    ];
+    let expected = parse("silent toe meat possible chair blossom wait occur this worth option bag nurse find fish scene bench asthma bike wage world quit primary indoor");
+    assert_eq!(seed_xor::combine_mnemonics(&parts).unwrap(), expected);
+
+    let entropies: std::vec::Vec<Entropy<32>> = parts
+        .iter()
+        .map(|p| Entropy::from_mnemonics::<24, 8>(p).unwrap())
+        .collect();
+    assert_eq!(
+        seed_xor::combine(&entropies).unwrap(),
+        Entropy::from_mnemonics::<24, 8>(&expected).unwrap()
+    );
+
+    // a part with an invalid checksum
+    let invalid = Mnemonics::<24>::from_string(
+        &dictionary::ENGLISH,
+        &format!("{} abandon", "zoo ".repeat(22) + "zoo"),
+    )
+    .unwrap();
+    assert!(matches!(
+        seed_xor::combine_mnemonics(&[parts[0].clone(), invalid]),
+        Err(SeedXorError::Entropy(EntropyError::ChecksumInvalid))
+    ));
+    assert!(matches!(
+        seed_xor::combine_mnemonics::<24>(&[]),
+        Err(SeedXorError::InvalidParts(0))
+    ));
+}
+
+#[test]
+#[cfg(all(feature = "seed_xor", feature = "rand"))]
+fn seed_xor_split() {
+    use seed_xor::SeedXorError;
+
+    let mut rng = StepRng(11);
+
+    // every part is a valid standard phrase
+    let original = Entropy::<16>([0x5a; 16]).to_mnemonics::<12, 4>().unwrap();
+    let mut split = std::vec::Vec::new();
+    seed_xor::split_mnemonics_with(&original, 4, &mut rng, |part| split.push(part)).unwrap();
+    assert_eq!(split.len(), 4);
+    for part in &split {
+        assert!(part.check_standard_checksum().is_ok());
+        assert!(*part != original);
+    }
+    assert_eq!(seed_xor::combine_mnemonics(&split).unwrap(), original);
+    assert!(seed_xor::combine_mnemonics(&split[1..]).unwrap() != original);
+    #[cfg(feature = "alloc")]
+    {
+        let split = seed_xor::split_mnemonics(&original, 2, &mut rng).unwrap();
+        assert_eq!(seed_xor::combine_mnemonics(&split).unwrap(), original);
+        let entropy = Entropy::<24>([0x33; 24]);
+        let split = seed_xor::split(&entropy, 3, &mut rng).unwrap();
+        assert_eq!(seed_xor::combine(&split).unwrap(), entropy);
+    }
+
+    assert!(matches!(
+        seed_xor::split_mnemonics_with(&original, 1, &mut rng, |_| ()),
+        Err(SeedXorError::InvalidParts(1))
+    ));
+    let fifteen = Entropy::<20>([0; 20]).to_mnemonics::<15, 5>().unwrap();
+    assert!(matches!(
+        seed_xor::split_mnemonics_with(&fifteen, 2, &mut rng, |_| ()),
+        Err(SeedXorError::InvalidWordCount(15))
+    ));
+    assert!(matches!(
+        seed_xor::split_with(&Entropy([0; 20]), 2, &mut rng, |_| ()),
+        Err(SeedXorError::InvalidLength(20))
+    ));
+
+    // a failing random generator, at the first or the second part
+    assert!(matches!(
+        seed_xor::split_with(&Entropy([0; 16]), 3, &mut CounterRng(0xff), |_| ()),
+        Err(SeedXorError::Rng(_))
+    ));
+    let mut count = 0;
+    assert!(matches!(
+        seed_xor::split_mnemonics_with(&original, 3, &mut CounterRng(0xff - 16), |_| count += 1),
+        Err(SeedXorError::Rng(_))
+    ));
+    assert_eq!(count, 1);
+}
+
+#[test]
+#[cfg(all(feature = "electrum", feature = "english"))]
+fn electrum_seeds() {
+    use electrum::SeedType;
+
+    let dic = &dictionary::ENGLISH;
+    // Electrum test vectors
+    let phrase =
+        "wild father tree among universe such mobile favorite target dynamic credit identify";
+    let mnemonics = Mnemonics::<12>::from_string(dic, phrase).unwrap();
+    assert_eq!(electrum::seed_type(dic, &mnemonics), Some(SeedType::Segwit));
+    assert!(electrum::is_seed_type(dic, &mnemonics, SeedType::Segwit));
+    assert!(!electrum::is_seed_type(dic, &mnemonics, SeedType::Standard));
+    assert_eq!(
+        hex::encode(electrum::seed(dic, &mnemonics, b"")),
+        "aac2a6302e48577ab4b46f23dbae0774e2e62c796f797d0a1b5faeb528301e3064342dafb79069e7c4c6b8c38ae11d7a973bec0d4f70626f8cc5184a8d0b0756"
+    );
+    // the passphrase is normalized to lowercase by Electrum
+    assert_eq!(
+        hex::encode(electrum::seed(
+            dic,
+            &mnemonics,
+            b"did you ever hear the tragedy of darth plagueis the wise?"
+        )),
+        "4aa29f2aeb0127efb55138ab9e7be83b36750358751906f86c662b21a1ea1370f949e6d1a12fa56d3d93cadda93038c76ac8118597364e46f5156fde6183c82f"
+    );
+
+    // a BIP39 phrase is not an Electrum seed
+    let bip39 = Mnemonics::<12>::from_string(dic, "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about").unwrap();
+    assert_eq!(electrum::seed_type(dic, &bip39), None);
+}
+
+#[test]
+#[cfg(feature = "electrum")]
+fn electrum_v1() {
+    use electrum::{V1Error, V1Mnemonics, V1_WORDLIST};
+
+    // x < 1626 is encoded as 3 times the word of index x
+    for x in 0u32..1626 {
+        let mut bytes = [0u8; 16];
+        bytes[..4].copy_from_slice(&x.to_be_bytes());
+        let mnemonics = V1Mnemonics::<12>::from_bytes(&bytes).unwrap();
+        assert!(mnemonics.indices().take(3).all(|i| i.0 == x as u16));
+        let parsed = V1Mnemonics::<12>::from_string(&format!("{}", mnemonics)).unwrap();
+        assert_eq!(parsed.to_bytes(), Ok(bytes));
+    }
+
+    let phrase = "hardly point goal hallway patience key stone difference ready caught listen fact";
+    let seed = hex::decode("8edad31a95e7d59f8837667510d75a4d").unwrap();
+    let mnemonics = V1Mnemonics::<12>::from_string(phrase).unwrap();
+    assert_eq!(mnemonics.to_bytes::<16>().unwrap()[..], seed[..]);
+    let encoded = V1Mnemonics::<12>::from_bytes::<16>(&seed.try_into().unwrap()).unwrap();
+    assert_eq!(encoded, mnemonics);
+    assert_eq!(format!("{}", encoded), phrase);
+
+    // the wordlist has 1626 words
+    assert_eq!(V1_WORDLIST.size(), 1626);
+    assert_eq!(
+        V1_WORDLIST.lookup_mnemonic("hardly").unwrap(),
+        *mnemonics.indices().next().unwrap()
+    );
+    assert_eq!(V1_WORDLIST.lookup_word(MnemonicIndex(0)), Some("like"));
+    assert_eq!(V1_WORDLIST.lookup_word(MnemonicIndex(1625)), Some("weary"));
+    assert_eq!(V1_WORDLIST.lookup_word(MnemonicIndex(1626)), None);
+    assert_eq!(V1_WORDLIST.lookup_word(MnemonicIndex(2047)), None);
+    assert!(V1_WORDLIST.lookup_mnemonic("abandon").is_err());
+
+    // 24 words phrases encode 32 bytes
+    let bytes: [u8; 32] = core::array::from_fn(|i| (i * 41) as u8);
+    let mnemonics = V1Mnemonics::<24>::from_bytes(&bytes).unwrap();
+    let parsed = V1Mnemonics::<24>::from_string(&format!("{}", mnemonics)).unwrap();
+    assert_eq!(parsed.to_bytes(), Ok(bytes));
+
+    // the largest 3 words value doesn't fit in 32 bits
+    let overflow = V1Mnemonics::<12>::from_string(
+        "like just love weary weapon unseen like just love like just love",
+    )
+    .unwrap();
+    assert_eq!(overflow.to_bytes::<16>(), Err(V1Error::Overflow(1)));
+    assert_eq!(
+        mnemonics.to_bytes::<16>(),
+        Err(V1Error::InvalidParameters {
+            bytes: 16,
+            words: 24
+        })
+    );
+    assert!(matches!(
+        V1Mnemonics::<12>::from_string("hardly point goal"),
+        Err(MnemonicError::InvalidWords {
+            expected_words: 12,
+            got_words: 3
+        })
+    ));
+    assert!(matches!(
+        V1Mnemonics::<12>::from_string(&phrase.replace("goal", "abandon")),
+        Err(MnemonicError::WordError { index: 2, .. })
+    ));
+}
+
+#[test]
+#[cfg(all(feature = "aezeed", feature = "english"))]
+fn aezeed_seeds() {
+    use aezeed::{AezeedError, CipherSeed};
+
+    // the version, salt and CRC32-C of the LND test phrases
+    for phrase in [
+        "ability liquid travel stem barely drastic pact cupboard apple thrive morning oak feature tissue couch old math inform success suggest drink motion know royal",
+        "able tree stool crush transfer cloud cross three profit outside hen citizen plate ride require leg siren drum success suggest drink require fiscal upgrade",
+    ] {
+        let mnemonics = Mnemonics::<24>::from_string(&ENGLISH, phrase).unwrap();
+        assert_eq!(aezeed::salt(&mnemonics).unwrap(), *b"salt1");
+        let mut indices: [MnemonicIndex; 24] = core::array::from_fn(|i| *mnemonics.indices().nth(i).unwrap());
+        indices[5] = MnemonicIndex(indices[5].0 ^ 1);
+        assert!(matches!(
+            aezeed::salt(&Mnemonics::from(indices)),
+            Err(AezeedError::InvalidChecksum)
+        ));
+    }
+    assert_eq!(CipherSeed::birthday_from_timestamp(1521799345), 3365);
+    assert_eq!(CipherSeed::birthday_from_timestamp(0), 0);
+
+    let seed = CipherSeed {
+        internal_version: 0,
+        birthday: 3365,
+        entropy: Entropy(core::array::from_fn(|i| 7 * i as u8)),
+        salt: *b"salt2",
+    };
+    assert_eq!(seed.birthday_timestamp(), 1521742505);
+    let mnemonics = seed.to_mnemonics(b"").unwrap();
+    assert_eq!(aezeed::salt(&mnemonics).unwrap(), seed.salt);
+    assert_eq!(CipherSeed::from_mnemonics(&mnemonics, b"").unwrap(), seed);
+    // the empty passphrase is the default one
+    assert_eq!(
+        CipherSeed::from_mnemonics(&mnemonics, b"aezeed").unwrap(),
+        seed
+    );
+    assert!(matches!(
+        CipherSeed::from_mnemonics(&mnemonics, b"wrong"),
+        Err(AezeedError::InvalidPassphrase)
+    ));
+
+    let seed = CipherSeed {
+        internal_version: 1,
+        birthday: u16::MAX,
+        entropy: Entropy([0xff; 16]),
+        salt: [0; 5],
+    };
+    let mnemonics = seed.to_mnemonics(b"!very_safe_55345_password*").unwrap();
+    let recovered = CipherSeed::from_mnemonics(&mnemonics, b"!very_safe_55345_password*");
+    assert_eq!(recovered.unwrap(), seed);
+}
+
+#[test]
+#[cfg(all(feature = "aezeed", feature = "english"))]
+fn aezeed_lnd_vectors() {
+    use aezeed::CipherSeed;
+
+    // LND aezeed version 0 test vectors, enciphered with the scrypt cost N = 16
+    let vectors: [(&str, &[u8], u16); 2] = [
+        (
+            "ability liquid travel stem barely drastic pact cupboard apple thrive morning oak feature tissue couch old math inform success suggest drink motion know royal",
+            b"",
+            0,
+        ),
+        (
+            "able tree stool crush transfer cloud cross three profit outside hen citizen plate ride require leg siren drum success suggest drink require fiscal upgrade",
+            b"!very_safe_55345_password*",
+            3365,
+        ),
+    ];
+    for (phrase, passphrase, birthday) in vectors {
+        let seed = CipherSeed {
+            internal_version: 0,
+            birthday,
+            entropy: Entropy([
+                0x81, 0xb6, 0x37, 0xd8, 0x63, 0x59, 0xe6, 0x96, 0x0d, 0xe7, 0x95, 0xe4, 0x1e, 0x0b,
+                0x4c, 0xfd,
+            ]),
+            salt: *b"salt1",
+        };
+        let mnemonics = Mnemonics::<24>::from_string(&ENGLISH, phrase).unwrap();
+        assert_eq!(
+            CipherSeed::from_mnemonics_with_cost(&mnemonics, passphrase, 4).unwrap(),
+            seed
+        );
+        assert_eq!(
+            seed.to_mnemonics_with_cost(passphrase, 4).unwrap(),
+            mnemonics
+        );
+        // the LND cost isn't the one of the test vectors
+        assert!(CipherSeed::from_mnemonics(&mnemonics, passphrase).is_err());
+    }
+}
+
+#[test]
+#[cfg(all(feature = "aezeed", feature = "rand"))]
+fn aezeed_generate() {
+    use aezeed::CipherSeed;
+
+    let seed = CipherSeed::generate(3365, &mut CounterRng(1)).unwrap();
+    assert_eq!(seed.internal_version, 0);
+    assert_eq!(seed.birthday, 3365);
+    assert_eq!(seed.entropy.0, core::array::from_fn(|i| i as u8 + 1));
+    assert_eq!(seed.salt, [17, 18, 19, 20, 21]);
+    assert!(CipherSeed::generate(0, &mut CounterRng(0xff)).is_err());
+}
+
+#[test]
+#[cfg(all(feature = "cardano", feature = "english"))]
+fn cardano_root_keys() {
+    // CIP-3 test vectors
+    let phrase = "eight country switch draw meat scout mystery blade tip drift useless good keep usage title";
+    let mnemonics = Mnemonics::<15>::from_string(&ENGLISH, phrase).unwrap();
+    let entropy = Entropy::<20>::from_mnemonics::<15, 5>(&mnemonics).unwrap();
+    assert_eq!(
+        hex::encode(cardano::icarus(&entropy, b"").as_bytes()),
+        "c065afd2832cd8b087c4d9ab7011f481ee1e0721e78ea5dd609f3ab3f156d245d176bd8fd4ec60b4731c3918a2a72a0226c0cd119ec35b47e4d55884667f552a23f7fdcd4a10c6cd2c7393ac61d877873e248f417634aa3d812af327ffe9d620"
+    );
+    assert_eq!(
+        hex::encode(cardano::icarus(&entropy, b"foo").as_bytes()),
+        "70531039904019351e1afb361cd1b312a4d0565d4ff9f8062d38acf4b15cce41d7b5738d9c893feea55512a3004acb0d222c35d3e3d5cde943a15a9824cbac59443cf67e589614076ba01e354b1a432e0e6db3b59e37fc56b5fb0222970a010e"
+    );
+
+    let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon art";
+    let mnemonics = Mnemonics::<24>::from_string(&ENGLISH, phrase).unwrap();
+    assert_eq!(
+        hex::encode(cardano::ledger(&ENGLISH, &mnemonics, b"foo").as_bytes()),
+        "f053a1e752de5c26197b60f032a4809f08bb3e5d90484fe42024be31efcba7578d914d3ff992e21652fee6a4d99f6091006938fac2c0c0f9d2de0ba64b754e92a4f3723f23472077aa4cd4dd8a8a175dba07ea1852dad1cf268c61a2679c3890"
+    );
+
+    // the three derivations give valid and different extended secret keys
+    let phrase = "roast crime bounce convince core happy pitch safe brush exit basic among";
+    let mnemonics = Mnemonics::<12>::from_string(&ENGLISH, phrase).unwrap();
+    let entropy = Entropy::<16>::from_mnemonics::<12, 4>(&mnemonics).unwrap();
+    let keys = [
+        cardano::icarus(&entropy, b""),
+        cardano::ledger(&ENGLISH, &mnemonics, b""),
+        cardano::byron(&entropy),
+    ];
+    for (i, key) in keys.iter().enumerate() {
+        let bytes = key.as_bytes();
+        assert_eq!(bytes[0] & 0b0000_0111, 0);
+        assert_eq!(bytes[31] & 0b1110_0000, 0b0100_0000);
+        for other in &keys[i + 1..] {
+            assert_ne!(key, other);
+        }
+    }
+
+    // CIP-3 Byron example
+    assert_eq!(
+        hex::encode(keys[2].as_bytes()),
+        "60f6e2b12f4c51ed2a42163935fd95a6c39126e88571fe5ffd0332a4924e5e5e9ceda72e3e526a625ea86d16151957d45747fff0f8fcd00e394b132155dfdfc2918019cda35f1df96dd5a798da4c40a2f382358496e6468e4e276db5ec35235f"
+    );
+    // found at the 5th iteration ("Root Seed Chain 5")
+    assert_eq!(
+        hex::encode(cardano::byron(&Entropy([2; 16])).as_bytes()),
+        "b0ad4f4629972ee14fc67385191f36d76eb3f6d2f6049298d3aad8c153ad7a5d0c43cbe13ff775cfb996c9e1f6921c246a89636d95e15f70fa62e61a43cd0e1c103e3bb3f9397782eaca2cab62b157d2ee3c9d81cbc8bde270ea9b5ac54487fc"
+    );
+}
+
+#[test]
+#[cfg(all(feature = "algorand", feature = "english"))]
+fn algorand_mnemonics() {
+    let zero = Entropy([0u8; 32]);
+    let mnemonics = algorand::to_mnemonics(&zero).unwrap();
+    let mut expected = [MnemonicIndex(0); 25];
+    expected[24] = MnemonicIndex::from_word(&ENGLISH, "invest").unwrap();
+    assert_eq!(mnemonics, Mnemonics::from(expected));
+    assert_eq!(algorand::from_mnemonics(&mnemonics).unwrap(), zero);
+
+    let phrase = "advice pudding treat near rule blouse same whisper inner electric quit surface sunny dismiss leader blood seat clown cost exist hospital century reform able sponsor";
+    let mnemonics = Mnemonics::<25>::from_string(&ENGLISH, phrase).unwrap();
+    let key = algorand::from_mnemonics(&mnemonics).unwrap();
+    assert_eq!(
+        hex::encode(key.0),
+        "2048abcf3999de60dc97faa3db915fa3bd6cfdd0ef1712064b61fcf4b6958856"
+    );
+    assert_eq!(algorand::to_mnemonics(&key).unwrap(), mnemonics);
+
+    // wrong checksum word
+    expected[24] = MnemonicIndex::from_word(&ENGLISH, "venue").unwrap();
+    assert!(matches!(
+        algorand::from_mnemonics(&Mnemonics::from(expected)),
+        Err(EntropyError::ChecksumInvalid)
+    ));
+    // non zero padding bits in the 24th word
+    expected[23] = MnemonicIndex(0b1000);
+    expected[24] = MnemonicIndex::from_word(&ENGLISH, "invest").unwrap();
+    assert!(matches!(
+        algorand::from_mnemonics(&Mnemonics::from(expected)),
+        Err(EntropyError::ChecksumInvalid)
+    ));
+}
+
+#[test]
+fn physical_required() {
+    // the roll counts of Coldcard
+    assert_eq!(Entropy::<16>::dice_rolls_required(), Some(50));
+    assert_eq!(Entropy::<32>::dice_rolls_required(), Some(99));
+    assert_eq!(Entropy::<33>::dice_rolls_required(), None);
+    assert_eq!(Entropy::<16>::dice_rolls_base6_required(), Some(50));
+    assert_eq!(Entropy::<32>::dice_rolls_base6_required(), Some(100));
+    assert_eq!(Entropy::<33>::dice_rolls_base6_required(), None);
+    assert_eq!(Entropy::<32>::coin_flips_required(), 256);
+    assert_eq!(Entropy::<16>::card_draws_required(), Some(30));
+    assert_eq!(Entropy::<28>::card_draws_required(), Some(52));
+    assert_eq!(Entropy::<29>::card_draws_required(), None);
+}
+
+#[test]
+fn physical_dice() {
+    // SHA256 of the rolls as ASCII digits ("123456123456..."), computed
+    // independently of this crate
+    let mut rolls = [0u8; 100];
+    for (i, r) in rolls.iter_mut().enumerate() {
+        *r = (i % 6) as u8 + 1;
+    }
+    let entropy = Entropy::<32>::from_dice_rolls(&rolls).unwrap();
+    assert_eq!(
+        hex::encode(entropy.0),
+        "e56403e8522ddeae1b44a1e8148b1ba4d3b4c626ccf20980056eedcc7e0c0f35"
+    );
+    let entropy = Entropy::<16>::from_dice_rolls(&rolls[0..50]).unwrap();
+    assert_eq!(hex::encode(entropy.0), "ee72ae915a4e6ea7ccbeb8e5e5eecef2");
+
+    // 99 rolls are enough for 24 words, as in Coldcard
+    let entropy = Entropy::<32>::from_dice_rolls(&rolls[0..99]).unwrap();
+    assert_eq!(
+        hex::encode(entropy.0),
+        "5588d3630bd19f6375b7bd922457af34ea9c74f00807566a1cf808e445dc8c20"
+    );
+    let err = Entropy::<32>::from_dice_rolls(&[1; 98]).unwrap_err();
+    assert_eq!(
+        err,
+        PhysicalEntropyError::NotEnoughEntropy {
+            required: 99,
+            got: 98
+        }
+    );
+    assert_eq!(err.remaining(), Some(1));
+
+    // the rolls as a base 6 number modulo 2^(N*8), computed independently of
+    // this crate
+    let entropy = Entropy::<32>::from_dice_rolls_base6(&rolls).unwrap();
+    assert_eq!(
+        hex::encode(entropy.0),
+        "5a6e97d56593b0471699027bb22614724ff7c3bf31af81237958cef90f0fe296"
+    );
+    let entropy = Entropy::<16>::from_dice_rolls_base6(&rolls[0..50]).unwrap();
+    assert_eq!(hex::encode(entropy.0), "91d89c79009852800bffccdc406b1ba0");
+    assert_eq!(
+        Entropy::<32>::from_dice_rolls_base6(&rolls[0..99]),
+        Err(PhysicalEntropyError::NotEnoughEntropy {
+            required: 100,
+            got: 99
+        })
+    );
+    // 6 is the digit 0
+    assert_eq!(
+        Entropy::<2>::from_dice_rolls_base6(&[6, 1, 6, 6, 6, 6, 6]).unwrap(),
+        Entropy([0x1e, 0x60])
+    );
+
+    rolls[10] = 7;
+    assert_eq!(
+        Entropy::<32>::from_dice_rolls(&rolls),
+        Err(PhysicalEntropyError::InvalidValue { index: 10 })
+    );
+    assert_eq!(
+        Entropy::<32>::from_dice_rolls_base6(&rolls),
+        Err(PhysicalEntropyError::InvalidValue { index: 10 })
+    );
+}
+
+#[test]
+fn physical_coins() {
+    let mut flips = [false; 17];
+    flips[0] = true;
+    flips[15] = true;
+    let entropy = Entropy::<2>::from_coin_flips(&flips).unwrap();
+    assert_eq!(entropy.0, [0x80, 0x01]);
+    assert_eq!(
+        Entropy::<4>::from_coin_flips(&flips)
+            .unwrap_err()
+            .remaining(),
+        Some(15)
+    );
+}
+
+#[test]
+fn physical_cards() {
+    // bits 2 to 130 of the SHA256 of
+    // "AC8C2D9D3HTH4SJS5CQC6DKD7HAS8S2C9C3DTD4HJH5SQS6CKC7DAH8H2S9S:0",
+    // computed independently of this crate
+    let deck: [u8; 52] = core::array::from_fn(|i| (i * 7 % 52) as u8);
+    let entropy = Entropy::<16>::from_card_draws(&deck[0..30]).unwrap();
+    assert_eq!(hex::encode(entropy.0), "2b2096d1322a6b1d96ae8472a9be7f2a");
+    // the last 128 of the 173 bits counted for 40 cards
+    let entropy = Entropy::<16>::from_card_draws(&deck[0..40]).unwrap();
+    assert_eq!(hex::encode(entropy.0), "20fe851ee3ab085c20467b598516efd7");
+    // a full deck, 225 bits
+    let entropy = Entropy::<28>::from_card_draws(&deck).unwrap();
+    assert_eq!(
+        hex::encode(entropy.0),
+        "f1ac042ed17e6e6c56ef41b8b4f38f545748a8d465757041346b6e7e"
+    );
+    assert_eq!(
+        Entropy::<16>::from_card_draws(&deck[0..29]),
+        Err(PhysicalEntropyError::NotEnoughEntropy {
+            required: 30,
+            got: 29
+        })
+    );
+
+    let mut dup = deck;
+    dup[5] = dup[1];
+    assert_eq!(
+        Entropy::<16>::from_card_draws(&dup[0..30]),
+        Err(PhysicalEntropyError::DuplicateCard { index: 5 })
+    );
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn physical_error_counts() {
+    // an error built by hand with more values than required doesn't underflow
+    let err = PhysicalEntropyError::NotEnoughEntropy {
+        required: 1,
+        got: 5,
+    };
+    assert_eq!(err.remaining(), Some(0));
+    assert_eq!(
+        err.to_string(),
+        "Not enough entropy, 0 more values needed (1 required)"
+    );
+}
+
+#[test]
+fn weak_entropy() {
+    let report = Entropy::<16>([0x7f; 16]).analyze();
+    assert_eq!(report.weaknesses().next(), Some(&Weakness::Repeated));
+
+    let mut pattern = [0u8; 16];
+    for (i, b) in pattern.iter_mut().enumerate() {
+        *b = [0x12, 0x34, 0x56][i % 3];
+    }
+    let report = Entropy::<16>(pattern).analyze();
+    assert!(report
+        .weaknesses()
+        .any(|w| *w == Weakness::RepeatedPattern { period: 3 }));
+
+    let sequence = core::array::from_fn(|i| i as u8);
+    let report = Entropy::<16>(sequence).analyze();
+    assert!(report.weaknesses().any(|w| *w == Weakness::Sequence));
+    // too short to be a meaningful sequence: any 2 values follow one
+    assert!(!Entropy::<2>([0x3a, 0xd1]).analyze().is_weak());
+    assert!(!Entropy::<5>([0x10, 0x20, 0x30, 0x40, 0x50])
+        .analyze()
+        .weaknesses()
+        .any(|w| *w == Weakness::Sequence));
+
+    let vector =
+        Entropy::<16>::from_slice(&hex::decode("0460ef47585604c5660618db2e6a7e7f").unwrap())
+            .unwrap();
+    assert_eq!(
+        vector.analyze().weaknesses().collect::<std::vec::Vec<_>>(),
+        [&Weakness::KnownTestVector]
+    );
+
+    let mut sparse = [0u8; 16];
+    sparse[3] = 0x81;
+    sparse[11] = 0x10;
+    sparse[14] = 0x03;
+    assert!(Entropy::<16>(sparse)
+        .analyze()
+        .weaknesses()
+        .any(|w| *w == Weakness::UnbalancedBits { ones: 5, bits: 128 }));
+
+    let random = Entropy::<16>([
+        0x3a, 0x91, 0x5c, 0xe2, 0x07, 0xb8, 0x4f, 0xd3, 0x66, 0x1e, 0xa5, 0x72, 0xc9, 0x08, 0xfb,
+        0x34,
+    ]);
+    assert!(!random.analyze().is_weak());
+}
+
+#[test]
+#[cfg(feature = "english")]
+fn weak_mnemonics() {
+    let zero = Mnemonics::<12>::from_string(
+        &ENGLISH,
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+    )
+    .unwrap();
+    let report = zero.analyze::<4>();
+    assert_eq!(report.weaknesses().next(), Some(&Weakness::Repeated));
+    assert!(report.weaknesses().any(|w| *w == Weakness::KnownTestVector));
+
+    let legal = Mnemonics::<18>::from_string(
+        &ENGLISH,
+        "legal winner thank year wave sausage worth useful legal winner thank year wave sausage worth useful legal will",
+    )
+    .unwrap();
+    let report = legal.analyze::<6>();
+    assert!(report
+        .weaknesses()
+        .any(|w| *w == Weakness::RepeatedPattern { period: 8 }));
+    assert!(report.weaknesses().any(|w| *w == Weakness::KnownTestVector));
+
+    let vector = Mnemonics::<12>::from_string(
+        &ENGLISH,
+        "afford alter spike radar gate glance object seek swamp infant panel yellow",
+    )
+    .unwrap();
+    assert_eq!(
+        vector
+            .analyze::<4>()
+            .weaknesses()
+            .collect::<std::vec::Vec<_>>(),
+        [&Weakness::KnownTestVector]
+    );
+    // not a test vector with another checksum size
+    assert!(!vector.analyze::<0>().is_weak());
+
+    // only the words carrying checksum bits are left out of the word checks
+    let mut words = [MnemonicIndex(0); 12];
+    words[11] = MnemonicIndex(2047);
+    let mnemonics = Mnemonics::from(words);
+    assert_eq!(
+        mnemonics.analyze::<4>().weaknesses().next(),
+        Some(&Weakness::Repeated)
+    );
+    assert_eq!(
+        mnemonics.analyze::<0>().weaknesses().next(),
+        Some(&Weakness::FewDistinctValues { distinct: 2 })
+    );
+    words[10] = MnemonicIndex(2047);
+    assert_eq!(
+        Mnemonics::from(words).analyze::<12>().weaknesses().next(),
+        Some(&Weakness::Repeated)
+    );
+}
+
+#[cfg(feature = "rand")]
+struct CounterRng(u8);
+
+#[cfg(feature = "rand")]
+impl rand_core::RngCore for CounterRng {
+    fn next_u32(&mut self) -> u32 {
+        rand_core::impls::next_u32_via_fill(self)
+    }
+    fn next_u64(&mut self) -> u64 {
+        rand_core::impls::next_u64_via_fill(self)
+    }
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.try_fill_bytes(dest).unwrap()
+    }
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        if self.0 == 0xff {
+            return Err(core::num::NonZeroU32::new(rand_core::Error::CUSTOM_START)
+                .unwrap()
+                .into());
+        }
+        for b in dest {
+            *b = self.0;
+            self.0 = self.0.wrapping_add(1);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "rand")]
+impl rand_core::CryptoRng for CounterRng {}
+
+// a never failing generator with a full period, for the splits
+#[cfg(feature = "rand")]
+pub(crate) struct StepRng(pub(crate) u8);
+
+#[cfg(feature = "rand")]
+impl rand_core::RngCore for StepRng {
+    fn next_u32(&mut self) -> u32 {
+        rand_core::impls::next_u32_via_fill(self)
+    }
+    fn next_u64(&mut self) -> u64 {
+        rand_core::impls::next_u64_via_fill(self)
+    }
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for b in dest {
+            self.0 = self.0.wrapping_mul(13).wrapping_add(7);
+            *b = self.0;
+        }
+    }
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "rand")]
+impl rand_core::CryptoRng for StepRng {}
+
+#[cfg(all(feature = "electrum", feature = "english", feature = "rand"))]
+struct ZeroRng;
+
+#[cfg(all(feature = "electrum", feature = "english", feature = "rand"))]
+impl rand_core::RngCore for ZeroRng {
+    fn next_u32(&mut self) -> u32 {
+        0
+    }
+    fn next_u64(&mut self) -> u64 {
+        0
+    }
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        dest.fill(0)
+    }
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        dest.fill(0);
+        Ok(())
+    }
+}
+
+#[cfg(all(feature = "electrum", feature = "english", feature = "rand"))]
+impl rand_core::CryptoRng for ZeroRng {}
+
+#[test]
+#[cfg(feature = "rand")]
+fn generate_from_rng() {
+    let entropy = Entropy::<4>::generate_from_rng(&mut CounterRng(1)).unwrap();
+    assert_eq!(entropy.0, [1, 2, 3, 4]);
+    assert!(Entropy::<4>::generate_from_rng(&mut CounterRng(0xff)).is_err());
+
+    let mnemonics = Mnemonics::<15>::generate_from_rng(&mut CounterRng(0)).unwrap();
+    let entropy = Entropy::<20>::from_mnemonics::<15, 5>(&mnemonics).unwrap();
+    assert_eq!(entropy.0[..4], [0, 1, 2, 3]);
+
+    assert!(matches!(
+        Mnemonics::<13>::generate_from_rng(&mut CounterRng(0)),
+        Err(GenerateError::NonStandardWords { words: 13 })
+    ));
+    assert!(matches!(
+        Mnemonics::<12>::generate_from_rng(&mut CounterRng(0xff)),
+        Err(GenerateError::Rng(_))
+    ));
+}
+
+#[test]
+#[cfg(all(feature = "electrum", feature = "english", feature = "rand"))]
+fn electrum_generate() {
+    use electrum::SeedType;
+
+    let dic = &dictionary::ENGLISH;
+    let mut rng = CounterRng(11);
+    for seed_type in [
+        SeedType::Standard,
+        SeedType::Segwit,
+        SeedType::Standard2fa,
+        SeedType::Segwit2fa,
+    ] {
+        let mnemonics = electrum::generate::<_, _, 12>(dic, seed_type, &mut rng).unwrap();
+        assert_eq!(electrum::seed_type(dic, &mnemonics), Some(seed_type));
+        assert!(mnemonics.check_standard_checksum().is_err());
+    }
+
+    // same grinding as Electrum, from the smallest 12 words number
+    let mnemonics = electrum::generate::<_, _, 12>(dic, SeedType::Standard, &mut ZeroRng).unwrap();
+    assert_eq!(
+        format!("{}", mnemonics.display(dic)),
+        "boss abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon ability"
+    );
+
+    assert!(matches!(
+        electrum::generate::<_, _, 0>(dic, SeedType::Standard, &mut ZeroRng),
+        Err(GenerateError::NonStandardWords { words: 0 })
+    ));
+    assert!(matches!(
+        electrum::generate::<_, _, 12>(dic, SeedType::Standard, &mut CounterRng(0xff)),
+        Err(GenerateError::Rng(_))
+    ));
+}
+
 #[derive(Debug)]
 struct TestVector<const W: usize, const N: usize, const CS: usize> {
     entropy: &'static str,
@@ -39,12 +2101,15 @@ fn mk_test<D: dictionary::Language, const W: usize, const N: usize, const CS: us
     let passphrase: String = test.passphrase.nfkd().collect();
 
     let mnemonics_ref = Mnemonics::<W>::from_string(dic, &mnemonics).expect("valid mnemonics");
+    #[cfg(feature = "alloc")]
     let mnemonics_str = mnemonics_ref.to_string(dic);
     let entropy_ref = Entropy::<N>::from_slice(&hex::decode(test.entropy).unwrap())
         .expect("decode entropy from hex");
     let seed_ref = hex::decode(test.seed).unwrap();
 
+    #[cfg(feature = "alloc")]
     assert_eq!(&mnemonics, &mnemonics_str);
+    assert_eq!(mnemonics, format!("{}", mnemonics_ref.display(dic)));
 
     assert!(
         entropy_ref
@@ -74,9 +2139,9 @@ fn test_vectors_english() {
     let dic = &dictionary::ENGLISH;
     for test in TEST_VECTORS_ENGLISH {
         match test {
-            TV::T12(tv) => mk_test(&tv, dic),
-            TV::T18(tv) => mk_test(&tv, dic),
-            TV::T24(tv) => mk_test(&tv, dic),
+            TV::T12(tv) => mk_test(tv, dic),
+            TV::T18(tv) => mk_test(tv, dic),
+            TV::T24(tv) => mk_test(tv, dic),
         }
     }
 }
@@ -87,9 +2152,9 @@ fn test_vectors_japanese() {
     let dic = &dictionary::JAPANESE;
     for test in TEST_VECTORS_JAPANESE {
         match test {
-            TV::T12(tv) => mk_test(&tv, dic),
-            TV::T18(tv) => mk_test(&tv, dic),
-            TV::T24(tv) => mk_test(&tv, dic),
+            TV::T12(tv) => mk_test(tv, dic),
+            TV::T18(tv) => mk_test(tv, dic),
+            TV::T24(tv) => mk_test(tv, dic),
         }
     }
 }